@@ -43,14 +43,19 @@ pub async fn reset(
     migration_source: &str,
     connect_opts: &ConnectOpts,
     confirm: bool,
+    locking: bool,
 ) -> anyhow::Result<()> {
     drop(connect_opts, confirm).await?;
-    setup(migration_source, connect_opts).await
+    setup(migration_source, connect_opts, locking).await
 }
 
-pub async fn setup(migration_source: &str, connect_opts: &ConnectOpts) -> anyhow::Result<()> {
+pub async fn setup(
+    migration_source: &str,
+    connect_opts: &ConnectOpts,
+    locking: bool,
+) -> anyhow::Result<()> {
     create(connect_opts).await?;
-    migrate::run(migration_source, connect_opts, false, false, None).await
+    migrate::run(migration_source, connect_opts, false, false, locking, None).await
 }
 
 fn ask_to_continue(connect_opts: &ConnectOpts) -> bool {
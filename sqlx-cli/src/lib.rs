@@ -34,6 +34,7 @@ pub async fn run(opt: Opt) -> Result<()> {
                 source,
                 dry_run,
                 ignore_missing,
+                no_lock,
                 connect_opts,
                 target_version,
             } => {
@@ -42,6 +43,7 @@ pub async fn run(opt: Opt) -> Result<()> {
                     &connect_opts,
                     dry_run,
                     *ignore_missing,
+                    !no_lock,
                     target_version,
                 )
                 .await?
@@ -50,6 +52,7 @@ pub async fn run(opt: Opt) -> Result<()> {
                 source,
                 dry_run,
                 ignore_missing,
+                no_lock,
                 connect_opts,
                 target_version,
             } => {
@@ -58,6 +61,7 @@ pub async fn run(opt: Opt) -> Result<()> {
                     &connect_opts,
                     dry_run,
                     *ignore_missing,
+                    !no_lock,
                     target_version,
                 )
                 .await?
@@ -78,12 +82,14 @@ pub async fn run(opt: Opt) -> Result<()> {
             DatabaseCommand::Reset {
                 confirmation,
                 source,
+                no_lock,
                 connect_opts,
-            } => database::reset(&source, &connect_opts, !confirmation.yes).await?,
+            } => database::reset(&source, &connect_opts, !confirmation.yes, !no_lock).await?,
             DatabaseCommand::Setup {
                 source,
+                no_lock,
                 connect_opts,
-            } => database::setup(&source, &connect_opts).await?,
+            } => database::setup(&source, &connect_opts, !no_lock).await?,
         },
 
         Command::Prepare {
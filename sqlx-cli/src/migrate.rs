@@ -264,6 +264,7 @@ pub async fn run(
     connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
+    locking: bool,
     target_version: Option<i64>,
 ) -> anyhow::Result<()> {
     let migrator = Migrator::new(Path::new(migration_source)).await?;
@@ -275,6 +276,13 @@ pub async fn run(
 
     let mut conn = crate::connect(connect_opts).await?;
 
+    // lock the database for exclusive access, like `Migrator` does, so that this
+    // doesn't race with an application (or another instance of this tool) migrating it;
+    // if anything fails, the lock is released along with the connection
+    if locking {
+        conn.lock().await?;
+    }
+
     conn.ensure_migrations_table().await?;
 
     let version = conn.dirty_version().await?;
@@ -344,6 +352,10 @@ pub async fn run(
         }
     }
 
+    if locking {
+        conn.unlock().await?;
+    }
+
     // Close the connection before exiting:
     // * For MySQL and Postgres this should ensure timely cleanup on the server side,
     //   including decrementing the open connection count.
@@ -359,6 +371,7 @@ pub async fn revert(
     connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
+    locking: bool,
     target_version: Option<i64>,
 ) -> anyhow::Result<()> {
     let migrator = Migrator::new(Path::new(migration_source)).await?;
@@ -370,6 +383,11 @@ pub async fn revert(
 
     let mut conn = crate::connect(&connect_opts).await?;
 
+    // see `run()`
+    if locking {
+        conn.lock().await?;
+    }
+
     conn.ensure_migrations_table().await?;
 
     let version = conn.dirty_version().await?;
@@ -444,6 +462,10 @@ pub async fn revert(
         println!("No migrations available to revert");
     }
 
+    if locking {
+        conn.unlock().await?;
+    }
+
     let _ = conn.close().await;
 
     Ok(())
@@ -86,6 +86,9 @@ pub enum DatabaseCommand {
         #[clap(flatten)]
         source: Source,
 
+        #[clap(flatten)]
+        no_lock: NoLock,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
     },
@@ -95,6 +98,9 @@ pub enum DatabaseCommand {
         #[clap(flatten)]
         source: Source,
 
+        #[clap(flatten)]
+        no_lock: NoLock,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
     },
@@ -157,6 +163,9 @@ pub enum MigrateCommand {
         #[clap(flatten)]
         ignore_missing: IgnoreMissing,
 
+        #[clap(flatten)]
+        no_lock: NoLock,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
 
@@ -178,6 +187,9 @@ pub enum MigrateCommand {
         #[clap(flatten)]
         ignore_missing: IgnoreMissing,
 
+        #[clap(flatten)]
+        no_lock: NoLock,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
 
@@ -283,3 +295,28 @@ impl Not for IgnoreMissing {
         !self.ignore_missing
     }
 }
+
+/// Argument for not locking the database while migrating.
+#[derive(Args, Copy, Clone, Debug)]
+pub struct NoLock {
+    /// Do not lock the database while migrating. Only use this if the database does not
+    /// support locking (e.g. CockroachDB) and nothing else migrates it at the same time.
+    #[clap(long)]
+    no_lock: bool,
+}
+
+impl Deref for NoLock {
+    type Target = bool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.no_lock
+    }
+}
+
+impl Not for NoLock {
+    type Output = bool;
+
+    fn not(self) -> Self::Output {
+        !self.no_lock
+    }
+}
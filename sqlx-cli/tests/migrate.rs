@@ -1,5 +1,6 @@
 mod common;
 
+use assert_cmd::Command;
 use common::TestDatabase;
 
 #[tokio::test]
@@ -145,3 +146,27 @@ async fn revert_migrations() {
         assert_eq!(db.applied_migrations().await, vec![] as Vec<i64>);
     }
 }
+
+#[tokio::test]
+async fn run_and_revert_migrations_without_locking() {
+    let db = TestDatabase::new("migrate_no_lock", "migrations_reversible");
+
+    for (command, applied) in [("run", 5), ("revert", 4)] {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "migrate",
+                command,
+                "--no-lock",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_reversible",
+            ])
+            .assert()
+            .success();
+
+        assert_eq!(db.applied_migrations().await.len(), applied);
+    }
+}
@@ -1,9 +1,12 @@
 use crate::any::{Any, AnyArguments, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
 use crate::describe::Describe;
+use crate::transaction::TransactionOptions;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use std::fmt::Debug;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// The backend name.
@@ -30,6 +33,19 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
     fn begin(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
+    /// Begin a new transaction with the given options, or establish a savepoint within the
+    /// active transaction if the options are the default.
+    ///
+    /// The default implementation only accepts the default options.
+    fn begin_with(&mut self, options: TransactionOptions) -> BoxFuture<'_, crate::Result<()>> {
+        if options == TransactionOptions::default() {
+            self.begin()
+        } else {
+            let message = format!("transaction options are not supported by {}", self.name());
+            Box::pin(async move { Err(crate::Error::Configuration(message.into())) })
+        }
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
     fn rollback(&mut self) -> BoxFuture<'_, crate::Result<()>>;
@@ -58,6 +74,21 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    #[doc(hidden)]
+    fn before_release(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        self.ping()
+    }
+
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        false
+    }
+
+    #[doc(hidden)]
+    fn set_poison_flag(&mut self, flag: Arc<AtomicBool>) {
+        let _ = flag;
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(&mut self) -> crate::Result<&mut (dyn crate::migrate::Migrate + Send + 'static)> {
         Err(crate::Error::Configuration(
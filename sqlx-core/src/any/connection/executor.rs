@@ -1,7 +1,7 @@
 use crate::any::{Any, AnyConnection, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{run_until, stream_until, Execute, Executor};
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
@@ -18,7 +18,10 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
         E: Execute<'q, Any>,
     {
         let arguments = query.take_arguments();
-        self.backend.fetch_many(query.sql(), arguments)
+        stream_until(
+            query.deadline(),
+            self.backend.fetch_many(query.sql(), arguments),
+        )
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -30,7 +33,10 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
         E: Execute<'q, Self::Database>,
     {
         let arguments = query.take_arguments();
-        self.backend.fetch_optional(query.sql(), arguments)
+        Box::pin(run_until(
+            query.deadline(),
+            self.backend.fetch_optional(query.sql(), arguments),
+        ))
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -7,7 +7,7 @@ use crate::error::Error;
 use crate::database::Database;
 pub use backend::AnyConnectionBackend;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 
 mod backend;
 mod executor;
@@ -90,6 +90,16 @@ impl Connection for AnyConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.backend.cached_statements_size()
     }
@@ -111,4 +121,19 @@ impl Connection for AnyConnection {
     fn should_flush(&self) -> bool {
         self.backend.should_flush()
     }
+
+    #[doc(hidden)]
+    fn before_release(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.backend.before_release()
+    }
+
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        self.backend.is_broken()
+    }
+
+    #[doc(hidden)]
+    fn set_poison_flag(&mut self, flag: std::sync::Arc<std::sync::atomic::AtomicBool>) {
+        self.backend.set_poison_flag(flag)
+    }
 }
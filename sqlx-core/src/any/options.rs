@@ -58,4 +58,9 @@ impl ConnectOptions for AnyConnectOptions {
         self.log_settings.slow_statements_duration = duration;
         self
     }
+
+    fn log_full_statements(mut self, enabled: bool) -> Self {
+        self.log_settings.full_statements = enabled;
+        self
+    }
 }
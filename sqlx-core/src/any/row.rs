@@ -60,7 +60,7 @@ impl Row for AnyRow {
             T::decode(value)
         }
         .map_err(|source| Error::ColumnDecode {
-            index: format!("{index:?}"),
+            index: crate::row::describe_index(self, &index),
             source,
         })
     }
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+/// An exponentially-weighted moving average of the round-trip latency of a connection.
+///
+/// Each new sample is weighted by 1/8, as with the smoothed round-trip time of TCP
+/// ([RFC 6298](https://www.rfc-editor.org/rfc/rfc6298)), so the average follows lasting changes
+/// in latency within a few dozen round trips while smoothing over the odd slow one.
+///
+/// The drivers take samples from both queries and pings, mixed into the same average, so a query
+/// the server spends a long time on (e.g. a large sort before the first row) raises it just like
+/// a slow network does. To compare connections by network latency alone, compare them while
+/// they are only being pinged, e.g. by a health check on otherwise idle connections.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LatencyEwma {
+    average: Option<Duration>,
+}
+
+impl LatencyEwma {
+    /// Fold a newly measured round trip into the average.
+    pub fn record(&mut self, sample: Duration) {
+        self.average = Some(match self.average {
+            // the first sample is the best estimate available
+            None => sample,
+            Some(average) => average - average / 8 + sample / 8,
+        });
+    }
+
+    /// The current average, or `None` if no round trip has been measured yet.
+    pub fn get(&self) -> Option<Duration> {
+        self.average
+    }
+}
+
+#[test]
+fn test_latency_ewma() {
+    let mut latency = LatencyEwma::default();
+    assert_eq!(latency.get(), None);
+
+    latency.record(Duration::from_millis(80));
+    assert_eq!(latency.get(), Some(Duration::from_millis(80)));
+
+    latency.record(Duration::from_millis(160));
+    assert_eq!(latency.get(), Some(Duration::from_millis(90)));
+
+    // converges on a lasting change
+    for _ in 0..100 {
+        latency.record(Duration::from_millis(8));
+    }
+
+    let average = latency.get().unwrap();
+    assert!(average < Duration::from_micros(8_100), "{average:?}");
+}
@@ -1,5 +1,7 @@
+mod latency_ewma;
 mod statement_cache;
 
+pub use latency_ewma::LatencyEwma;
 pub use statement_cache::StatementCache;
 use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
@@ -1,11 +1,13 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::fmt::Debug;
 use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -49,6 +51,38 @@ pub trait Connection: Send {
     where
         Self: Sized;
 
+    /// Begin a new transaction with the given isolation level and access mode.
+    ///
+    /// The options are sent to the database before the transaction's first statement runs.
+    /// With the default options this is the same as [`begin()`][Self::begin], and so it
+    /// establishes a savepoint if a transaction is already active; otherwise it is an error to
+    /// call this within a transaction. See [`TransactionOptions`] for which options each
+    /// database supports.
+    ///
+    /// The default implementation calls [`begin()`][Self::begin] for the default options and
+    /// returns [`Error::Configuration`] for any others.
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        if options == TransactionOptions::default() {
+            self.begin()
+        } else {
+            Box::pin(async move {
+                Err(Error::Configuration(
+                    format!(
+                        "transaction options are not supported by {}",
+                        <Self::Database as Database>::NAME
+                    )
+                    .into(),
+                ))
+            })
+        }
+    }
+
     /// Execute the function inside a transaction.
     ///
     /// If the function returns an error, the transaction will be rolled back. If it does not
@@ -132,6 +166,26 @@ pub trait Connection: Send {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Prepare the connection to be returned to a pool; an error means it should be closed.
+    #[doc(hidden)]
+    fn before_release(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.ping()
+    }
+
+    /// Returns `true` if an I/O or protocol error left the connection unusable; a pool closes
+    /// such connections instead of testing them.
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        false
+    }
+
+    /// Shut down the connection's socket, and fail every later use of it, once `flag` is set;
+    /// the pool sets it when [`Pool::close()`][crate::pool::Pool::close] times out.
+    #[doc(hidden)]
+    fn set_poison_flag(&mut self, flag: Arc<AtomicBool>) {
+        let _ = flag;
+    }
+
     /// Establish a new database connection.
     ///
     /// A value of [`Options`][Self::Options] is parsed from the provided connection string. This parsing
@@ -161,6 +215,7 @@ pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    pub full_statements: bool,
 }
 
 impl Default for LogSettings {
@@ -169,6 +224,7 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            full_statements: true,
         }
     }
 }
@@ -181,6 +237,10 @@ impl LogSettings {
         self.slow_statements_level = level;
         self.slow_statements_duration = duration;
     }
+
+    pub fn log_full_statements(&mut self, enabled: bool) {
+        self.full_statements = enabled;
+    }
 }
 
 pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug + Clone {
@@ -201,6 +261,17 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// at the specified `level`.
     fn log_slow_statements(self, level: LevelFilter, duration: Duration) -> Self;
 
+    /// Log the full text of statements alongside their summary (the default).
+    ///
+    /// A logged statement is summarized by its first few words; when this is disabled,
+    /// longer statements such as bulk inserts are logged with only that summary.
+    ///
+    /// The default implementation ignores this setting, for drivers which don't support it.
+    fn log_full_statements(self, enabled: bool) -> Self {
+        let _ = enabled;
+        self
+    }
+
     /// Entirely disables statement logging (both slow and regular).
     fn disable_statement_logging(self) -> Self {
         self.log_statements(LevelFilter::Off)
@@ -8,6 +8,8 @@ use std::convert::identity;
 ///
 /// The query macros (e.g., `query!`, `query_as!`, etc.) use the information here to validate
 /// output and parameter types; and, generate an anonymous record.
+///
+/// [`Executor::describe`]: crate::executor::Executor::describe
 #[derive(Debug)]
 #[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -17,10 +19,12 @@ use std::convert::identity;
         deserialize = "DB::TypeInfo: serde::de::DeserializeOwned, DB::Column: serde::de::DeserializeOwned",
     ))
 )]
-#[doc(hidden)]
 pub struct Describe<DB: Database> {
+    #[doc(hidden)]
     pub columns: Vec<DB::Column>,
+    #[doc(hidden)]
     pub parameters: Option<Either<Vec<DB::TypeInfo>, usize>>,
+    #[doc(hidden)]
     pub nullable: Vec<Option<bool>>,
 }
 
@@ -57,6 +57,14 @@ pub enum Error {
     #[error("no rows returned by a query that expected to return at least one row")]
     RowNotFound,
 
+    /// More than one row returned by a query that expected to return at most one row.
+    ///
+    /// Returned by `fetch_one()` and `fetch_optional()`. Earlier versions of SQLx returned the
+    /// first row and silently discarded the rest instead; to keep that behavior, add `LIMIT 1`
+    /// to the query, or use `fetch()` and take the first row of the stream.
+    #[error("more than one row returned by a query that expected to return at most one row")]
+    TooManyRows,
+
     /// Type in query doesn't exist. Likely due to typo or missing user type.
     #[error("type named {type_name} not found")]
     TypeNotFound { type_name: String },
@@ -220,6 +228,14 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         None
     }
 
+    /// Returns the name of the column that was affected by the error, if applicable.
+    ///
+    /// ### Note
+    /// Currently only populated by the Postgres driver.
+    fn column(&self) -> Option<&str> {
+        None
+    }
+
     /// Returns the kind of the error, if supported.
     ///
     /// ### Note
@@ -236,6 +252,11 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         matches!(self.kind(), ErrorKind::ForeignKeyViolation)
     }
 
+    /// Returns whether the error kind is a violation of a not-null constraint.
+    fn is_not_null_violation(&self) -> bool {
+        matches!(self.kind(), ErrorKind::NotNullViolation)
+    }
+
     /// Returns whether the error kind is a violation of a check.
     fn is_check_violation(&self) -> bool {
         matches!(self.kind(), ErrorKind::CheckViolation)
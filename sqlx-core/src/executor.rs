@@ -7,6 +7,9 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::time::Instant;
 
 /// A type that contains or can provide a database
 /// connection to use for executing queries against the database.
@@ -65,6 +68,15 @@ pub trait Executor<'c>: Send + Debug + Sized {
     }
 
     /// Execute the query and return the generated results as a stream.
+    ///
+    /// Rows are read from the connection as the stream is polled rather than buffered up front,
+    /// so large result sets can be processed in constant memory. Dropping the stream early
+    /// leaves the connection usable; any rows not yet read are discarded before the next query.
+    ///
+    /// On Postgres and MySQL the server keeps producing the rest of the result, so the next use
+    /// of the connection first reads and discards all of it, which can take arbitrarily long for
+    /// a large or slow query. Set `cancel_on_drop(true)` on `PgConnectOptions` or
+    /// `MySqlConnectOptions` to cancel the abandoned query at that point instead.
     fn fetch<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
@@ -85,6 +97,10 @@ pub trait Executor<'c>: Send + Debug + Sized {
 
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
+    ///
+    /// The rows of every result set are followed by a `QueryResult` (`Either::Left`), so a
+    /// statement returning several result sets (e.g. a `CALL` to a stored procedure in MySQL)
+    /// can be split back into them.
     fn fetch_many<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
@@ -112,6 +128,11 @@ pub trait Executor<'c>: Send + Debug + Sized {
     }
 
     /// Execute the query and returns exactly one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::RowNotFound`] if the query returns no rows.
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     fn fetch_one<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
@@ -129,6 +150,10 @@ pub trait Executor<'c>: Send + Debug + Sized {
     }
 
     /// Execute the query and returns at most one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
@@ -145,6 +170,14 @@ pub trait Executor<'c>: Send + Debug + Sized {
     ///
     /// This explicit API is provided to allow access to the statement metadata available after
     /// it prepared but before the first row is returned.
+    ///
+    /// The returned statement can be executed any number of times, with different arguments, on
+    /// this or any other connection (see [`Statement::query()`](crate::statement::Statement::query)).
+    /// As it carries its own metadata, executing it never has to describe the query again.
+    ///
+    /// On Postgres and MySQL, the statement stays prepared on this connection, independently of
+    /// the statement cache, until it and every copy of it are dropped. On any other connection
+    /// it is prepared like any other query.
     #[inline]
     fn prepare<'e, 'q: 'e>(
         self,
@@ -172,9 +205,9 @@ pub trait Executor<'c>: Send + Debug + Sized {
     /// Describe the SQL query and return type information about its parameters
     /// and results.
     ///
-    /// This is used by compile-time verification in the query macros to
-    /// power their type inference.
-    #[doc(hidden)]
+    /// The query is prepared but not executed. This is used by compile-time verification in the
+    /// query macros to power their type inference, and can be used to introspect arbitrary
+    /// queries at runtime.
     fn describe<'e, 'q: 'e>(
         self,
         sql: &'q str,
@@ -206,6 +239,62 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// Returns the point in time by which the query must have completed, if any.
+    ///
+    /// See [`Query::with_deadline`](crate::query::Query::with_deadline).
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
+}
+
+/// Returns the error for a query that did not complete before its deadline.
+fn deadline_elapsed() -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::TimedOut,
+        "query did not complete before its deadline",
+    ))
+}
+
+/// Fails `future` with [`io::ErrorKind::TimedOut`] if it does not complete before `deadline`.
+#[doc(hidden)]
+pub async fn run_until<F, T>(deadline: Option<Instant>, future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let Some(deadline) = deadline else {
+        return future.await;
+    };
+
+    crate::rt::timeout(deadline.saturating_duration_since(Instant::now()), future)
+        .await
+        .unwrap_or_else(|_| Err(deadline_elapsed()))
+}
+
+/// Ends `stream` with [`io::ErrorKind::TimedOut`] if it is not exhausted before `deadline`.
+#[doc(hidden)]
+pub fn stream_until<'e, T: Send + 'e>(
+    deadline: Option<Instant>,
+    stream: BoxStream<'e, Result<T, Error>>,
+) -> BoxStream<'e, Result<T, Error>> {
+    let Some(deadline) = deadline else {
+        return stream;
+    };
+
+    Box::pin(futures_util::stream::unfold(
+        Some(stream),
+        move |stream| async move {
+            let mut stream = stream?;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            match crate::rt::timeout(remaining, stream.next()).await {
+                Ok(Some(item)) => Some((item, Some(stream))),
+                Ok(None) => None,
+                Err(_) => Some((Err(deadline_elapsed()), None)),
+            }
+        },
+    ))
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
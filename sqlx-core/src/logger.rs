@@ -62,16 +62,27 @@ pub struct QueryLogger<'q> {
     rows_affected: u64,
     start: Instant,
     settings: LogSettings,
+    span: tracing::Span,
 }
 
 impl<'q> QueryLogger<'q> {
-    pub fn new(sql: &'q str, settings: LogSettings) -> Self {
+    /// `db_system` is the OpenTelemetry name of the database (e.g. `postgresql`), and
+    /// `connection_id` the server's identifier for the connection, if it has one.
+    pub fn new(
+        sql: &'q str,
+        db_system: &'static str,
+        connection_id: Option<u32>,
+        settings: LogSettings,
+    ) -> Self {
+        let span = query_span(sql, db_system, connection_id, &settings);
+
         Self {
             sql,
             rows_returned: 0,
             rows_affected: 0,
             start: Instant::now(),
             settings,
+            span,
         }
     }
 
@@ -86,6 +97,9 @@ impl<'q> QueryLogger<'q> {
     pub fn finish(&self) {
         let elapsed = self.start.elapsed();
 
+        self.span.record("db.rows_affected", self.rows_affected);
+        self.span.record("db.rows_returned", self.rows_returned);
+
         let was_slow = elapsed >= self.settings.slow_statements_duration;
 
         let lvl = if was_slow {
@@ -102,19 +116,27 @@ impl<'q> QueryLogger<'q> {
             if log_is_enabled {
                 let mut summary = parse_query_summary(&self.sql);
 
-                let sql = if summary != self.sql {
+                let elided = summary != self.sql;
+                if elided {
                     summary.push_str(" …");
-                    format!(
+                }
+
+                // without full statements, the `db.statement` field is left out entirely
+                let sql = if !self.settings.full_statements {
+                    None
+                } else if elided {
+                    Some(format!(
                         "\n\n{}\n",
                         sqlformat::format(
                             &self.sql,
                             &sqlformat::QueryParams::None,
                             sqlformat::FormatOptions::default()
                         )
-                    )
+                    ))
                 } else {
-                    String::new()
+                    Some(String::new())
                 };
+                let sql = sql.as_deref();
 
                 if was_slow {
                     private_tracing_dynamic_event!(
@@ -153,6 +175,42 @@ impl<'q> Drop for QueryLogger<'q> {
     }
 }
 
+/// Open the span covering a query, which is closed when its `QueryLogger` is dropped.
+fn query_span(
+    sql: &str,
+    db_system: &'static str,
+    connection_id: Option<u32>,
+    settings: &LogSettings,
+) -> tracing::Span {
+    #[cfg(feature = "tracing-spans")]
+    {
+        let summary;
+        let statement = if settings.full_statements {
+            sql
+        } else {
+            summary = parse_query_summary(sql);
+            &summary
+        };
+
+        tracing::info_span!(
+            target: "sqlx::query",
+            "query",
+            otel.kind = "client",
+            db.system = db_system,
+            db.statement = statement,
+            db.connection_id = connection_id,
+            db.rows_affected = tracing::field::Empty,
+            db.rows_returned = tracing::field::Empty,
+        )
+    }
+
+    #[cfg(not(feature = "tracing-spans"))]
+    {
+        let _ = (sql, db_system, connection_id, settings);
+        tracing::Span::none()
+    }
+}
+
 pub fn parse_query_summary(sql: &str) -> String {
     // For now, just take the first 4 words
     sql.split_whitespace()
@@ -124,22 +124,23 @@ impl Migrator {
             conn.lock().await?;
         }
 
-        // creates [_migrations] table only if needed
-        // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        let result = self.run_locked(conn).await;
 
-        let version = conn.dirty_version().await?;
-        if let Some(version) = version {
-            return Err(MigrateError::Dirty(version));
+        // unlock the migrator to allow other migrators to run, even if migrating failed;
+        // the lock is held by the connection, which may go back to a pool and be reused
+        if self.locking {
+            let unlocked = conn.unlock().await;
+            return result.and(unlocked);
         }
 
-        let applied_migrations = conn.list_applied_migrations().await?;
-        validate_applied_migrations(&applied_migrations, self)?;
+        result
+    }
 
-        let applied_migrations: HashMap<_, _> = applied_migrations
-            .into_iter()
-            .map(|m| (m.version, m))
-            .collect();
+    async fn run_locked<C>(&self, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate,
+    {
+        let applied_migrations = self.applied_migrations(conn).await?;
 
         for migration in self.iter() {
             if migration.migration_type.is_down_migration() {
@@ -158,12 +159,6 @@ impl Migrator {
             }
         }
 
-        // unlock the migrator to allow other migrators to run
-        // but do nothing as we already migrated
-        if self.locking {
-            conn.unlock().await?;
-        }
-
         Ok(())
     }
 
@@ -196,22 +191,22 @@ impl Migrator {
             conn.lock().await?;
         }
 
-        // creates [_migrations] table only if needed
-        // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        let result = self.undo_locked(&mut *conn, target).await;
 
-        let version = conn.dirty_version().await?;
-        if let Some(version) = version {
-            return Err(MigrateError::Dirty(version));
+        // see `run_direct()`
+        if self.locking {
+            let unlocked = conn.unlock().await;
+            return result.and(unlocked);
         }
 
-        let applied_migrations = conn.list_applied_migrations().await?;
-        validate_applied_migrations(&applied_migrations, self)?;
+        result
+    }
 
-        let applied_migrations: HashMap<_, _> = applied_migrations
-            .into_iter()
-            .map(|m| (m.version, m))
-            .collect();
+    async fn undo_locked<C>(&self, conn: &mut C, target: i64) -> Result<(), MigrateError>
+    where
+        C: Migrate,
+    {
+        let applied_migrations = self.applied_migrations(conn).await?;
 
         for migration in self
             .iter()
@@ -223,12 +218,31 @@ impl Migrator {
             conn.revert(migration).await?;
         }
 
-        // unlock the migrator to allow other migrators to run
-        // but do nothing as we already migrated
-        if self.locking {
-            conn.unlock().await?;
+        Ok(())
+    }
+
+    async fn applied_migrations<C>(
+        &self,
+        conn: &mut C,
+    ) -> Result<HashMap<i64, AppliedMigration>, MigrateError>
+    where
+        C: Migrate,
+    {
+        // creates [_migrations] table only if needed
+        // eventually this will likely migrate previous versions of the table
+        conn.ensure_migrations_table().await?;
+
+        let version = conn.dirty_version().await?;
+        if let Some(version) = version {
+            return Err(MigrateError::Dirty(version));
         }
 
-        Ok(())
+        let applied_migrations = conn.list_applied_migrations().await?;
+        validate_applied_migrations(&applied_migrations, self)?;
+
+        Ok(applied_migrations
+            .into_iter()
+            .map(|m| (m.version, m))
+            .collect())
     }
 }
@@ -1,5 +1,8 @@
 use crate::net::Socket;
 use bytes::BytesMut;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use std::{cmp, io};
 
 use crate::error::Error;
@@ -13,6 +16,14 @@ pub struct BufferedSocket<S> {
     socket: S,
     write_buf: WriteBuffer,
     read_buf: ReadBuffer,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    // set once a read or write timed out, after which the connection is out of sync
+    timed_out: bool,
+    // set by the pool when it gives up waiting for this connection to be returned
+    poisoned: Option<Arc<AtomicBool>>,
+    // set once a read or write failed, after which the connection should not be reused
+    broken: bool,
 }
 
 pub struct WriteBuffer {
@@ -42,11 +53,62 @@ impl<S: Socket> BufferedSocket<S> {
                 read: BytesMut::new(),
                 available: BytesMut::with_capacity(DEFAULT_BUF_SIZE),
             },
+            read_timeout: None,
+            write_timeout: None,
+            timed_out: false,
+            poisoned: None,
+            broken: false,
         }
     }
 
+    /// Set the maximum time to wait for data to read, or for the socket to accept data to write.
+    ///
+    /// Once either times out, the socket is considered broken and every later read or write
+    /// fails immediately, as it is unknown how much of a message was read or written.
+    pub fn set_timeouts(&mut self, read: Option<Duration>, write: Option<Duration>) {
+        self.read_timeout = read;
+        self.write_timeout = write;
+    }
+
+    /// Shut down the socket, and fail every later read or write, once `flag` is set.
+    pub fn set_poison_flag(&mut self, flag: Arc<AtomicBool>) {
+        self.poisoned = Some(flag);
+    }
+
     pub async fn read_buffered(&mut self, len: usize) -> io::Result<BytesMut> {
-        self.read_buf.read(len, &mut self.socket).await
+        self.read_buffered_with_timeout(len, self.read_timeout)
+            .await
+    }
+
+    /// Read without the read timeout, for a connection that is idle but waiting on messages the
+    /// server may send unprompted (e.g. Postgres notifications), which can take arbitrarily long.
+    pub async fn read_buffered_idle(&mut self, len: usize) -> io::Result<BytesMut> {
+        self.read_buffered_with_timeout(len, None).await
+    }
+
+    async fn read_buffered_with_timeout(
+        &mut self,
+        len: usize,
+        timeout: Option<Duration>,
+    ) -> io::Result<BytesMut> {
+        self.check_timed_out()?;
+        self.check_poisoned().await?;
+
+        let read = self.read_buf.read(len, &mut self.socket);
+
+        let result = match timeout {
+            Some(timeout) => match crate::rt::timeout(timeout, read).await {
+                Ok(result) => result,
+                Err(_) => {
+                    self.timed_out = true;
+                    Err(timed_out("reading from"))
+                }
+            },
+            None => read.await,
+        };
+
+        self.broken |= result.is_err();
+        result
     }
 
     pub fn write_buffer(&self) -> &WriteBuffer {
@@ -88,6 +150,25 @@ impl<S: Socket> BufferedSocket<S> {
     }
 
     pub async fn flush(&mut self) -> io::Result<()> {
+        self.check_timed_out()?;
+        self.check_poisoned().await?;
+
+        let result = match self.write_timeout {
+            Some(timeout) => match crate::rt::timeout(timeout, self.flush_inner()).await {
+                Ok(result) => result,
+                Err(_) => {
+                    self.timed_out = true;
+                    Err(timed_out("writing to"))
+                }
+            },
+            None => self.flush_inner().await,
+        };
+
+        self.broken |= result.is_err();
+        result
+    }
+
+    async fn flush_inner(&mut self) -> io::Result<()> {
         while !self.write_buf.is_empty() {
             let written = self.socket.write(self.write_buf.get()).await?;
             self.write_buf.consume(written);
@@ -99,11 +180,57 @@ impl<S: Socket> BufferedSocket<S> {
         Ok(())
     }
 
+    fn check_timed_out(&self) -> io::Result<()> {
+        if self.timed_out {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "connection is unusable after an earlier read or write timed out",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if a read or write failed, or the pool gave up on the connection; the
+    /// state of the protocol is then unknown, so the connection should be closed.
+    pub fn is_broken(&self) -> bool {
+        self.broken || self.is_poisoned()
+    }
+
+    fn is_poisoned(&self) -> bool {
+        self.poisoned
+            .as_ref()
+            .is_some_and(|poisoned| poisoned.load(Ordering::Acquire))
+    }
+
+    async fn check_poisoned(&mut self) -> io::Result<()> {
+        if self.is_poisoned() {
+            let _ = self.socket.shutdown().await;
+
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "connection was closed by `Pool::close()` after its `close_timeout` elapsed",
+            ));
+        }
+
+        Ok(())
+    }
+
     pub async fn shutdown(&mut self) -> io::Result<()> {
-        self.flush().await?;
+        // after a timeout, whatever is left in the write buffer can't be sent,
+        // but the socket should still be shut down
+        if !self.timed_out && !self.is_poisoned() {
+            self.flush().await?;
+        }
+
         self.socket.shutdown().await
     }
 
+    /// The DER encoding of the certificate the server presented, if this is a TLS connection.
+    pub fn peer_certificate(&self) -> Option<Vec<u8>> {
+        self.socket.peer_certificate()
+    }
+
     pub fn shrink_buffers(&mut self) {
         // Won't drop data still in the buffer.
         self.write_buf.shrink();
@@ -119,10 +246,22 @@ impl<S: Socket> BufferedSocket<S> {
             socket: Box::new(self.socket),
             write_buf: self.write_buf,
             read_buf: self.read_buf,
+            read_timeout: self.read_timeout,
+            write_timeout: self.write_timeout,
+            timed_out: self.timed_out,
+            poisoned: self.poisoned,
+            broken: self.broken,
         }
     }
 }
 
+fn timed_out(action: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("timed out {action} the server"),
+    )
+}
+
 impl WriteBuffer {
     fn sanity_check(&self) {
         assert_ne!(self.buf.capacity(), 0);
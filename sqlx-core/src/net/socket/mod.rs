@@ -3,6 +3,7 @@ use std::io;
 use std::path::Path;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use bytes::BufMut;
 use futures_core::ready;
@@ -29,6 +30,11 @@ pub trait Socket: Send + Sync + Unpin + 'static {
 
     fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
 
+    /// The DER encoding of the certificate the server presented, if this is a TLS connection.
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     fn read<'a, B: ReadBuf>(&'a mut self, buf: &'a mut B) -> Read<'a, Self, B>
     where
         Self: Sized,
@@ -180,11 +186,23 @@ impl<S: Socket + ?Sized> Socket for Box<S> {
     fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         (**self).poll_shutdown(cx)
     }
+
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        (**self).peer_certificate()
+    }
 }
 
+/// Connect a TCP socket to the given host and port.
+///
+/// Nagle's algorithm is disabled on the socket, as it is by libpq: messages are already buffered
+/// into as few writes as possible, and delaying a small trailing write only adds latency.
+///
+/// If `keepalive` is set, TCP keepalive probes are sent after the connection has been idle for
+/// that long, so a peer that disappeared without closing the connection is eventually detected.
 pub async fn connect_tcp<Ws: WithSocket>(
     host: &str,
     port: u16,
+    keepalive: Option<Duration>,
     with_socket: Ws,
 ) -> crate::Result<Ws::Output> {
     // IPv6 addresses in URLs will be wrapped in brackets and the `url` crate doesn't trim those.
@@ -195,6 +213,11 @@ pub async fn connect_tcp<Ws: WithSocket>(
         use tokio::net::TcpStream;
 
         let stream = TcpStream::connect((host, port)).await?;
+        stream.set_nodelay(true)?;
+
+        if let Some(keepalive) = keepalive {
+            set_tcp_keepalive(socket2::SockRef::from(&stream), keepalive)?;
+        }
 
         return Ok(with_socket.with_socket(stream));
     }
@@ -210,7 +233,15 @@ pub async fn connect_tcp<Ws: WithSocket>(
         // Loop through all the Socket Addresses that the hostname resolves to
         for socket_addr in (host, port).to_socket_addrs().await? {
             match Async::<TcpStream>::connect(socket_addr).await {
-                Ok(stream) => return Ok(with_socket.with_socket(stream)),
+                Ok(stream) => {
+                    stream.get_ref().set_nodelay(true)?;
+
+                    if let Some(keepalive) = keepalive {
+                        set_tcp_keepalive(socket2::SockRef::from(stream.get_ref()), keepalive)?;
+                    }
+
+                    return Ok(with_socket.with_socket(stream));
+                }
                 Err(e) => last_err = Some(e),
             }
         }
@@ -231,10 +262,15 @@ pub async fn connect_tcp<Ws: WithSocket>(
 
     #[cfg(not(feature = "_rt-async-std"))]
     {
-        crate::rt::missing_rt((host, port, with_socket))
+        crate::rt::missing_rt((host, port, keepalive, with_socket))
     }
 }
 
+#[cfg(any(feature = "_rt-tokio", feature = "_rt-async-std"))]
+fn set_tcp_keepalive(socket: socket2::SockRef<'_>, keepalive: Duration) -> io::Result<()> {
+    socket.set_tcp_keepalive(&socket2::TcpKeepalive::new().with_time(keepalive))
+}
+
 /// Connect a Unix Domain Socket at the given path.
 ///
 /// Returns an error if Unix Domain Sockets are not supported on this platform.
@@ -275,3 +311,60 @@ pub async fn connect_uds<P: AsRef<Path>, Ws: WithSocket>(
         crate::rt::missing_rt((path, with_socket))
     }
 }
+
+#[cfg(all(test, any(feature = "_rt-tokio", feature = "_rt-async-std")))]
+mod tests {
+    use super::*;
+    use std::any::Any;
+
+    // reports whether Nagle's algorithm is disabled on the connected socket
+    struct NoDelay;
+
+    impl WithSocket for NoDelay {
+        type Output = io::Result<bool>;
+
+        fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
+            let socket: &dyn Any = &socket;
+
+            #[cfg(feature = "_rt-tokio")]
+            if let Some(stream) = socket.downcast_ref::<tokio::net::TcpStream>() {
+                return stream.nodelay();
+            }
+
+            #[cfg(feature = "_rt-async-std")]
+            if let Some(stream) = socket.downcast_ref::<async_io::Async<std::net::TcpStream>>() {
+                return stream.get_ref().nodelay();
+            }
+
+            panic!("expected a TCP socket")
+        }
+    }
+
+    async fn connect_nodelay() -> bool {
+        // the connection is established once it is queued, without being accepted
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        connect_tcp("127.0.0.1", port, None, NoDelay)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[cfg(feature = "_rt-tokio")]
+    #[test]
+    fn test_connect_tcp_sets_nodelay_tokio() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        assert!(runtime.block_on(connect_nodelay()));
+    }
+
+    #[cfg(feature = "_rt-async-std")]
+    #[test]
+    fn test_connect_tcp_sets_nodelay_async_std() {
+        assert!(async_std::task::block_on(connect_nodelay()));
+    }
+}
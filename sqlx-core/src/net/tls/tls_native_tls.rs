@@ -36,6 +36,11 @@ impl<S: Socket> Socket for NativeTlsSocket<S> {
             ready => Poll::Ready(ready),
         }
     }
+
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        let certificate = self.stream.peer_certificate().ok()??;
+        certificate.to_der().ok()
+    }
 }
 
 pub async fn handshake<S: Socket>(
@@ -9,7 +9,7 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 
-use super::inner::{DecrementSizeGuard, PoolInner};
+use super::inner::{is_beyond_max_lifetime, DecrementSizeGuard, PoolInner};
 use crate::pool::options::PoolConnectionMetadata;
 use std::future::Future;
 
@@ -149,6 +149,10 @@ impl<'c, DB: Database> crate::acquire::Acquire<'c> for &'c mut PoolConnection<DB
 
     #[inline]
     fn acquire(self) -> futures_core::future::BoxFuture<'c, Result<Self::Connection, Error>> {
+        if self.pool.is_poisoned() {
+            return Box::pin(futures_util::future::err(Error::PoolClosed));
+        }
+
         Box::pin(futures_util::future::ok(&mut **self))
     }
 
@@ -157,6 +161,10 @@ impl<'c, DB: Database> crate::acquire::Acquire<'c> for &'c mut PoolConnection<DB
         self,
     ) -> futures_core::future::BoxFuture<'c, Result<crate::transaction::Transaction<'c, DB>, Error>>
     {
+        if self.pool.is_poisoned() {
+            return Box::pin(futures_util::future::err(Error::PoolClosed));
+        }
+
         crate::transaction::Transaction::begin(&mut **self)
     }
 }
@@ -235,10 +243,28 @@ impl<DB: Database> Floating<DB, Live<DB>> {
     async fn return_to_pool(mut self) -> bool {
         // Immediately close the connection.
         if self.guard.pool.is_closed() {
+            // `Pool::close()` gave up waiting on this connection, don't hold things up any longer
+            if self.guard.pool.is_poisoned() {
+                self.close_hard().await;
+            } else {
+                self.close().await;
+            }
+
+            return false;
+        }
+
+        // retire the connection now instead of leaving it for the reaper or the next acquire
+        if is_beyond_max_lifetime(&self, &self.guard.pool.options) {
             self.close().await;
             return false;
         }
 
+        // an I/O or protocol error already showed the connection can't be reused
+        if self.raw.is_broken() {
+            self.close_hard().await;
+            return false;
+        }
+
         if let Some(test) = &self.guard.pool.options.after_release {
             let meta = self.metadata();
             match (test)(&mut self.inner.raw, meta).await {
@@ -264,7 +290,8 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         // returned to the pool; also of course, if it was dropped due to an error
         // this is simply a band-aid as SQLx-next connections should be able
         // to recover from cancellations
-        if let Err(error) = self.raw.ping().await {
+        // (drivers may also reset the session here instead of just pinging)
+        if let Err(error) = self.raw.before_release().await {
             tracing::warn!(
                 %error,
                 "error occurred while testing the connection on-release",
@@ -23,9 +23,10 @@ where
         E: Execute<'q, Self::Database>,
     {
         let pool = self.clone();
+        let deadline = query.deadline();
 
         Box::pin(try_stream! {
-            let mut conn = pool.acquire().await?;
+            let mut conn = pool.acquire_until(deadline).await?;
             let mut s = conn.fetch_many(query);
 
             while let Some(v) = s.try_next().await? {
@@ -44,8 +45,14 @@ where
         E: Execute<'q, Self::Database>,
     {
         let pool = self.clone();
+        let deadline = query.deadline();
 
-        Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
+        Box::pin(async move {
+            pool.acquire_until(deadline)
+                .await?
+                .fetch_optional(query)
+                .await
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
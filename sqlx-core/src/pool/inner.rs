@@ -3,6 +3,7 @@ use crate::connection::ConnectOptions;
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
+use crate::pool::metrics::{PoolCounters, PoolMetrics};
 use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions};
 use crossbeam_queue::ArrayQueue;
 
@@ -26,8 +27,11 @@ pub(crate) struct PoolInner<DB: Database> {
     pub(super) size: AtomicU32,
     pub(super) num_idle: AtomicUsize,
     is_closed: AtomicBool,
+    // shared with every connection, so those still checked out are shut down once it is set
+    is_poisoned: Arc<AtomicBool>,
     pub(super) on_closed: event_listener::Event,
     pub(super) options: PoolOptions<DB>,
+    counters: PoolCounters,
 }
 
 impl<DB: Database> PoolInner<DB> {
@@ -53,8 +57,10 @@ impl<DB: Database> PoolInner<DB> {
             size: AtomicU32::new(0),
             num_idle: AtomicUsize::new(0),
             is_closed: AtomicBool::new(false),
+            is_poisoned: Arc::new(AtomicBool::new(false)),
             on_closed: event_listener::Event::new(),
             options,
+            counters: PoolCounters::default(),
         };
 
         let pool = Arc::new(pool);
@@ -77,10 +83,19 @@ impl<DB: Database> PoolInner<DB> {
         self.num_idle.load(Ordering::Acquire)
     }
 
+    pub(super) fn metrics(&self) -> PoolMetrics {
+        self.counters.snapshot(self.size(), self.num_idle())
+    }
+
     pub(super) fn is_closed(&self) -> bool {
         self.is_closed.load(Ordering::Acquire)
     }
 
+    /// Returns `true` if `close()` timed out waiting for checked-out connections.
+    pub(super) fn is_poisoned(&self) -> bool {
+        self.is_poisoned.load(Ordering::Acquire)
+    }
+
     fn mark_closed(&self) {
         self.is_closed.store(true, Ordering::Release);
         self.on_closed.notify(usize::MAX);
@@ -90,18 +105,36 @@ impl<DB: Database> PoolInner<DB> {
         self.mark_closed();
 
         async move {
-            for permits in 1..=self.options.max_connections {
-                // Close any currently idle connections in the pool.
-                while let Some(idle) = self.idle_conns.pop() {
-                    let _ = idle.live.float((*self).clone()).close().await;
-                }
+            let close_all = async {
+                for permits in 1..=self.options.max_connections {
+                    // Close any currently idle connections in the pool.
+                    while let Some(idle) = self.idle_conns.pop() {
+                        let _ = idle.live.float((*self).clone()).close().await;
+                    }
+
+                    if self.size() == 0 {
+                        break;
+                    }
 
-                if self.size() == 0 {
-                    break;
+                    // Wait for all permits to be released.
+                    let _permits = self.semaphore.acquire(permits).await;
                 }
+            };
 
-                // Wait for all permits to be released.
-                let _permits = self.semaphore.acquire(permits).await;
+            let Some(close_timeout) = self.options.close_timeout else {
+                return close_all.await;
+            };
+
+            if crate::rt::timeout(close_timeout, close_all).await.is_err() {
+                // Don't wait on connections that are still checked out any longer;
+                // their sockets are shut down on their next use, and they will be closed hard
+                // when they're returned.
+                self.is_poisoned.store(true, Ordering::Release);
+
+                tracing::warn!(
+                    remaining = self.size(),
+                    "timed out waiting for checked-out connections while closing the pool"
+                );
             }
         }
     }
@@ -236,15 +269,23 @@ impl<DB: Database> PoolInner<DB> {
         }
     }
 
-    pub(super) async fn acquire(self: &Arc<Self>) -> Result<Floating<DB, Live<DB>>, Error> {
+    /// Gives up after `acquire_timeout`, or at `deadline` if that comes first.
+    pub(super) async fn acquire(
+        self: &Arc<Self>,
+        deadline: Option<Instant>,
+    ) -> Result<Floating<DB, Live<DB>>, Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
 
-        let deadline = Instant::now() + self.options.acquire_timeout;
+        let start = Instant::now();
+        let timeout_deadline = start + self.options.acquire_timeout;
+        let deadline = deadline.map_or(timeout_deadline, |deadline| {
+            cmp::min(deadline, timeout_deadline)
+        });
 
-        crate::rt::timeout(
-            self.options.acquire_timeout,
+        let acquired = crate::rt::timeout(
+            deadline_as_timeout::<DB>(deadline)?,
             async {
                 loop {
                     // Handles the close-event internally
@@ -286,7 +327,16 @@ impl<DB: Database> PoolInner<DB> {
             }
         )
             .await
-            .map_err(|_| Error::PoolTimedOut)?
+            .map_err(|_| Error::PoolTimedOut)
+            .and_then(|acquired| acquired);
+
+        match &acquired {
+            Ok(_) => self.counters.record_acquire(start.elapsed()),
+            Err(Error::PoolTimedOut) => self.counters.record_acquire_timeout(),
+            Err(_) => {}
+        }
+
+        acquired
     }
 
     pub(super) async fn connect(
@@ -317,6 +367,8 @@ impl<DB: Database> PoolInner<DB> {
             match crate::rt::timeout(timeout, connect_options.connect()).await {
                 // successfully established connection
                 Ok(Ok(mut raw)) => {
+                    raw.set_poison_flag(Arc::clone(&self.is_poisoned));
+
                     // See comment on `PoolOptions::after_connect`
                     let meta = PoolConnectionMetadata {
                         age: Duration::ZERO,
@@ -330,8 +382,12 @@ impl<DB: Database> PoolInner<DB> {
                     };
 
                     match res {
-                        Ok(()) => return Ok(Floating::new_live(raw, guard)),
+                        Ok(()) => {
+                            self.counters.record_connection_opened();
+                            return Ok(Floating::new_live(raw, guard));
+                        }
                         Err(error) => {
+                            self.counters.record_connect_error();
                             tracing::error!(%error, "error returned from after_connect");
                             // The connection is broken, don't try to close nicely.
                             let _ = raw.close_hard().await;
@@ -342,17 +398,35 @@ impl<DB: Database> PoolInner<DB> {
                 }
 
                 // an IO error while connecting is assumed to be the system starting up
-                Ok(Err(Error::Io(e))) if e.kind() == std::io::ErrorKind::ConnectionRefused => (),
+                // (or restarting, in which case it may drop connections mid-handshake)
+                Ok(Err(Error::Io(e)))
+                    if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::ConnectionRefused
+                            | std::io::ErrorKind::ConnectionReset
+                            | std::io::ErrorKind::ConnectionAborted
+                    ) =>
+                {
+                    self.counters.record_connect_error();
+                }
 
                 // We got a transient database error, retry.
-                Ok(Err(Error::Database(error))) if error.is_transient_in_connect_phase() => (),
+                Ok(Err(Error::Database(error))) if error.is_transient_in_connect_phase() => {
+                    self.counters.record_connect_error();
+                }
 
                 // Any other error while connection should immediately
                 // terminate and bubble the error up
-                Ok(Err(e)) => return Err(e),
+                Ok(Err(e)) => {
+                    self.counters.record_connect_error();
+                    return Err(e);
+                }
 
                 // timed out
-                Err(_) => return Err(Error::PoolTimedOut),
+                Err(_) => {
+                    self.counters.record_connect_error();
+                    return Err(Error::PoolTimedOut);
+                }
             }
 
             // If the connection is refused, wait in exponentially
@@ -417,7 +491,10 @@ impl<DB: Database> Drop for PoolInner<DB> {
 }
 
 /// Returns `true` if the connection has exceeded `options.max_lifetime` if set, `false` otherwise.
-fn is_beyond_max_lifetime<DB: Database>(live: &Live<DB>, options: &PoolOptions<DB>) -> bool {
+pub(super) fn is_beyond_max_lifetime<DB: Database>(
+    live: &Live<DB>,
+    options: &PoolOptions<DB>,
+) -> bool {
     options
         .max_lifetime
         .map_or(false, |max| live.created_at.elapsed() > max)
@@ -440,15 +517,36 @@ async fn check_idle_conn<DB: Database>(
         return Err(conn.close().await);
     }
 
+    // an I/O or protocol error already showed the connection can't be reused
+    if conn.live.raw.is_broken() {
+        return Err(conn.close_hard().await);
+    }
+
     if options.test_before_acquire {
         // Check that the connection is still live
-        if let Err(error) = conn.ping().await {
-            // an error here means the other end has hung up or we lost connectivity
-            // either way we're fine to just discard the connection
-            // the error itself here isn't necessarily unexpected so WARN is too strong
-            tracing::info!(%error, "ping on idle connection returned error");
-            // connection is broken so don't try to close nicely
-            return Err(conn.close_hard().await);
+        let ping = match options.ping_timeout {
+            Some(timeout) => crate::rt::timeout(timeout, conn.ping()).await,
+            None => Ok(conn.ping().await),
+        };
+
+        match ping {
+            Ok(Ok(())) => {}
+
+            Ok(Err(error)) => {
+                // an error here means the other end has hung up or we lost connectivity
+                // either way we're fine to just discard the connection
+                // the error itself here isn't necessarily unexpected so WARN is too strong
+                tracing::info!(%error, "ping on idle connection returned error");
+                // connection is broken so don't try to close nicely
+                return Err(conn.close_hard().await);
+            }
+
+            Err(_) => {
+                // most likely, the connection was silently dropped by something in between
+                tracing::info!("ping on idle connection timed out");
+                // a half-sent ping leaves the connection unusable anyway
+                return Err(conn.close_hard().await);
+            }
         }
     }
 
@@ -0,0 +1,101 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// A snapshot of the state of a [`Pool`][super::Pool] and of counters kept since it was created.
+///
+/// Returned by [`Pool::metrics()`][super::Pool::metrics]. The counters only ever increase, so
+/// the rate of an event is the difference between two snapshots divided by the time in between;
+/// e.g. a steady rise of [`acquire_timeouts`][Self::acquire_timeouts], or of the mean time spent
+/// waiting in `acquire()`, suggests that `max_connections` is too low for the load.
+#[derive(Debug, Clone)]
+#[non_exhaustive] // So we can safely add fields in the future.
+pub struct PoolMetrics {
+    /// The number of connections currently open, idle or in use; see [`Pool::size()`].
+    ///
+    /// [`Pool::size()`]: super::Pool::size
+    pub size: u32,
+
+    /// The number of idle connections; see [`Pool::num_idle()`].
+    ///
+    /// [`Pool::num_idle()`]: super::Pool::num_idle
+    pub num_idle: usize,
+
+    /// The number of connections handed out by `acquire()` (and the methods which call it).
+    pub acquires: u64,
+
+    /// The number of calls to `acquire()` that failed with [`Error::PoolTimedOut`].
+    ///
+    /// [`Error::PoolTimedOut`]: crate::error::Error::PoolTimedOut
+    pub acquire_timeouts: u64,
+
+    /// The total time spent waiting in `acquire()` by the calls that got a connection.
+    pub acquire_wait_total: Duration,
+
+    /// The longest time spent waiting in a single call to `acquire()` that got a connection.
+    pub acquire_wait_max: Duration,
+
+    /// The number of connections the pool opened.
+    pub connections_opened: u64,
+
+    /// The number of failed attempts to open a connection, including attempts retried by the
+    /// pool and errors returned by [`after_connect`][super::PoolOptions::after_connect].
+    pub connect_errors: u64,
+}
+
+impl PoolMetrics {
+    /// The mean time spent waiting in `acquire()` by the calls that got a connection, or `None`
+    /// if there were none.
+    pub fn acquire_wait_mean(&self) -> Option<Duration> {
+        let acquires = u32::try_from(self.acquires).unwrap_or(u32::MAX);
+
+        (acquires > 0).then(|| self.acquire_wait_total / acquires)
+    }
+}
+
+#[derive(Default)]
+pub(super) struct PoolCounters {
+    acquires: AtomicU64,
+    acquire_timeouts: AtomicU64,
+    // in nanoseconds
+    acquire_wait_total: AtomicU64,
+    acquire_wait_max: AtomicU64,
+    connections_opened: AtomicU64,
+    connect_errors: AtomicU64,
+}
+
+impl PoolCounters {
+    pub(super) fn record_acquire(&self, wait: Duration) {
+        let wait = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+
+        self.acquires.fetch_add(1, Ordering::Relaxed);
+        self.acquire_wait_total.fetch_add(wait, Ordering::Relaxed);
+        self.acquire_wait_max.fetch_max(wait, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_acquire_timeout(&self) {
+        self.acquire_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_connection_opened(&self) {
+        self.connections_opened.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_connect_error(&self) {
+        self.connect_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn snapshot(&self, size: u32, num_idle: usize) -> PoolMetrics {
+        PoolMetrics {
+            size,
+            num_idle,
+            acquires: self.acquires.load(Ordering::Relaxed),
+            acquire_timeouts: self.acquire_timeouts.load(Ordering::Relaxed),
+            acquire_wait_total: Duration::from_nanos(
+                self.acquire_wait_total.load(Ordering::Relaxed),
+            ),
+            acquire_wait_max: Duration::from_nanos(self.acquire_wait_max.load(Ordering::Relaxed)),
+            connections_opened: self.connections_opened.load(Ordering::Relaxed),
+            connect_errors: self.connect_errors.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -68,7 +68,7 @@ use crate::any::{Any, AnyKind};
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use event_listener::EventListener;
 use futures_core::FusedFuture;
 use futures_util::FutureExt;
@@ -78,6 +78,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
+use tracing::Instrument;
 
 #[macro_use]
 mod executor;
@@ -87,9 +88,11 @@ pub mod maybe;
 
 mod connection;
 mod inner;
+mod metrics;
 mod options;
 
 pub use self::connection::PoolConnection;
+pub use self::metrics::PoolMetrics;
 pub use self::options::{PoolConnectionMetadata, PoolOptions};
 
 #[doc(hidden)]
@@ -363,7 +366,20 @@ impl<DB: Database> Pool<DB> {
     /// returning it.
     pub fn acquire(&self) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
         let shared = self.0.clone();
-        async move { shared.acquire().await.map(|conn| conn.reattach()) }
+        async move { shared.acquire(None).await.map(|conn| conn.reattach()) }
+            .instrument(acquire_span(DB::NAME))
+    }
+
+    /// Like [`acquire()`][Self::acquire], but gives up at `deadline` if it comes first.
+    pub(crate) async fn acquire_until(
+        &self,
+        deadline: Option<Instant>,
+    ) -> Result<PoolConnection<DB>, Error> {
+        self.0
+            .acquire(deadline)
+            .instrument(acquire_span(DB::NAME))
+            .await
+            .map(|conn| conn.reattach())
     }
 
     /// Attempts to retrieve a connection from the pool if there is one available.
@@ -379,6 +395,20 @@ impl<DB: Database> Pool<DB> {
         Ok(Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await?)
     }
 
+    /// Retrieves a connection and immediately begins a new transaction with the given options.
+    ///
+    /// See [`TransactionOptions`] for the available options.
+    pub async fn begin_with(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Transaction<'static, DB>, Error> {
+        Transaction::begin_with(
+            MaybePoolConnection::PoolConnection(self.acquire().await?),
+            options,
+        )
+        .await
+    }
+
     /// Attempts to retrieve a connection and immediately begins a new transaction if successful.
     pub async fn try_begin(&self) -> Result<Option<Transaction<'static, DB>>, Error> {
         match self.try_acquire() {
@@ -410,6 +440,9 @@ impl<DB: Database> Pool<DB> {
     /// when the last handle for the given pool instance is dropped, which could happen in a task
     /// spawned by `Pool` internally and so may be unpredictable otherwise.
     ///
+    /// To bound how long this waits for checked-out connections, see
+    /// [`PoolOptions::close_timeout()`].
+    ///
     /// `.close()` may be safely called and `.await`ed on multiple handles concurrently.
     pub fn close(&self) -> impl Future<Output = ()> + '_ {
         self.0.close()
@@ -512,6 +545,15 @@ impl<DB: Database> Pool<DB> {
         self.0.num_idle()
     }
 
+    /// Returns a snapshot of the size of the pool and of counters kept since it was created,
+    /// such as the number of acquires, timeouts and connection errors, and the time spent waiting
+    /// for a connection.
+    ///
+    /// This is cheap enough to call periodically, e.g. to graph the saturation of the pool.
+    pub fn metrics(&self) -> PoolMetrics {
+        self.0.metrics()
+    }
+
     /// Gets a clone of the connection options for this pool
     pub fn connect_options(&self) -> Arc<<DB::Connection as Connection>::Options> {
         self.0
@@ -639,6 +681,25 @@ impl FusedFuture for CloseEvent {
     }
 }
 
+/// Open the span covering a pool acquire, which is closed once a connection is handed out.
+fn acquire_span(db_name: &'static str) -> tracing::Span {
+    #[cfg(feature = "tracing-spans")]
+    {
+        tracing::info_span!(
+            target: "sqlx::pool",
+            "acquire",
+            otel.kind = "client",
+            db.system = %db_name.to_ascii_lowercase(),
+        )
+    }
+
+    #[cfg(not(feature = "tracing-spans"))]
+    {
+        let _ = db_name;
+        tracing::Span::none()
+    }
+}
+
 /// get the time between the deadline and now and use that as our timeout
 ///
 /// returns `Error::PoolTimedOut` if the deadline is in the past
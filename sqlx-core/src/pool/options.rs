@@ -43,6 +43,7 @@ use std::time::{Duration, Instant};
 /// the perspectives of both API designer and consumer.
 pub struct PoolOptions<DB: Database> {
     pub(crate) test_before_acquire: bool,
+    pub(crate) ping_timeout: Option<Duration>,
     pub(crate) after_connect: Option<
         Arc<
             dyn Fn(&mut DB::Connection, PoolConnectionMetadata) -> BoxFuture<'_, Result<(), Error>>
@@ -78,6 +79,7 @@ pub struct PoolOptions<DB: Database> {
     pub(crate) min_connections: u32,
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) close_timeout: Option<Duration>,
     pub(crate) fair: bool,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
@@ -90,6 +92,7 @@ impl<DB: Database> Clone for PoolOptions<DB> {
     fn clone(&self) -> Self {
         PoolOptions {
             test_before_acquire: self.test_before_acquire,
+            ping_timeout: self.ping_timeout,
             after_connect: self.after_connect.clone(),
             before_acquire: self.before_acquire.clone(),
             after_release: self.after_release.clone(),
@@ -98,6 +101,7 @@ impl<DB: Database> Clone for PoolOptions<DB> {
             min_connections: self.min_connections,
             max_lifetime: self.max_lifetime,
             idle_timeout: self.idle_timeout,
+            close_timeout: self.close_timeout,
             fair: self.fair,
             parent_pool: self.parent_pool.as_ref().map(Pool::clone),
         }
@@ -140,12 +144,14 @@ impl<DB: Database> PoolOptions<DB> {
             before_acquire: None,
             after_release: None,
             test_before_acquire: true,
+            ping_timeout: None,
             // A production application will want to set a higher limit than this.
             max_connections: 10,
             min_connections: 0,
             acquire_timeout: Duration::from_secs(30),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
+            close_timeout: None,
             fair: true,
             parent_pool: None,
         }
@@ -223,7 +229,8 @@ impl<DB: Database> PoolOptions<DB> {
 
     /// Set the maximum lifetime of individual connections.
     ///
-    /// Any connection with a lifetime greater than this will be closed.
+    /// Any connection with a lifetime greater than this will be closed: by the idle reaper if it
+    /// sits in the pool, or as soon as it is released if it was checked out at the time.
     ///
     /// When set to `None`, all connections live until either reaped by [`idle_timeout`]
     /// or explicitly disconnected.
@@ -260,6 +267,31 @@ impl<DB: Database> PoolOptions<DB> {
         self.idle_timeout
     }
 
+    /// Set the maximum amount of time [`Pool::close()`] will wait for checked-out connections
+    /// to be returned.
+    ///
+    /// Once this timeout elapses, `.close()` completes even if connections are still checked out.
+    /// Those connections are poisoned: acquiring them again through [`Acquire`] returns
+    /// [`Error::PoolClosed`], their next read or write shuts down the socket and fails with an
+    /// I/O error of kind [`ConnectionAborted`][std::io::ErrorKind::ConnectionAborted], and when
+    /// they are dropped they are closed without the graceful shutdown handshake.
+    ///
+    /// A read or write already waiting on the server when the timeout elapses is not interrupted.
+    /// Connections that don't use a socket (SQLite) are only poisoned for [`Acquire`].
+    ///
+    /// When set to `None` (the default), `.close()` waits for all connections indefinitely.
+    ///
+    /// [`Acquire`]: crate::acquire::Acquire
+    pub fn close_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.close_timeout = timeout.into();
+        self
+    }
+
+    /// Get the maximum amount of time [`Pool::close()`] will wait for checked-out connections.
+    pub fn get_close_timeout(&self) -> Option<Duration> {
+        self.close_timeout
+    }
+
     /// If true, the health of a connection will be verified by a call to [`Connection::ping`]
     /// before returning the connection.
     ///
@@ -274,6 +306,28 @@ impl<DB: Database> PoolOptions<DB> {
         self.test_before_acquire
     }
 
+    /// Set the maximum amount of time to wait for the ping of
+    /// [`test_before_acquire`][Self::test_before_acquire].
+    ///
+    /// A connection that does not answer in time is discarded, without the graceful shutdown
+    /// handshake, and `acquire()` moves on to another idle connection or opens a new one.
+    ///
+    /// This is useful when a firewall or load balancer silently drops idle connections: the
+    /// ping of such a connection gets no response at all, rather than an error, and would
+    /// otherwise hold up `acquire()` until [`acquire_timeout`][Self::acquire_timeout] elapses.
+    /// It should comfortably exceed the round-trip time to the database.
+    ///
+    /// Defaults to `None`, which waits as long as `acquire_timeout` allows.
+    pub fn ping_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.ping_timeout = timeout.into();
+        self
+    }
+
+    /// Get the maximum amount of time to wait for the ping of `test_before_acquire`.
+    pub fn get_ping_timeout(&self) -> Option<Duration> {
+        self.ping_timeout
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -304,6 +358,10 @@ impl<DB: Database> PoolOptions<DB> {
     /// [`min_connections`][Self::min_connections], that are then immediately returned to the pool
     /// without invoking [`after_release`][Self::after_release].
     ///
+    /// It is not run again if the driver resets the session of a connection when it is
+    /// released (such as MySQL with `reset_on_release`); the MySQL driver has an `after_connect`
+    /// option of its own for that case.
+    ///
     /// # Example: Additional Parameters
     /// This callback may be used to set additional configuration parameters
     /// that are not exposed by the database's `ConnectOptions`.
@@ -504,7 +562,7 @@ impl<DB: Database> PoolOptions<DB> {
 
         // If `min_connections` is nonzero then we'll likely just pull a connection
         // from the idle queue here, but it should at least get tested first.
-        let conn = inner.acquire().await?;
+        let conn = inner.acquire(None).await?;
         inner.release(conn);
 
         Ok(Pool(inner))
@@ -543,7 +601,9 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("connect_timeout", &self.acquire_timeout)
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
+            .field("close_timeout", &self.close_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("ping_timeout", &self.ping_timeout)
             .finish()
     }
 }
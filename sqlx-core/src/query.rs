@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::time::Instant;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -19,6 +20,7 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<A>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) deadline: Option<Instant>,
 }
 
 /// SQL query that will map its results to owned Rust types.
@@ -65,6 +67,11 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
 }
 
 impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
@@ -85,6 +92,24 @@ impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
     }
 }
 
+impl<'q, DB: Database, A> Query<'q, DB, A> {
+    /// Set an absolute deadline by which the query must have completed.
+    ///
+    /// Unlike wrapping each call in a timeout, the deadline is shared by every step it takes
+    /// to run the query: acquiring a connection from a [`Pool`](crate::pool::Pool), including
+    /// any connection attempts and their retries, and then executing the query itself.
+    ///
+    /// If the deadline passes while waiting on the pool, [`Error::PoolTimedOut`] is returned,
+    /// as it would be for [`PoolOptions::acquire_timeout`](crate::pool::PoolOptions::acquire_timeout),
+    /// whichever comes first. If it passes during execution, the query fails with
+    /// [`Error::Io`] of kind [`TimedOut`](std::io::ErrorKind::TimedOut). The statement is
+    /// abandoned on the client side, but the server may still run it to completion.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
+
 impl<'q, DB, A> Query<'q, DB, A>
 where
     DB: Database + HasStatementCache,
@@ -168,6 +193,8 @@ where
     }
 
     /// Execute the query and return the generated results as a stream.
+    ///
+    /// Rows are read incrementally as the stream is polled; see [`Executor::fetch()`].
     #[inline]
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<DB::Row, Error>>
     where
@@ -205,6 +232,11 @@ where
     }
 
     /// Execute the query and returns exactly one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::RowNotFound`] if the query returns no rows.
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     #[inline]
     pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<DB::Row, Error>
     where
@@ -216,6 +248,10 @@ where
     }
 
     /// Execute the query and returns at most one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     #[inline]
     pub async fn fetch_optional<'e, 'c: 'e, E>(self, executor: E) -> Result<Option<DB::Row>, Error>
     where
@@ -251,6 +287,21 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.inner.deadline
+    }
+}
+
+impl<'q, DB: Database, F, A> Map<'q, DB, F, A> {
+    /// Set an absolute deadline by which the query must have completed.
+    ///
+    /// See [`Query::with_deadline`].
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.inner = self.inner.with_deadline(deadline);
+        self
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -359,6 +410,11 @@ where
     }
 
     /// Execute the query and returns exactly one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::RowNotFound`] if the query returns no rows.
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<O, Error>
     where
         'q: 'e,
@@ -376,6 +432,10 @@ where
     }
 
     /// Execute the query and returns at most one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     pub async fn fetch_optional<'e, 'c: 'e, E>(mut self, executor: E) -> Result<Option<O>, Error>
     where
         'q: 'e,
@@ -406,6 +466,7 @@ where
         arguments: Some(Default::default()),
         statement: Either::Right(statement),
         persistent: true,
+        deadline: None,
     }
 }
 
@@ -423,6 +484,7 @@ where
         arguments: Some(arguments),
         statement: Either::Right(statement),
         persistent: true,
+        deadline: None,
     }
 }
 
@@ -436,6 +498,7 @@ where
         arguments: Some(Default::default()),
         statement: Either::Left(sql),
         persistent: true,
+        deadline: None,
     }
 }
 
@@ -450,5 +513,6 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql),
         persistent: true,
+        deadline: None,
     }
 }
@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::time::Instant;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -45,6 +46,11 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.inner.deadline()
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -57,6 +63,16 @@ impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments
     }
 }
 
+impl<'q, DB: Database, O, A> QueryAs<'q, DB, O, A> {
+    /// Set an absolute deadline by which the query must have completed.
+    ///
+    /// See [`Query::with_deadline`](Query::with_deadline).
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.inner = self.inner.with_deadline(deadline);
+        self
+    }
+}
+
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
 where
     DB: Database + HasStatementCache,
@@ -134,6 +150,11 @@ where
     }
 
     /// Execute the query and returns exactly one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::RowNotFound`] if the query returns no rows.
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<O, Error>
     where
         'q: 'e,
@@ -148,6 +169,10 @@ where
     }
 
     /// Execute the query and returns at most one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     pub async fn fetch_optional<'e, 'c: 'e, E>(self, executor: E) -> Result<Option<O>, Error>
     where
         'q: 'e,
@@ -433,6 +433,65 @@ where
         separated.query_builder
     }
 
+    /// Push an `IN` list with a bind argument for each item in `values`,
+    /// e.g. `IN ($1, $2, $3)` for Postgres or `IN (?, ?, ?)` for MySQL and SQLite.
+    ///
+    /// Placeholders continue from any arguments bound before, so this can be combined freely
+    /// with [`.push_bind()`][Self::push_bind]. See that method for the limits on the number of
+    /// bind arguments in a single query.
+    ///
+    /// ### Notes
+    ///
+    /// An empty `values` produces `IN (NULL)` instead of the syntactically invalid `IN ()`.
+    /// The comparison then matches no rows, but keep in mind that its negation doesn't match
+    /// any rows either: `x NOT IN (NULL)` is `NULL`, not `TRUE`.
+    ///
+    /// If `values` can have many different lengths, you may want to call
+    /// [`.persistent(false)`][Query::persistent] after [`.build()`][Self::build] to avoid
+    /// filling up the connection's prepared statement cache.
+    ///
+    /// ### Example (MySQL)
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mysql")]
+    /// # {
+    /// use sqlx::{Execute, MySql, QueryBuilder};
+    ///
+    /// let mut query_builder: QueryBuilder<MySql> =
+    ///     QueryBuilder::new("SELECT * FROM food WHERE name ");
+    ///
+    /// query_builder.push_in_list(["pizza", "chips"]);
+    ///
+    /// let query = query_builder.build();
+    /// assert_eq!(query.sql(), "SELECT * FROM food WHERE name IN (?, ?)");
+    /// # }
+    /// ```
+    pub fn push_in_list<I, T>(&mut self, values: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: 'args + Encode<'args, DB> + Send + Type<DB>,
+    {
+        self.sanity_check();
+
+        self.push("IN (");
+
+        let mut separated = self.separated(", ");
+        let mut is_empty = true;
+
+        for value in values {
+            separated.push_bind(value);
+            is_empty = false;
+        }
+
+        if is_empty {
+            separated.push_unseparated("NULL");
+        }
+
+        separated.push_unseparated(")");
+
+        separated.query_builder
+    }
+
     /// Produce an executable query from this builder.
     ///
     /// ### Note: Query is not Checked
@@ -453,6 +512,7 @@ where
             arguments: self.arguments.take(),
             database: PhantomData,
             persistent: true,
+            deadline: None,
         }
     }
 
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
@@ -42,6 +44,11 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.inner.deadline()
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -54,6 +61,16 @@ impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Argum
     }
 }
 
+impl<'q, DB: Database, O, A> QueryScalar<'q, DB, O, A> {
+    /// Set an absolute deadline by which the query must have completed.
+    ///
+    /// See [`Query::with_deadline`](crate::query::Query::with_deadline).
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.inner = self.inner.with_deadline(deadline);
+        self
+    }
+}
+
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
 where
     DB: Database + HasStatementCache,
@@ -132,6 +149,11 @@ where
     }
 
     /// Execute the query and returns exactly one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::RowNotFound`] if the query returns no rows.
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     #[inline]
     pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<O, Error>
     where
@@ -145,6 +167,10 @@ where
     }
 
     /// Execute the query and returns at most one row.
+    ///
+    /// # Errors
+    ///
+    ///  * [`Error::TooManyRows`] if the query returns more than one row.
     #[inline]
     pub async fn fetch_optional<'e, 'c: 'e, E>(self, executor: E) -> Result<Option<O>, Error>
     where
@@ -1,4 +1,4 @@
-use crate::column::ColumnIndex;
+use crate::column::{Column, ColumnIndex};
 use crate::database::{Database, HasValueRef};
 use crate::decode::Decode;
 use crate::error::{mismatched_types, Error};
@@ -119,14 +119,14 @@ pub trait Row: Unpin + Send + Sync + 'static {
 
             if !ty.is_null() && !T::compatible(&ty) {
                 return Err(Error::ColumnDecode {
-                    index: format!("{index:?}"),
+                    index: describe_index(self, &index),
                     source: mismatched_types::<Self::Database, T>(&ty),
                 });
             }
         }
 
         T::decode(value).map_err(|source| Error::ColumnDecode {
-            index: format!("{index:?}"),
+            index: describe_index(self, &index),
             source,
         })
     }
@@ -156,7 +156,7 @@ pub trait Row: Unpin + Send + Sync + 'static {
         let value = self.try_get_raw(&index)?;
 
         T::decode(value).map_err(|source| Error::ColumnDecode {
-            index: format!("{index:?}"),
+            index: describe_index(self, &index),
             source,
         })
     }
@@ -178,3 +178,20 @@ pub trait Row: Unpin + Send + Sync + 'static {
     where
         I: ColumnIndex<Self>;
 }
+
+/// Describe the column at `index` for a [`Error::ColumnDecode`], adding the name of the column
+/// when it was accessed by position.
+pub(crate) fn describe_index<R: Row + ?Sized, I: ColumnIndex<R>>(row: &R, index: &I) -> String {
+    let description = format!("{index:?}");
+
+    let name = index
+        .index(row)
+        .ok()
+        .and_then(|i| row.columns().get(i))
+        .map(|column| format!("{:?}", column.name()));
+
+    match name {
+        Some(name) if name != description => format!("{description} ({name})"),
+        _ => description,
+    }
+}
@@ -20,6 +20,29 @@ pub trait TransactionManager {
         conn: &mut <Self::Database as Database>::Connection,
     ) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Begin a new transaction with the given options, or establish a savepoint within the
+    /// active transaction if the options are the default.
+    ///
+    /// The default implementation only accepts the default options.
+    fn begin_with(
+        conn: &mut <Self::Database as Database>::Connection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        if options == TransactionOptions::default() {
+            Self::begin(conn)
+        } else {
+            Box::pin(async move {
+                Err(Error::Configuration(
+                    format!(
+                        "transaction options are not supported by {}",
+                        <Self::Database as Database>::NAME
+                    )
+                    .into(),
+                ))
+            })
+        }
+    }
+
     /// Commit the active transaction or release the most recent savepoint.
     fn commit(
         conn: &mut <Self::Database as Database>::Connection,
@@ -34,6 +57,113 @@ pub trait TransactionManager {
     fn start_rollback(conn: &mut <Self::Database as Database>::Connection);
 }
 
+/// The isolation level of a transaction, as defined by the SQL standard.
+///
+/// See [`TransactionOptions::isolation_level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// The name of the isolation level as it appears in SQL, e.g. `REPEATABLE READ`.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Options for starting a transaction with [`Connection::begin_with`] or [`Pool::begin_with`].
+///
+/// The defaults leave the isolation level and access mode up to the database; beginning a
+/// transaction with them is the same as calling `begin()`.
+///
+/// These options are supported by Postgres and MySQL (which does not support `DEFERRABLE`).
+/// They can only be set for a new transaction, not for a savepoint within one.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::{IsolationLevel, TransactionOptions};
+///
+/// let mut tx = pool
+///     .begin_with(
+///         TransactionOptions::new()
+///             .isolation_level(IsolationLevel::Serializable)
+///             .read_only(true),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Connection::begin_with`]: crate::connection::Connection::begin_with()
+/// [`Pool::begin_with`]: crate::pool::Pool::begin_with()
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TransactionOptions {
+    isolation_level: Option<IsolationLevel>,
+    read_only: bool,
+    deferrable: bool,
+}
+
+impl TransactionOptions {
+    /// Creates a new, default set of options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the isolation level of the transaction.
+    ///
+    /// By default the session's isolation level is used.
+    pub fn isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    /// Sets whether the transaction is read-only; writes within it fail.
+    ///
+    /// Defaults to `false`, which uses the session's access mode.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets whether the transaction is deferrable (Postgres only).
+    ///
+    /// A `SERIALIZABLE`, `READ ONLY`, `DEFERRABLE` transaction may block when it starts, but then
+    /// runs without the overhead of serializable checks and cannot fail with a serialization
+    /// error.
+    ///
+    /// Defaults to `false`.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+
+    /// Get the isolation level, if set.
+    pub fn get_isolation_level(&self) -> Option<IsolationLevel> {
+        self.isolation_level
+    }
+
+    /// Get whether the transaction is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Get whether the transaction is deferrable.
+    pub fn is_deferrable(&self) -> bool {
+        self.deferrable
+    }
+}
+
 /// An in-progress database transaction or savepoint.
 ///
 /// A transaction starts with a call to [`Pool::begin`] or [`Connection::begin`].
@@ -78,6 +208,23 @@ where
         })
     }
 
+    #[doc(hidden)]
+    pub fn begin_with(
+        conn: impl Into<MaybePoolConnection<'c, DB>>,
+        options: TransactionOptions,
+    ) -> BoxFuture<'c, Result<Self, Error>> {
+        let mut conn = conn.into();
+
+        Box::pin(async move {
+            DB::TransactionManager::begin_with(&mut conn, options).await?;
+
+            Ok(Self {
+                connection: conn,
+                open: true,
+            })
+        })
+    }
+
     /// Commits this transaction or savepoint.
     pub async fn commit(mut self) -> Result<(), Error> {
         DB::TransactionManager::commit(&mut self.connection).await?;
@@ -260,9 +407,12 @@ pub fn rollback_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
     if depth == 1 {
         Cow::Borrowed("ROLLBACK")
     } else {
+        // rolling back to a savepoint keeps it around, so release it as well; otherwise
+        // repeatedly beginning and rolling back a savepoint piles them up in the transaction
+        let savepoint = format!("_sqlx_savepoint_{}", depth - 1);
+
         Cow::Owned(format!(
-            "ROLLBACK TO SAVEPOINT _sqlx_savepoint_{}",
-            depth - 1
+            "ROLLBACK TO SAVEPOINT {savepoint}; RELEASE SAVEPOINT {savepoint}"
         ))
     }
 }
@@ -36,6 +36,11 @@ use crate::types::Type;
 /// }
 /// ```
 ///
+/// By default, `serde_json` parses JSON numbers that are not integers as `f64`, which loses
+/// precision for decimals with many significant digits. Enable the `json-arbitrary-precision`
+/// feature to keep the exact textual representation of every number instead. Note that this
+/// enables `serde_json/arbitrary_precision` for your whole dependency graph.
+///
 /// Can also be used to turn the json/jsonb into a hashmap
 /// ```
 /// use std::collections::HashMap;
@@ -89,6 +94,63 @@ impl<T> AsMut<T> for Json<T> {
     }
 }
 
+/// A pre-serialized JSON document, bound as-is without being parsed or re-serialized.
+///
+/// Useful when the JSON text is already at hand (e.g. read back from a cache) and going through
+/// [`JsonValue`] would mean parsing it only to serialize it again.
+///
+/// The string is **not** validated; the database will reject it if it is not valid JSON.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::types::RawJson;
+///
+/// let cached = r#"{"name":"Joe","age":33}"#;
+///
+/// sqlx::query("INSERT INTO people (data) VALUES ($1)")
+///     .bind(RawJson(cached))
+///     .execute(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct RawJson<'a>(pub &'a str);
+
+impl<'a> From<&'a str> for RawJson<'a> {
+    fn from(value: &'a str) -> Self {
+        Self(value)
+    }
+}
+
+impl<'a> From<&'a JsonRawValue> for RawJson<'a> {
+    fn from(value: &'a JsonRawValue) -> Self {
+        Self(value.get())
+    }
+}
+
+impl Deref for RawJson<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<DB> Type<DB> for RawJson<'_>
+where
+    for<'a> Json<&'a JsonRawValue>: Type<DB>,
+    DB: Database,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Json<&JsonRawValue> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Json<&JsonRawValue> as Type<DB>>::compatible(ty)
+    }
+}
+
 const JSON_SERIALIZE_ERR: &str = "failed to encode value as JSON; the most likely cause is \
                                   attempting to serialize a map with a non-string key type";
 
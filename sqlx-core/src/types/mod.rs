@@ -79,7 +79,7 @@ pub mod mac_address {
 }
 
 #[cfg(feature = "json")]
-pub use json::{Json, JsonRawValue, JsonValue};
+pub use json::{Json, JsonRawValue, JsonValue, RawJson};
 
 /// Indicates that a SQL type is supported for a database.
 ///
@@ -174,6 +174,9 @@ pub use json::{Json, JsonRawValue, JsonValue};
 /// enum Color { Red, Green, Blue }
 /// ```
 ///
+/// In PostgreSQL, the OID of the named type is looked up on first use and cached per connection.
+/// Values of the enum type and of textual types (e.g. `color::text`) can both be decoded.
+///
 /// ### Records
 ///
 /// User-defined composite types are supported through deriving a `struct`.
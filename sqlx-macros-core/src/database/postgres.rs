@@ -23,6 +23,8 @@ impl_database_ext! {
 
         sqlx::postgres::types::PgLQuery,
 
+        sqlx::postgres::types::PgXml,
+
         #[cfg(feature = "uuid")]
         sqlx::types::Uuid,
 
@@ -76,17 +76,20 @@ fn expand_derive_from_row_struct(
         default_instance = None;
     }
 
-    let reads: Vec<Stmt> = fields
+    let reads = fields
         .iter()
-        .filter_map(|field| -> Option<Stmt> {
+        .filter_map(|field| -> Option<syn::Result<Stmt>> {
             let id = &field.ident.as_ref()?;
-            let attributes = parse_child_attributes(&field.attrs).unwrap();
+            let attributes = match parse_child_attributes(&field.attrs) {
+                Ok(attributes) => attributes,
+                Err(e) => return Some(Err(e)),
+            };
             let ty = &field.ty;
 
             if attributes.skip {
-                return Some(parse_quote!(
+                return Some(Ok(parse_quote!(
                     let #id: #ty = Default::default();
-                ));
+                )));
             }
 
             let id_s = attributes
@@ -115,7 +118,7 @@ fn expand_derive_from_row_struct(
                 // Flatten + Try from
                 (true, Some(try_from), false) => {
                     predicates.push(parse_quote!(#try_from: ::sqlx::FromRow<#lifetime, R>));
-                    parse_quote!(<#try_from as ::sqlx::FromRow<#lifetime, R>>::from_row(row).and_then(|v| <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v).map_err(|e| ::sqlx::Error::ColumnNotFound("FromRow: try_from failed".to_string())))) 
+                    parse_quote!(<#try_from as ::sqlx::FromRow<#lifetime, R>>::from_row(row).and_then(|v| <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v).map_err(|_| ::sqlx::Error::Decode(::std::convert::From::from("FromRow: try_from failed"))))) 
                 }
                 // Flatten + Json
                 (true, _, true) => {
@@ -127,7 +130,7 @@ fn expand_derive_from_row_struct(
                         .push(parse_quote!(#try_from: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(#try_from: ::sqlx::types::Type<R::Database>)); 
 
-                    parse_quote!(row.try_get(#id_s).and_then(|v| <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v).map_err(|e| ::sqlx::Error::ColumnNotFound("FromRow: try_from failed".to_string()))))
+                    parse_quote!(row.try_get(#id_s).and_then(|v| <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v).map_err(|_| ::sqlx::Error::ColumnDecode { index: ::std::format!("{:?}", #id_s), source: ::std::convert::From::from("FromRow: try_from failed") })))
                 }
                 // Try from + Json
                 (false, Some(try_from), true) => {
@@ -138,7 +141,7 @@ fn expand_derive_from_row_struct(
                     parse_quote!(
                         row.try_get::<::sqlx::types::Json<_>, _>(#id_s).and_then(|v|
                             <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v.0)
-                            .map_err(|e| ::sqlx::Error::ColumnNotFound("FromRow: try_from failed".to_string()))
+                            .map_err(|_| ::sqlx::Error::ColumnDecode { index: ::std::format!("{:?}", #id_s), source: ::std::convert::From::from("FromRow: try_from failed") })
                         )
                     )
                 },
@@ -153,26 +156,26 @@ fn expand_derive_from_row_struct(
             };
 
             if attributes.default {
-                Some(parse_quote!(let #id: #ty = #expr.or_else(|e| match e {
+                Some(Ok(parse_quote!(let #id: #ty = #expr.or_else(|e| match e {
                 ::sqlx::Error::ColumnNotFound(_) => {
                     ::std::result::Result::Ok(Default::default())
                 },
                 e => ::std::result::Result::Err(e)
-            })?;))
+            })?;)))
             } else if container_attributes.default {
-                Some(parse_quote!(let #id: #ty = #expr.or_else(|e| match e {
+                Some(Ok(parse_quote!(let #id: #ty = #expr.or_else(|e| match e {
                     ::sqlx::Error::ColumnNotFound(_) => {
                         ::std::result::Result::Ok(__default.#id)
                     },
                     e => ::std::result::Result::Err(e)
-                })?;))
+                })?;)))
             } else {
-                Some(parse_quote!(
+                Some(Ok(parse_quote!(
                     let #id: #ty = #expr?;
-                ))
+                )))
             }
         })
-        .collect();
+        .collect::<syn::Result<Vec<Stmt>>>()?;
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
 
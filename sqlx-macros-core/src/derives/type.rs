@@ -167,7 +167,10 @@ fn expand_derive_has_sql_type_strong_enum(
                 }
 
                 fn compatible(ty: &::sqlx::mysql::MySqlTypeInfo) -> ::std::primitive::bool {
+                    // `ENUM` columns are sent as strings flagged as enums, and the values may also
+                    // be stored in plain string columns
                     *ty == ::sqlx::mysql::MySqlTypeInfo::__enum()
+                        || <::std::primitive::str as ::sqlx::Type<::sqlx::MySql>>::compatible(ty)
                 }
             }
         ));
@@ -182,6 +185,12 @@ fn expand_derive_has_sql_type_strong_enum(
                 fn type_info() -> ::sqlx::postgres::PgTypeInfo {
                     ::sqlx::postgres::PgTypeInfo::with_name(#ty_name)
                 }
+
+                fn compatible(ty: &::sqlx::postgres::PgTypeInfo) -> ::std::primitive::bool {
+                    // enum labels are also commonly stored in, or cast to, plain text columns
+                    *ty == ::sqlx::postgres::PgTypeInfo::with_name(#ty_name)
+                        || <::std::primitive::str as ::sqlx::Type<::sqlx::Postgres>>::compatible(ty)
+                }
             }
         ));
     }
@@ -5,6 +5,7 @@ use std::io::Write as _;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::SystemTime;
 
 use once_cell::sync::Lazy;
 use serde::{Serialize, Serializer};
@@ -65,7 +66,10 @@ impl<DB: Database> Serialize for SerializeDbName<DB> {
     }
 }
 
-static OFFLINE_DATA_CACHE: Lazy<Mutex<HashMap<PathBuf, DynQueryData>>> =
+/// Parsed query data along with the modification time of the file it was read from.
+type CachedQueryData = (Option<SystemTime>, DynQueryData);
+
+static OFFLINE_DATA_CACHE: Lazy<Mutex<HashMap<PathBuf, CachedQueryData>>> =
     Lazy::new(Default::default);
 
 /// Offline query data
@@ -79,7 +83,7 @@ pub struct DynQueryData {
 
 impl DynQueryData {
     /// Loads a query given the path to its "query-<hash>.json" file. Subsequent calls for the same
-    /// path are retrieved from an in-memory cache.
+    /// path are retrieved from an in-memory cache, unless the file has been modified since.
     pub fn from_data_file(path: impl AsRef<Path>, query: &str) -> crate::Result<Self> {
         let path = path.as_ref();
 
@@ -91,16 +95,22 @@ impl DynQueryData {
                 *guard = Default::default();
                 guard
             });
-        if let Some(cached) = cache.get(path).cloned() {
-            if query != cached.query {
-                return Err("hash collision for saved query data".into());
+
+        // A long-lived proc-macro server (e.g. rust-analyzer) may outlive a `cargo sqlx prepare`.
+        let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+
+        if let Some((cached_modified, cached)) = cache.get(path) {
+            if modified.is_some() && *cached_modified == modified {
+                if query != cached.query {
+                    return Err("hash collision for saved query data".into());
+                }
+                return Ok(cached.clone());
             }
-            return Ok(cached);
         }
 
-        #[cfg(procmacr2_semver_exempt)]
+        #[cfg(procmacro2_semver_exempt)]
         {
-            let path = path.as_ref().canonicalize()?;
+            let path = path.canonicalize()?;
             let path = path.to_str().ok_or_else(|| {
                 format!(
                     "query-<hash>.json path cannot be represented as a string: {:?}",
@@ -119,7 +129,7 @@ impl DynQueryData {
             return Err("hash collision for saved query data".into());
         }
 
-        let _ = cache.insert(path.to_owned(), dyn_data.clone());
+        let _ = cache.insert(path.to_owned(), (modified, dyn_data.clone()));
         Ok(dyn_data)
     }
 }
@@ -177,3 +187,52 @@ pub(super) fn hash_string(query: &str) -> String {
 
     hex::encode(Sha256::digest(query.as_bytes()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn write_query_data(path: &Path, describe: &str) {
+        let data = serde_json::json!({
+            "db_name": "PostgreSQL",
+            "query": "SELECT 1",
+            "describe": describe,
+            "hash": hash_string("SELECT 1"),
+        });
+
+        fs::write(path, data.to_string()).unwrap();
+    }
+
+    #[test]
+    fn it_reloads_a_rewritten_query_data_file() {
+        let path = std::env::temp_dir().join(format!(
+            "sqlx-macros-core-query-{}.json",
+            std::process::id()
+        ));
+
+        write_query_data(&path, "stale");
+        let data = DynQueryData::from_data_file(&path, "SELECT 1").unwrap();
+        assert_eq!(data.describe, "stale");
+
+        // served from the cache while the file is unchanged
+        let data = DynQueryData::from_data_file(&path, "SELECT 1").unwrap();
+        assert_eq!(data.describe, "stale");
+
+        write_query_data(&path, "fresh");
+
+        // the file system may not record a rewrite this quick as a later modification
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified + Duration::from_secs(1))
+            .unwrap();
+
+        let data = DynQueryData::from_data_file(&path, "SELECT 1").unwrap();
+        assert_eq!(data.describe, "fresh");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
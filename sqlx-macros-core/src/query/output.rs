@@ -9,6 +9,7 @@ use crate::database::DatabaseExt;
 
 use crate::query::QueryMacroInput;
 use std::fmt::{self, Display, Formatter};
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::Token;
 
@@ -76,9 +77,30 @@ impl Display for DisplayColumn<'_> {
 }
 
 pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Result<Vec<RustColumn>> {
-    (0..describe.columns().len())
+    let columns = (0..describe.columns().len())
         .map(|i| column_to_rust(describe, i))
-        .collect::<crate::Result<Vec<_>>>()
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    // otherwise the generated struct fails to compile with a "field is already declared" error
+    // that doesn't point at the query
+    for (i, column) in columns.iter().enumerate() {
+        if let Some(prev) = columns[..i].iter().position(|it| it.ident == column.ident) {
+            let display = |idx: usize| DisplayColumn {
+                idx,
+                name: describe.columns()[idx].name(),
+            };
+
+            return Err(format!(
+                "{} and {} both map to the field `{}`; give one of them a different name with `AS`",
+                display(prev),
+                display(i),
+                column.ident.unraw(),
+            )
+            .into());
+        }
+    }
+
+    Ok(columns)
 }
 
 fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::Result<RustColumn> {
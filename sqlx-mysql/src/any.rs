@@ -15,7 +15,7 @@ use sqlx_core::connection::Connection;
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{TransactionManager, TransactionOptions};
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = MySql);
 
@@ -40,6 +40,10 @@ impl AnyConnectionBackend for MySqlConnection {
         MySqlTransactionManager::begin(self)
     }
 
+    fn begin_with(&mut self, options: TransactionOptions) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        MySqlTransactionManager::begin_with(self, options)
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         MySqlTransactionManager::commit(self)
     }
@@ -64,6 +68,18 @@ impl AnyConnectionBackend for MySqlConnection {
         Connection::should_flush(self)
     }
 
+    fn before_release(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::before_release(self)
+    }
+
+    fn is_broken(&self) -> bool {
+        Connection::is_broken(self)
+    }
+
+    fn set_poison_flag(&mut self, flag: std::sync::Arc<std::sync::atomic::AtomicBool>) {
+        Connection::set_poison_flag(self, flag)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -80,7 +96,7 @@ impl AnyConnectionBackend for MySqlConnection {
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
         Box::pin(
-            self.run(query, args, persistent)
+            self.run(query, args, persistent, None, None)
                 .try_flatten_stream()
                 .map(|res| {
                     Ok(match res? {
@@ -100,14 +116,22 @@ impl AnyConnectionBackend for MySqlConnection {
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
         Box::pin(async move {
-            let stream = self.run(query, args, persistent).await?;
+            let stream = self.run(query, args, persistent, None, None).await?;
             futures_util::pin_mut!(stream);
 
-            if let Some(Either::Right(row)) = stream.try_next().await? {
-                return Ok(Some(AnyRow::try_from(&row)?));
+            let mut row = None;
+
+            while let Some(res) = stream.try_next().await? {
+                if let Either::Right(r) = res {
+                    if row.is_some() {
+                        return Err(sqlx_core::Error::TooManyRows);
+                    }
+
+                    row = Some(AnyRow::try_from(&r)?);
+                }
             }
 
-            Ok(None)
+            Ok(row)
         })
     }
 
@@ -9,6 +9,8 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: Vec<u8>,
+    // where each encoded value ends in `values`
+    pub(crate) value_ends: Vec<usize>,
 }
 
 impl MySqlArguments {
@@ -25,6 +27,32 @@ impl MySqlArguments {
         if let IsNull::Yes = value.encode(&mut self.values) {
             self.null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
         }
+
+        self.value_ends.push(self.values.len());
+    }
+
+    /// Build the arguments for a query whose placeholders refer to the arguments at the
+    /// given indices, in order; an argument may be repeated or left out.
+    pub(crate) fn reorder(&self, order: &[usize]) -> MySqlArguments {
+        let mut arguments = MySqlArguments {
+            null_bitmap: vec![0; order.len().div_ceil(8)],
+            ..Default::default()
+        };
+
+        for (index, &from) in order.iter().enumerate() {
+            let start = from.checked_sub(1).map_or(0, |prev| self.value_ends[prev]);
+            let end = self.value_ends[from];
+
+            arguments.types.push(self.types[from].clone());
+            arguments.values.extend_from_slice(&self.values[start..end]);
+            arguments.value_ends.push(arguments.values.len());
+
+            if self.null_bitmap[from / 8] & (1 << (from % 8)) as u8 != 0 {
+                arguments.null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
+            }
+        }
+
+        arguments
     }
 
     #[doc(hidden)]
@@ -38,6 +66,7 @@ impl<'q> Arguments<'q> for MySqlArguments {
 
     fn reserve(&mut self, len: usize, size: usize) {
         self.types.reserve(len);
+        self.value_ends.reserve(len);
         self.values.reserve(size);
     }
 
@@ -0,0 +1,62 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::connection::Connection;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::{MySqlConnectOptions, MySqlConnection};
+
+/// A handle for cancelling the query currently running on a [`MySqlConnection`].
+///
+/// Obtained with [`MySqlConnection::cancel_token()`]. The token does not borrow the connection,
+/// so it can be used from another task (or the same task, between polls of a
+/// [`fetch()`][crate::Executor::fetch] stream) while a query is in progress.
+///
+/// Cancellation is best-effort: a new connection to the server is opened, as the same user,
+/// to send `KILL QUERY` for the connection's id. If it arrives before the query completes, the
+/// query fails with an error (`ER_QUERY_INTERRUPTED`, error code 1317); the connection itself
+/// stays open and can be used for further queries. Otherwise it has no effect.
+///
+/// See [the MySQL manual](https://dev.mysql.com/doc/refman/8.0/en/kill.html) for details.
+#[derive(Clone)]
+pub struct MySqlCancelToken {
+    options: Arc<MySqlConnectOptions>,
+    connection_id: u32,
+}
+
+impl MySqlCancelToken {
+    /// Ask the server to cancel the query currently running on the connection.
+    ///
+    /// This returns once the server has acknowledged the request, which does not wait for the
+    /// query to stop.
+    pub async fn cancel(&self) -> Result<(), Error> {
+        // the callback is meant for connections that run queries, not this one
+        let mut options = MySqlConnectOptions::clone(&self.options);
+        options.after_connect = None;
+
+        let mut conn = MySqlConnection::establish(&options).await?;
+
+        conn.execute(&*format!("KILL QUERY {}", self.connection_id))
+            .await?;
+
+        conn.close().await
+    }
+}
+
+impl MySqlConnection {
+    /// Get a token for cancelling the query currently running on this connection.
+    pub fn cancel_token(&self) -> MySqlCancelToken {
+        MySqlCancelToken {
+            options: Arc::clone(&self.connect_options),
+            connection_id: self.stream.connection_id,
+        }
+    }
+}
+
+impl Debug for MySqlCancelToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MySqlCancelToken")
+            .field("connection_id", &self.connection_id)
+            .finish()
+    }
+}
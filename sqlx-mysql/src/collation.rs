@@ -94,6 +94,45 @@ impl CharSet {
         }
     }
 
+    /// Check that the character set can be used for the connection with `collation`.
+    ///
+    /// The server does not reject an unusable character set or collation in the handshake, it
+    /// silently falls back to its own default, so this is checked before connecting.
+    pub(crate) fn check_client_collation(&self, collation: Collation) -> Result<(), Error> {
+        // https://dev.mysql.com/doc/refman/8.0/en/charset-connection.html#charset-connection-impermissible-client-charset
+        if matches!(
+            self,
+            CharSet::ucs2 | CharSet::utf16 | CharSet::utf16le | CharSet::utf32
+        ) {
+            return Err(Error::Configuration(
+                format!(
+                    "MySQL charset {} cannot be used as a client character set",
+                    self.as_str()
+                )
+                .into(),
+            ));
+        }
+
+        // collations are named after their character set, e.g. `utf8mb4_unicode_ci`
+        let belongs = match collation.as_str().strip_prefix(self.as_str()) {
+            Some(rest) => rest.is_empty() || rest.starts_with('_'),
+            None => false,
+        };
+
+        if !belongs {
+            return Err(Error::Configuration(
+                format!(
+                    "MySQL collation {} is not valid for charset {}",
+                    collation.as_str(),
+                    self.as_str()
+                )
+                .into(),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn default_collation(&self) -> Collation {
         match self {
             CharSet::armscii8 => Collation::armscii8_general_ci,
@@ -898,3 +937,30 @@ impl FromStr for Collation {
         })
     }
 }
+
+#[test]
+fn test_check_client_collation() {
+    let check = |charset: &str, collation: &str| {
+        charset
+            .parse::<CharSet>()
+            .unwrap()
+            .check_client_collation(collation.parse().unwrap())
+    };
+
+    assert!(check("utf8mb4", "utf8mb4_unicode_ci").is_ok());
+    assert!(check("utf8mb4", "utf8mb4_bin").is_ok());
+    assert!(check("utf8", "utf8_general_ci").is_ok());
+    assert!(check("binary", "binary").is_ok());
+
+    assert!(check("utf8", "utf8mb4_unicode_ci").is_err());
+    assert!(check("latin1", "utf8mb4_bin").is_err());
+    assert!(check("utf16", "utf16_general_ci").is_err());
+    assert!(check("ucs2", "ucs2_general_ci").is_err());
+
+    for charset in ["utf8mb4", "utf8", "latin1", "ascii", "binary"] {
+        let charset: CharSet = charset.parse().unwrap();
+        assert!(charset
+            .check_client_collation(charset.default_collation())
+            .is_ok());
+    }
+}
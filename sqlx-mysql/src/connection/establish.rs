@@ -1,46 +1,275 @@
-use bytes::buf::Buf;
+use bytes::buf::{Buf, Chain};
 use bytes::Bytes;
 use futures_core::future::BoxFuture;
+use std::future::Future;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use crate::collation::{CharSet, Collation};
-use crate::common::StatementCache;
+use crate::common::{LatencyEwma, StatementCache};
 use crate::connection::{tls, MySqlStream, MAX_PACKET_SIZE};
 use crate::error::Error;
 use crate::net::{Socket, WithSocket};
+use crate::protocol::auth::AuthPlugin;
 use crate::protocol::connect::{
     AuthSwitchRequest, AuthSwitchResponse, Handshake, HandshakeResponse,
 };
+use crate::protocol::text::ChangeUser;
 use crate::protocol::Capabilities;
 use crate::{MySqlConnectOptions, MySqlConnection, MySqlSslMode};
 
 impl MySqlConnection {
     pub(crate) async fn establish(options: &MySqlConnectOptions) -> Result<Self, Error> {
-        let do_handshake = DoHandshake::new(options)?;
+        retry_rsa_exchange(options.rsa_exchange_retries, |rsa_exchange_failed| {
+            Self::establish_once(options, rsa_exchange_failed)
+        })
+        .await
+    }
+
+    async fn establish_once(
+        options: &MySqlConnectOptions,
+        rsa_exchange_failed: Arc<AtomicBool>,
+    ) -> Result<Self, Error> {
+        let do_handshake = DoHandshake::new(options, rsa_exchange_failed)?;
 
         let handshake = match &options.socket {
             Some(path) => crate::net::connect_uds(path, do_handshake).await?,
-            None => crate::net::connect_tcp(&options.host, options.port, do_handshake).await?,
+            None => {
+                crate::net::connect_tcp(
+                    &options.host,
+                    options.port,
+                    options.tcp_keepalive,
+                    do_handshake,
+                )
+                .await?
+            }
         };
 
-        let stream = handshake.await?;
+        let (stream, auth_plugin, auth_nonce) = handshake.await?;
 
         Ok(Self {
             stream,
             transaction_depth: 0,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
+            dropped_statements: Arc::default(),
             log_settings: options.log_settings.clone(),
+            auth_plugin,
+            auth_nonce,
+            session_init: String::new(),
+            reset_on_release: options.reset_on_release,
+            numbered_placeholders: options.numbered_placeholders,
+            zero_date_policy: options.zero_date_policy,
+            latency: LatencyEwma::default(),
+            connect_options: Arc::new(options.clone()),
         })
     }
+
+    /// Re-authenticate this connection as the user in `options`, without reconnecting.
+    ///
+    /// This sends `COM_CHANGE_USER`, which runs the whole authentication exchange again using
+    /// the username, password and database of `options`. Other options, such as the host or
+    /// the SSL mode, are ignored.
+    ///
+    /// The server resets the session as if it was a new connection: open transactions are
+    /// rolled back, temporary tables and user variables are dropped, and all prepared
+    /// statements are deallocated (so the statement cache is cleared as well). The session
+    /// settings SQLx applies when connecting are then restored, and
+    /// [`MySqlConnectOptions::after_connect`] is run again.
+    ///
+    /// The callback that runs is the one this connection was opened with; like the other
+    /// options, the `after_connect` of `options` is ignored.
+    ///
+    /// If authentication fails, the server closes the connection.
+    pub async fn change_user(&mut self, options: &MySqlConnectOptions) -> Result<(), Error> {
+        self.wait_until_ready().await?;
+
+        let plugin = self.auth_plugin;
+        let password = options.password.as_deref();
+
+        let auth_response = if let (Some(plugin), Some(password)) = (plugin, password) {
+            Some(
+                plugin
+                    .scramble(&mut self.stream, password, &self.auth_nonce)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        self.stream.sequence_id = 0;
+        self.stream.write_packet(ChangeUser {
+            username: &options.username,
+            auth_response: auth_response.as_deref().map(Vec::as_slice),
+            database: options.database.as_deref(),
+            collation: self.stream.collation as u8,
+            auth_plugin: plugin,
+        });
+
+        self.stream.flush().await?;
+
+        // the server has forgotten everything about the previous session
+        self.cache_statement.clear();
+        self.forget_prepared_statements();
+        self.transaction_depth = 0;
+
+        // a query can only be killed by its own user (or a privileged one)
+        let connect_options = Arc::make_mut(&mut self.connect_options);
+        connect_options.username.clone_from(&options.username);
+        connect_options.password.clone_from(&options.password);
+        connect_options.database.clone_from(&options.database);
+
+        let nonce = clone_nonce(&self.auth_nonce);
+
+        // the server may have switched us to another plugin, whose challenge is used from now on
+        (self.auth_plugin, self.auth_nonce) = authenticate(
+            &mut self.stream,
+            plugin,
+            password,
+            nonce,
+            options.enable_cleartext_plugin,
+        )
+        .await?;
+
+        self.init_session().await
+    }
+}
+
+// the server closes the connection when the RSA password exchange fails, so the exchange is
+// retried with a new connection if it fails on a transient network error
+async fn retry_rsa_exchange<T, F, Fut>(mut retries: u32, mut attempt: F) -> Result<T, Error>
+where
+    F: FnMut(Arc<AtomicBool>) -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    loop {
+        let rsa_exchange_failed = Arc::new(AtomicBool::new(false));
+
+        match attempt(Arc::clone(&rsa_exchange_failed)).await {
+            Err(Error::Io(error))
+                if retries > 0
+                    && rsa_exchange_failed.load(Ordering::Relaxed)
+                    && matches!(
+                        error.kind(),
+                        io::ErrorKind::ConnectionReset
+                            | io::ErrorKind::ConnectionAborted
+                            | io::ErrorKind::BrokenPipe
+                            | io::ErrorKind::UnexpectedEof
+                    ) =>
+            {
+                retries -= 1;
+
+                tracing::debug!(
+                    %error,
+                    retries_left = retries,
+                    "RSA password exchange failed; retrying with a new connection"
+                );
+            }
+
+            result => return result,
+        }
+    }
+}
+
+fn clone_nonce(nonce: &Chain<Bytes, Bytes>) -> Chain<Bytes, Bytes> {
+    nonce.first_ref().clone().chain(nonce.last_ref().clone())
+}
+
+// handle the server's response to a handshake response or `COM_CHANGE_USER`,
+// until authentication succeeds or fails
+//
+// returns the plugin and challenge that were in effect when authentication succeeded,
+// which differ from the initial ones if the server asked to switch plugins
+async fn authenticate(
+    stream: &mut MySqlStream,
+    mut plugin: Option<AuthPlugin>,
+    password: Option<&str>,
+    mut nonce: Chain<Bytes, Bytes>,
+    enable_cleartext_plugin: bool,
+) -> Result<(Option<AuthPlugin>, Chain<Bytes, Bytes>), Error> {
+    loop {
+        let packet = stream.recv_packet().await?;
+        match packet[0] {
+            0x00 => {
+                let ok = packet.ok()?;
+                stream.status = ok.status;
+
+                break;
+            }
+
+            0xfe => {
+                let switch: AuthSwitchRequest = packet.decode_with(enable_cleartext_plugin)?;
+
+                if matches!(switch.plugin, AuthPlugin::MySqlClearPassword) {
+                    check_cleartext_plugin(stream, enable_cleartext_plugin)?;
+                }
+
+                // any further exchange (e.g. the RSA path of `caching_sha2_password`)
+                // uses the new challenge
+                plugin = Some(switch.plugin);
+                nonce = switch.data.chain(Bytes::new());
+
+                let response = switch
+                    .plugin
+                    .scramble(stream, password.unwrap_or_default(), &nonce)
+                    .await?;
+
+                stream.write_packet(AuthSwitchResponse(&response));
+                stream.flush().await?;
+            }
+
+            id => {
+                if let (Some(plugin), Some(password)) = (plugin, password) {
+                    if plugin.handle(stream, packet, password, &nonce).await? {
+                        // plugin signaled authentication is ok
+                        break;
+                    }
+
+                    // plugin signaled to continue authentication
+                } else {
+                    return Err(err_protocol!(
+                        "unexpected packet 0x{:02x} during authentication",
+                        id
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok((plugin, nonce))
+}
+
+// `mysql_clear_password` sends the password as-is, so it is only used if enabled and the
+// connection is encrypted (or over a Unix socket)
+fn check_cleartext_plugin(
+    stream: &MySqlStream,
+    enable_cleartext_plugin: bool,
+) -> Result<(), Error> {
+    if !enable_cleartext_plugin {
+        return Err(err_protocol!("mysql_cleartext_plugin disabled"));
+    }
+
+    if !stream.is_secure {
+        return Err(err_protocol!(
+            "mysql_cleartext_plugin refused over an insecure connection"
+        ));
+    }
+
+    Ok(())
 }
 
 struct DoHandshake<'a> {
     options: &'a MySqlConnectOptions,
     charset: CharSet,
     collation: Collation,
+    rsa_exchange_failed: Arc<AtomicBool>,
 }
 
 impl<'a> DoHandshake<'a> {
-    fn new(options: &'a MySqlConnectOptions) -> Result<Self, Error> {
+    fn new(
+        options: &'a MySqlConnectOptions,
+        rsa_exchange_failed: Arc<AtomicBool>,
+    ) -> Result<Self, Error> {
         let charset: CharSet = options.charset.parse()?;
         let collation: Collation = options
             .collation
@@ -49,27 +278,37 @@ impl<'a> DoHandshake<'a> {
             .transpose()?
             .unwrap_or_else(|| charset.default_collation());
 
+        charset.check_client_collation(collation)?;
+
         if options.enable_cleartext_plugin
             && matches!(
                 options.ssl_mode,
                 MySqlSslMode::Disabled | MySqlSslMode::Preferred
             )
         {
-            log::warn!("Security warning: sending cleartext passwords without requiring SSL");
+            log::warn!(
+                "cleartext plugin enabled without requiring SSL; \
+                 it will be refused if the connection is not secure"
+            );
         }
 
         Ok(Self {
             options,
             charset,
             collation,
+            rsa_exchange_failed,
         })
     }
 
-    async fn do_handshake<S: Socket>(self, socket: S) -> Result<MySqlStream, Error> {
+    async fn do_handshake<S: Socket>(
+        self,
+        socket: S,
+    ) -> Result<(MySqlStream, Option<AuthPlugin>, Chain<Bytes, Bytes>), Error> {
         let DoHandshake {
             options,
             charset,
             collation,
+            rsa_exchange_failed,
         } = self;
 
         let mut stream = MySqlStream::with_socket(charset, collation, options, socket);
@@ -79,7 +318,7 @@ impl<'a> DoHandshake<'a> {
 
         let handshake: Handshake = stream.recv_packet().await?.decode()?;
 
-        let mut plugin = handshake.auth_plugin;
+        let plugin = handshake.auth_plugin;
         let nonce = handshake.auth_plugin_data;
 
         // FIXME: server version parse is a bit ugly
@@ -110,84 +349,320 @@ impl<'a> DoHandshake<'a> {
             server_version_minor,
             server_version_patch,
         );
+        stream.connection_id = handshake.connection_id;
 
         stream.capabilities &= handshake.server_capabilities;
         stream.capabilities |= Capabilities::PROTOCOL_41;
 
-        let mut stream = tls::maybe_upgrade(stream, self.options).await?;
+        let mut stream = tls::maybe_upgrade(stream, options).await?;
 
-        let auth_response = if let (Some(plugin), Some(password)) = (plugin, &options.password) {
-            Some(plugin.scramble(&mut stream, password, &nonce).await?)
-        } else {
-            None
-        };
+        match send_handshake_response(&mut stream, options, plugin, nonce).await {
+            Ok((plugin, nonce)) => Ok((stream, plugin, nonce)),
 
-        stream.write_packet(HandshakeResponse {
-            collation: stream.collation as u8,
-            max_packet_size: MAX_PACKET_SIZE,
-            username: &options.username,
-            database: options.database.as_deref(),
-            auth_plugin: plugin,
-            auth_response: auth_response.as_deref(),
-        });
+            Err(error) => {
+                if stream.rsa_exchange_started {
+                    rsa_exchange_failed.store(true, Ordering::Relaxed);
+                }
 
-        stream.flush().await?;
+                Err(error)
+            }
+        }
+    }
+}
 
-        loop {
-            let packet = stream.recv_packet().await?;
-            match packet[0] {
-                0x00 => {
-                    let _ok = packet.ok()?;
+async fn send_handshake_response(
+    stream: &mut MySqlStream,
+    options: &MySqlConnectOptions,
+    plugin: Option<AuthPlugin>,
+    nonce: Chain<Bytes, Bytes>,
+) -> Result<(Option<AuthPlugin>, Chain<Bytes, Bytes>), Error> {
+    // the server may offer `mysql_clear_password` up front instead of switching to it
+    if matches!(plugin, Some(AuthPlugin::MySqlClearPassword)) {
+        check_cleartext_plugin(stream, options.enable_cleartext_plugin)?;
+    }
 
-                    break;
-                }
+    let auth_response = if let (Some(plugin), Some(password)) = (plugin, &options.password) {
+        Some(plugin.scramble(stream, password, &nonce).await?)
+    } else {
+        None
+    };
+
+    stream.write_packet(HandshakeResponse {
+        collation: stream.collation as u8,
+        max_packet_size: MAX_PACKET_SIZE,
+        username: &options.username,
+        database: options.database.as_deref(),
+        auth_plugin: plugin,
+        auth_response: auth_response.as_deref().map(Vec::as_slice),
+    });
+
+    stream.flush().await?;
+
+    authenticate(
+        stream,
+        plugin,
+        options.password.as_deref(),
+        nonce,
+        options.enable_cleartext_plugin,
+    )
+    .await
+}
+
+impl<'a> WithSocket for DoHandshake<'a> {
+    type Output =
+        BoxFuture<'a, Result<(MySqlStream, Option<AuthPlugin>, Chain<Bytes, Bytes>), Error>>;
 
-                0xfe => {
-                    let switch: AuthSwitchRequest =
-                        packet.decode_with(self.options.enable_cleartext_plugin)?;
+    fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
+        Box::pin(self.do_handshake(socket))
+    }
+}
 
-                    plugin = Some(switch.plugin);
-                    let nonce = switch.data.chain(Bytes::new());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::stream::tests::{packet, MockSocket, OK};
+    use futures_util::FutureExt;
+    use sha1::{Digest, Sha1};
+    use std::sync::Mutex;
 
-                    let response = switch
-                        .plugin
-                        .scramble(
-                            &mut stream,
-                            options.password.as_deref().unwrap_or_default(),
-                            &nonce,
-                        )
-                        .await?;
+    fn reset() -> Error {
+        io::Error::from(io::ErrorKind::ConnectionReset).into()
+    }
 
-                    stream.write_packet(AuthSwitchResponse(response));
-                    stream.flush().await?;
+    #[test]
+    fn test_retry_rsa_exchange() {
+        // a transient failure during the exchange is retried
+        let mut attempts = 0;
+        let result = retry_rsa_exchange(2, |rsa_exchange_failed| {
+            attempts += 1;
+            let attempt = attempts;
+
+            async move {
+                if attempt == 1 {
+                    rsa_exchange_failed.store(true, Ordering::Relaxed);
+                    return Err(reset());
                 }
 
-                id => {
-                    if let (Some(plugin), Some(password)) = (plugin, &options.password) {
-                        if plugin.handle(&mut stream, packet, password, &nonce).await? {
-                            // plugin signaled authentication is ok
-                            break;
-                        }
-
-                        // plugin signaled to continue authentication
-                    } else {
-                        return Err(err_protocol!(
-                            "unexpected packet 0x{:02x} during authentication",
-                            id
-                        ));
-                    }
-                }
+                Ok(attempt)
+            }
+        })
+        .now_or_never()
+        .unwrap();
+
+        assert_eq!(result.unwrap(), 2);
+
+        // the last error is returned once the retries are used up
+        let mut attempts = 0;
+        let result = retry_rsa_exchange(2, |rsa_exchange_failed| {
+            attempts += 1;
+
+            async move {
+                rsa_exchange_failed.store(true, Ordering::Relaxed);
+                Err::<(), _>(reset())
             }
+        })
+        .now_or_never()
+        .unwrap();
+
+        assert!(matches!(result, Err(Error::Io(_))));
+        assert_eq!(attempts, 3);
+
+        // failures outside of the exchange are not retried
+        let mut attempts = 0;
+        let result = retry_rsa_exchange(2, |_| {
+            attempts += 1;
+            async move { Err::<(), _>(reset()) }
+        })
+        .now_or_never()
+        .unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    // MySQL 8.0.18, offering `caching_sha2_password`
+    const HANDSHAKE: &[u8] = b"\n8.0.18\x00\x19\x00\x00\x00\x114aB0c\x06g\x00\xff\xff\xff\x02\x00\xff\xc7\x15\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00tL\x03s\x0f[4\rl4. \x00caching_sha2_password\x00";
+
+    fn handshake(
+        incoming: Vec<u8>,
+    ) -> (
+        Result<(MySqlStream, Option<AuthPlugin>, Chain<Bytes, Bytes>), Error>,
+        Vec<u8>,
+    ) {
+        handshake_with(MySqlConnectOptions::new(), incoming)
+    }
+
+    fn handshake_with(
+        options: MySqlConnectOptions,
+        incoming: Vec<u8>,
+    ) -> (
+        Result<(MySqlStream, Option<AuthPlugin>, Chain<Bytes, Bytes>), Error>,
+        Vec<u8>,
+    ) {
+        let options = options
+            .username("root")
+            .password("password")
+            .ssl_mode(MySqlSslMode::Disabled);
+
+        let outgoing = Arc::new(Mutex::new(Vec::new()));
+        let socket = MockSocket {
+            incoming,
+            outgoing: outgoing.clone(),
+        };
+
+        let result = DoHandshake::new(&options, Arc::new(AtomicBool::new(false)))
+            .unwrap()
+            .do_handshake(socket)
+            .now_or_never()
+            .unwrap();
+
+        let outgoing = outgoing.lock().unwrap().clone();
+
+        (result, outgoing)
+    }
+
+    #[test]
+    fn test_handshake_with_auth_switch() {
+        let challenge = b"abcdefghijabcdefghij";
+
+        let mut switch = b"\xfemysql_native_password\x00".to_vec();
+        switch.extend_from_slice(challenge);
+        switch.push(0);
+
+        let mut incoming = packet(0, HANDSHAKE);
+        incoming.extend(packet(2, &switch));
+        incoming.extend(packet(4, OK));
+
+        let (result, outgoing) = handshake(incoming);
+        let (_, plugin, nonce) = result.unwrap();
+
+        // SHA1(password) ^ SHA1(challenge + SHA1(SHA1(password)))
+        let pw_hash = Sha1::digest("password");
+        let mut expected = Sha1::new()
+            .chain_update(challenge)
+            .chain_update(Sha1::digest(pw_hash))
+            .finalize();
+
+        for (byte, pw_byte) in expected.iter_mut().zip(&pw_hash) {
+            *byte ^= pw_byte;
         }
 
-        Ok(stream)
+        // the password is scrambled again with the plugin and challenge the server asked for
+        assert!(outgoing.ends_with(&packet(3, &expected)));
+
+        // and both are kept for re-authenticating later
+        assert!(matches!(plugin, Some(AuthPlugin::MySqlNativePassword)));
+        assert_eq!(nonce.first_ref(), &challenge[..]);
+        assert!(nonce.last_ref().is_empty());
     }
-}
 
-impl<'a> WithSocket for DoHandshake<'a> {
-    type Output = BoxFuture<'a, Result<MySqlStream, Error>>;
+    #[test]
+    fn test_caching_sha2_full_auth_over_unix_socket() {
+        let mut incoming = packet(0, HANDSHAKE);
+        // perform full authentication
+        incoming.extend(packet(2, b"\x01\x04"));
+        incoming.extend(packet(4, OK));
 
-    fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
-        Box::pin(self.do_handshake(socket))
+        let options = MySqlConnectOptions::new().socket("/tmp/mysql.sock");
+        let (result, outgoing) = handshake_with(options, incoming);
+        result.unwrap();
+
+        // a Unix socket is a secure transport, so the password is sent as-is
+        assert!(outgoing.ends_with(&packet(3, b"password\x00")));
+    }
+
+    #[test]
+    fn test_caching_sha2_full_auth_over_plain_tcp() {
+        let mut incoming = packet(0, HANDSHAKE);
+        incoming.extend(packet(2, b"\x01\x04"));
+
+        let (result, outgoing) = handshake(incoming);
+        assert!(result.is_err());
+
+        // without TLS, the server's public key is requested to encrypt the password with
+        assert!(outgoing.ends_with(&packet(3, b"\x02")));
+    }
+
+    fn cleartext_handshake() -> Vec<u8> {
+        let handshake = HANDSHAKE
+            .strip_suffix(b"caching_sha2_password\x00")
+            .unwrap();
+
+        let mut handshake = handshake.to_vec();
+        handshake.extend_from_slice(b"mysql_clear_password\x00");
+
+        packet(0, &handshake)
+    }
+
+    #[test]
+    fn test_handshake_offering_cleartext_plugin() {
+        let mut incoming = cleartext_handshake();
+        incoming.extend(packet(2, OK));
+
+        let options = MySqlConnectOptions::new()
+            .enable_cleartext_plugin(true)
+            .socket("/tmp/mysql.sock");
+        let (result, outgoing) = handshake_with(options, incoming);
+        result.unwrap();
+
+        assert!(outgoing
+            .windows(b"password\x00".len())
+            .any(|w| w == b"password\x00"));
+    }
+
+    #[test]
+    fn test_handshake_offering_cleartext_plugin_over_insecure_connection() {
+        let options = MySqlConnectOptions::new().enable_cleartext_plugin(true);
+        let (result, outgoing) = handshake_with(options, cleartext_handshake());
+
+        let err = result.err().expect("the handshake should fail");
+        assert!(err.to_string().contains("insecure connection"), "{err}");
+
+        // the password is never sent
+        assert!(!outgoing
+            .windows(b"password".len())
+            .any(|w| w == b"password"));
+    }
+
+    #[test]
+    fn test_handshake_with_auth_switch_to_cleartext_over_insecure_connection() {
+        let mut incoming = packet(0, HANDSHAKE);
+        incoming.extend(packet(2, b"\xfemysql_clear_password\x00"));
+
+        let options = MySqlConnectOptions::new().enable_cleartext_plugin(true);
+        let (result, outgoing) = handshake_with(options, incoming);
+
+        let err = result.err().expect("the handshake should fail");
+        assert!(err.to_string().contains("insecure connection"), "{err}");
+
+        // no response to the switch request is sent
+        assert!(!outgoing.ends_with(&packet(3, b"password\x00")));
+    }
+
+    #[test]
+    fn test_handshake_offering_disabled_cleartext_plugin() {
+        let (result, outgoing) = handshake(cleartext_handshake());
+
+        let err = result.err().expect("the handshake should fail");
+        assert!(
+            err.to_string().contains("mysql_cleartext_plugin disabled"),
+            "{err}"
+        );
+
+        // the password is never sent
+        assert!(!outgoing
+            .windows(b"password".len())
+            .any(|w| w == b"password"));
+    }
+
+    #[test]
+    fn test_handshake_with_auth_switch_to_unsupported_plugin() {
+        let mut incoming = packet(0, HANDSHAKE);
+        incoming.extend(packet(2, b"\xfedialog\x00\x01Password: \x00"));
+
+        let (result, _) = handshake(incoming);
+
+        let err = result.err().expect("the handshake should fail");
+        assert!(err.to_string().contains("\"dialog\""), "{err}");
     }
 }
@@ -2,16 +2,17 @@ use super::MySqlStream;
 use crate::connection::stream::Waiting;
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{stream_until, Execute, Executor};
 use crate::ext::ustr::UStr;
 use crate::io::MySqlBufExt;
 use crate::logger::QueryLogger;
+use crate::placeholders::bind_numbered_placeholders;
 use crate::protocol::response::Status;
 use crate::protocol::statement::{
     BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
 };
 use crate::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
-use crate::statement::{MySqlStatement, MySqlStatementMetadata};
+use crate::statement::{MySqlPreparedStatement, MySqlStatement, MySqlStatementMetadata};
 use crate::HashMap;
 use crate::{
     MySql, MySqlArguments, MySqlColumn, MySqlConnection, MySqlQueryResult, MySqlRow, MySqlTypeInfo,
@@ -22,10 +23,11 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
+use std::time::Instant;
 use std::{borrow::Cow, sync::Arc};
 
 impl MySqlConnection {
-    async fn get_or_prepare<'c>(
+    pub(crate) async fn get_or_prepare<'c>(
         &mut self,
         sql: &str,
         persistent: bool,
@@ -35,6 +37,22 @@ impl MySqlConnection {
             return Ok((*statement).clone());
         }
 
+        let (id, metadata) = self.prepare_statement(sql).await?;
+
+        if persistent && self.cache_statement.is_enabled() {
+            // in case of the cache being full, close the least recently used statement
+            if let Some((id, _)) = self.cache_statement.insert(sql, (id, metadata.clone())) {
+                self.stream.send_packet(StmtClose { statement: id }).await?;
+            }
+        }
+
+        Ok((id, metadata))
+    }
+
+    async fn prepare_statement(
+        &mut self,
+        sql: &str,
+    ) -> Result<(u32, MySqlStatementMetadata), Error> {
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare.html
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
 
@@ -72,27 +90,39 @@ impl MySqlConnection {
             column_names: Arc::new(column_names),
         };
 
-        if persistent && self.cache_statement.is_enabled() {
-            // in case of the cache being full, close the least recently used statement
-            if let Some((id, _)) = self.cache_statement.insert(sql, (id, metadata.clone())) {
-                self.stream.send_packet(StmtClose { statement: id }).await?;
-            }
-        }
-
         Ok((id, metadata))
     }
 
+    // the statement `prepare()` created for a query, if that was on this connection
+    fn owned_statement(
+        &self,
+        prepared: Option<&Arc<MySqlPreparedStatement>>,
+        metadata: Option<&MySqlStatementMetadata>,
+    ) -> Option<(u32, MySqlStatementMetadata)> {
+        let prepared =
+            prepared.filter(|prepared| Arc::ptr_eq(&prepared.dropped, &self.dropped_statements))?;
+
+        Some((prepared.id, metadata?.clone()))
+    }
+
     #[allow(clippy::needless_lifetimes)]
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         sql: &'q str,
         arguments: Option<MySqlArguments>,
         persistent: bool,
+        metadata_opt: Option<MySqlStatementMetadata>,
+        prepared: Option<Arc<MySqlPreparedStatement>>,
     ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
     {
-        let mut logger = QueryLogger::new(sql, self.log_settings.clone());
-
-        self.stream.wait_until_ready().await?;
+        let mut logger = QueryLogger::new(
+            sql,
+            "mysql",
+            Some(self.stream.connection_id),
+            self.log_settings.clone(),
+        );
+
+        self.wait_until_ready().await?;
         self.stream.waiting.push_back(Waiting::Result);
 
         Ok(Box::pin(try_stream! {
@@ -102,11 +132,35 @@ impl MySqlConnection {
             let mut columns = Arc::new(Vec::new());
 
             let (mut column_names, format, mut needs_metadata) = if let Some(arguments) = arguments {
-                let (id, metadata) = self.get_or_prepare(
-                    sql,
-                    persistent,
-                )
-                .await?;
+                let owned = self.owned_statement(prepared.as_ref(), metadata_opt.as_ref());
+                let is_owned = owned.is_some();
+
+                let (id, metadata) = match owned {
+                    Some(owned) => owned,
+                    None => self.get_or_prepare(sql, persistent).await?,
+                };
+
+                // a statement that did not end up in the cache (not persistent, or the cache is
+                // disabled) would otherwise stay prepared on the server until the connection
+                // closes; `COM_STMT_CLOSE` has no response, so it can be sent right away
+                let close_statement = !is_owned
+                    && self.cache_statement.get_mut(sql).map(|cached| cached.0) != Some(id);
+
+                // the execute packet is only well-formed if every parameter has an argument
+                if arguments.types.len() != metadata.parameters {
+                    if close_statement {
+                        self.stream.send_packet(StmtClose { statement: id }).await?;
+                    }
+
+                    // nothing was sent, so there is no result to wait for
+                    self.stream.waiting.pop_front();
+
+                    return Err(err_protocol!(
+                        "expected {} arguments for query but {} were bound",
+                        metadata.parameters,
+                        arguments.types.len()
+                    ));
+                }
 
                 // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                 self.stream
@@ -116,7 +170,14 @@ impl MySqlConnection {
                     })
                     .await?;
 
-                (metadata.column_names, MySqlValueFormat::Binary, false)
+                if close_statement {
+                    self.stream.send_packet(StmtClose { statement: id }).await?;
+                }
+
+                // a `CALL` is prepared without the columns of the result sets it returns
+                let needs_metadata = metadata.columns.is_empty();
+
+                (metadata.column_names, MySqlValueFormat::Binary, needs_metadata)
             } else {
                 // https://dev.mysql.com/doc/internals/en/com-query.html
                 self.stream.send_packet(Query(sql)).await?;
@@ -124,15 +185,28 @@ impl MySqlConnection {
                 (Arc::default(), MySqlValueFormat::Text, true)
             };
 
+            let mut sent = Some(Instant::now());
+
             loop {
                 // query response is a meta-packet which may be one of:
-                //  Ok, Err, ResultSet, or (unhandled) LocalInfileRequest
+                //  Ok, Err, ResultSet, or LocalInfileRequest
                 let mut packet = self.stream.recv_packet().await?;
 
+                if let Some(sent) = sent.take() {
+                    self.latency.record(sent.elapsed());
+                }
+
+                if packet[0] == 0xfb {
+                    // the server wants us to upload a file; the query response follows
+                    self.stream.local_infile(packet).await?;
+                    continue;
+                }
+
                 if packet[0] == 0x00 || packet[0] == 0xff {
                     // first packet in a query response is OK or ERR
                     // this indicates either a successful query with no rows at all or a failed query
                     let ok = packet.ok()?;
+                    self.stream.status = ok.status;
 
                     let rows_affected = ok.affected_rows;
                     logger.increase_rows_affected(rows_affected);
@@ -173,6 +247,7 @@ impl MySqlConnection {
 
                     if packet[0] == 0xfe && packet.len() < 9 {
                         let eof = packet.eof(self.stream.capabilities)?;
+                        self.stream.status = eof.status;
 
                         r#yield!(Either::Left(MySqlQueryResult {
                             rows_affected: 0,
@@ -197,6 +272,7 @@ impl MySqlConnection {
                     let v = Either::Right(MySqlRow {
                         row,
                         format,
+                        zero_date_policy: self.zero_date_policy,
                         columns: Arc::clone(&columns),
                         column_names: Arc::clone(&column_names),
                     });
@@ -208,6 +284,80 @@ impl MySqlConnection {
             }
         }))
     }
+
+    /// Execute a text-protocol query and discard any rows it returns without decoding them.
+    ///
+    /// This is useful for statements where the result rows are not needed. Every packet of every
+    /// result set is read, so the connection is immediately ready for the next query.
+    ///
+    /// The returned [`MySqlQueryResult`] covers all result sets of the query; `last_insert_id`
+    /// is that of the last statement which reported one.
+    pub async fn execute_drain(&mut self, sql: &str) -> Result<MySqlQueryResult, Error> {
+        let mut logger = QueryLogger::new(
+            sql,
+            "mysql",
+            Some(self.stream.connection_id),
+            self.log_settings.clone(),
+        );
+
+        self.wait_until_ready().await?;
+        self.stream.waiting.push_back(Waiting::Result);
+
+        // https://dev.mysql.com/doc/internals/en/com-query.html
+        self.stream.send_packet(Query(sql)).await?;
+
+        let mut done = MySqlQueryResult::default();
+
+        loop {
+            let packet = self.stream.recv_packet().await?;
+
+            if packet[0] == 0xfb {
+                self.stream.local_infile(packet).await?;
+                continue;
+            }
+
+            if packet[0] == 0x00 || packet[0] == 0xff {
+                let ok = packet.ok()?;
+                self.stream.status = ok.status;
+
+                logger.increase_rows_affected(ok.affected_rows);
+                done.rows_affected += ok.affected_rows;
+
+                if ok.last_insert_id != 0 {
+                    done.last_insert_id = ok.last_insert_id;
+                }
+
+                if ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                    continue;
+                }
+
+                self.stream.waiting.pop_front();
+                return Ok(done);
+            }
+
+            *self.stream.waiting.front_mut().unwrap() = Waiting::Row;
+            self.stream.skip_result_metadata(packet).await?;
+
+            loop {
+                let packet = self.stream.recv_packet().await?;
+
+                if packet[0] == 0xfe && packet.len() < 9 {
+                    let eof = packet.eof(self.stream.capabilities)?;
+                    self.stream.status = eof.status;
+
+                    if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                        *self.stream.waiting.front_mut().unwrap() = Waiting::Result;
+                        break;
+                    }
+
+                    self.stream.waiting.pop_front();
+                    return Ok(done);
+                }
+
+                logger.increment_rows_returned();
+            }
+        }
+    }
 }
 
 impl<'c> Executor<'c> for &'c mut MySqlConnection {
@@ -222,19 +372,41 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         E: Execute<'q, Self::Database>,
     {
         let sql = query.sql();
-        let arguments = query.take_arguments();
+        let metadata = query.statement().map(|s| s.metadata.clone());
+        let prepared = query.statement().and_then(|s| s.prepared.clone());
+        let mut arguments = query.take_arguments();
         let persistent = query.persistent();
+        let deadline = query.deadline();
+
+        stream_until(
+            deadline,
+            Box::pin(try_stream! {
+                let rewritten = match &arguments {
+                    Some(arguments) if self.numbered_placeholders => {
+                        bind_numbered_placeholders(sql, arguments)?
+                    }
+                    _ => None,
+                };
+
+                let (sql, prepared) = match rewritten {
+                    // the statement was prepared with the original placeholders
+                    Some((rewritten_sql, rewritten_arguments)) => {
+                        arguments = Some(rewritten_arguments);
+                        (Cow::Owned(rewritten_sql), None)
+                    }
+                    None => (Cow::Borrowed(sql), prepared),
+                };
 
-        Box::pin(try_stream! {
-            let s = self.run(sql, arguments, persistent).await?;
-            pin_mut!(s);
+                let s = self.run(&sql, arguments, persistent, metadata, prepared).await?;
+                pin_mut!(s);
 
-            while let Some(v) = s.try_next().await? {
-                r#yield!(v);
-            }
+                while let Some(v) = s.try_next().await? {
+                    r#yield!(v);
+                }
 
-            Ok(())
-        })
+                Ok(())
+            }),
+        )
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -248,13 +420,20 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         let mut s = self.fetch_many(query);
 
         Box::pin(async move {
+            let mut row = None;
+
             while let Some(v) = s.try_next().await? {
                 if let Either::Right(r) = v {
-                    return Ok(Some(r));
+                    if row.is_some() {
+                        // the rest of the response is discarded on the next use of the connection
+                        return Err(Error::TooManyRows);
+                    }
+
+                    row = Some(r);
                 }
             }
 
-            Ok(None)
+            Ok(row)
         })
     }
 
@@ -267,14 +446,19 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         'c: 'e,
     {
         Box::pin(async move {
-            self.stream.wait_until_ready().await?;
+            self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, true).await?;
+            // prepared apart from the statement cache, so nothing but dropping the returned
+            // statement closes it
+            let (id, metadata) = self.prepare_statement(sql).await?;
 
             Ok(MySqlStatement {
                 sql: Cow::Borrowed(sql),
-                // metadata has internal Arcs for expensive data structures
-                metadata: metadata.clone(),
+                metadata,
+                prepared: Some(Arc::new(MySqlPreparedStatement {
+                    id,
+                    dropped: Arc::clone(&self.dropped_statements),
+                })),
             })
         })
     }
@@ -285,9 +469,14 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         'c: 'e,
     {
         Box::pin(async move {
-            self.stream.wait_until_ready().await?;
+            self.wait_until_ready().await?;
+
+            let (id, metadata) = self.get_or_prepare(sql, false).await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, false).await?;
+            // unless it was already cached, the statement is not needed past this point
+            if self.cache_statement.get_mut(sql).map(|cached| cached.0) != Some(id) {
+                self.stream.send_packet(StmtClose { statement: id }).await?;
+            }
 
             let columns = (&*metadata.columns).clone();
 
@@ -363,6 +552,8 @@ async fn recv_result_metadata(
 
         let column = recv_next_result_column(&def, ordinal)?;
 
+        // with duplicate names (e.g. `id` from both sides of a join), the last column wins,
+        // as it does for the other drivers
         column_names.insert(column.name.clone(), ordinal);
         columns.push(column);
     }
@@ -1,17 +1,26 @@
 use std::fmt::{self, Debug, Formatter};
+use std::mem;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
+use bytes::buf::Chain;
+use bytes::Bytes;
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 pub(crate) use sqlx_core::connection::*;
 pub(crate) use stream::{MySqlStream, Waiting};
 
-use crate::common::StatementCache;
+use crate::common::{LatencyEwma, StatementCache};
 use crate::error::Error;
+use crate::protocol::auth::AuthPlugin;
+use crate::protocol::response::Status;
 use crate::protocol::statement::StmtClose;
-use crate::protocol::text::{Ping, Quit};
+use crate::protocol::text::{Ping, Quit, ResetConnection};
 use crate::statement::MySqlStatementMetadata;
-use crate::transaction::Transaction;
-use crate::{MySql, MySqlConnectOptions};
+use crate::transaction::{Transaction, TransactionOptions};
+use crate::{MySql, MySqlConnectOptions, MySqlZeroDatePolicy};
+use sqlx_core::executor::Executor;
 
 mod auth;
 mod establish;
@@ -34,7 +43,148 @@ pub struct MySqlConnection {
     // cache by query string to the statement id and metadata
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
+    // statements created by `prepare()` that are no longer referred to, to be closed
+    pub(crate) dropped_statements: Arc<Mutex<Vec<u32>>>,
+
     log_settings: LogSettings,
+
+    // authentication plugin and scramble sent by the server in the initial handshake,
+    // which are needed again to change the user of the connection
+    auth_plugin: Option<AuthPlugin>,
+    auth_nonce: Chain<Bytes, Bytes>,
+
+    // statements configuring the session, run after connecting and after the session is reset
+    pub(crate) session_init: String,
+
+    // whether to reset the session instead of pinging when returned to a pool
+    reset_on_release: bool,
+    numbered_placeholders: bool,
+    pub(crate) zero_date_policy: MySqlZeroDatePolicy,
+
+    // observed round-trip latency
+    pub(crate) latency: LatencyEwma,
+
+    // options this connection was established with
+    // used to open the connection that `KILL QUERY` is sent on
+    pub(crate) connect_options: Arc<MySqlConnectOptions>,
+}
+
+impl MySqlConnection {
+    /// An exponentially-weighted moving average of the round-trip latency to the server, or
+    /// `None` if no round trip has been measured yet.
+    ///
+    /// A sample is taken on every [`ping()`][Connection::ping] and on every query, from sending
+    /// the query to receiving the first response from the server; this includes the time the
+    /// server takes to start responding, but not the time taken to transfer or read the results.
+    /// Useful for preferring faster connections, e.g. when balancing load across replicas.
+    ///
+    /// See [`LatencyEwma`] for how query and ping samples are mixed into the average.
+    pub fn latency_ewma(&self) -> Option<Duration> {
+        self.latency.get()
+    }
+
+    // will return when the connection is ready for another query
+    pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
+        // the results of a query were abandoned, e.g. by dropping a `fetch()` stream
+        if self.connect_options.cancel_on_drop && !self.stream.waiting.is_empty() {
+            // the query may still be running; stop it rather than wait for it to complete
+            if let Err(error) = self.cancel_token().cancel().await {
+                tracing::debug!(%error, "failed to cancel an abandoned query");
+            }
+
+            // which makes it fail with `ER_QUERY_INTERRUPTED`, no longer a concern of anyone
+            loop {
+                match self.stream.wait_until_ready().await {
+                    Err(Error::Database(error)) => {
+                        tracing::debug!(%error, "discarding error from an abandoned query");
+                    }
+
+                    result => break result?,
+                }
+            }
+        } else {
+            self.stream.wait_until_ready().await?;
+        }
+
+        let dropped = mem::take(
+            &mut *self
+                .dropped_statements
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+
+        // `COM_STMT_CLOSE` has no response
+        for statement in dropped {
+            self.stream.send_packet(StmtClose { statement }).await?;
+        }
+
+        Ok(())
+    }
+
+    // the server deallocated every prepared statement; the ones `prepare()` created are no
+    // longer recognized as prepared on this connection, and are not closed when dropped
+    fn forget_prepared_statements(&mut self) {
+        self.dropped_statements = Arc::default();
+    }
+
+    pub(crate) async fn init_session(&mut self) -> Result<(), Error> {
+        if !self.session_init.is_empty() {
+            let sql = self.session_init.clone();
+            self.execute(&*sql).await?;
+        }
+
+        if let Some(after_connect) = self.connect_options.after_connect.clone() {
+            (after_connect.0)(self).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reset the session to the state of a freshly opened connection, without re-authenticating.
+    ///
+    /// This sends `COM_RESET_CONNECTION` (MySQL 5.7.3 and MariaDB 10.2.4 or later), which rolls
+    /// back any open transaction, drops temporary tables, clears user-defined variables
+    /// (`@var`), releases locks and deallocates all prepared statements (so the statement cache
+    /// is cleared as well). The session settings SQLx applies when connecting are then
+    /// restored, and [`MySqlConnectOptions::after_connect`] is run again.
+    ///
+    /// See also [`MySqlConnectOptions::reset_on_release`].
+    pub async fn reset_connection(&mut self) -> Result<(), Error> {
+        self.wait_until_ready().await?;
+        self.stream.send_packet(ResetConnection).await?;
+        self.stream.recv_ok().await?;
+
+        // the server has forgotten everything about the previous session
+        self.cache_statement.clear();
+        self.forget_prepared_statements();
+        self.transaction_depth = 0;
+
+        self.init_session().await
+    }
+
+    /// List the user-defined variables (`@var`) currently set in this session,
+    /// with their values as text.
+    ///
+    /// This is meant for debugging and requires `performance_schema` (MySQL 5.7 or later).
+    pub async fn user_variables(&mut self) -> Result<Vec<(String, Option<String>)>, Error> {
+        let rows: Vec<(String, Option<Vec<u8>>)> = sqlx_core::query_as::query_as(
+            "SELECT VARIABLE_NAME, VARIABLE_VALUE \
+             FROM performance_schema.user_variables_by_thread \
+             WHERE THREAD_ID = (SELECT THREAD_ID FROM performance_schema.threads \
+                                WHERE PROCESSLIST_ID = CONNECTION_ID()) \
+             ORDER BY VARIABLE_NAME",
+        )
+        .fetch_all(&mut *self)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, value)| {
+                let value = value.map(|value| String::from_utf8_lossy(&value).into_owned());
+                (name, value)
+            })
+            .collect())
+    }
 }
 
 impl Debug for MySqlConnection {
@@ -66,9 +216,12 @@ impl Connection for MySqlConnection {
 
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            self.stream.wait_until_ready().await?;
+            self.wait_until_ready().await?;
+
+            let start = Instant::now();
             self.stream.send_packet(Ping).await?;
             self.stream.recv_ok().await?;
+            self.latency.record(start.elapsed());
 
             Ok(())
         })
@@ -76,7 +229,29 @@ impl Connection for MySqlConnection {
 
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
-        self.stream.wait_until_ready().boxed()
+        self.wait_until_ready().boxed()
+    }
+
+    #[doc(hidden)]
+    fn before_release(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        if self.reset_on_release {
+            return Box::pin(self.reset_connection());
+        }
+
+        Box::pin(async move {
+            self.ping().await?;
+
+            // a transaction opened with a raw `BEGIN` (or a leaked `Transaction`) would otherwise
+            // be inherited by whoever acquires the connection next
+            if self.stream.status.contains(Status::SERVER_STATUS_IN_TRANS) {
+                tracing::warn!("rolling back a transaction left open on a released connection");
+
+                self.execute("ROLLBACK").await?;
+                self.transaction_depth = 0;
+            }
+
+            Ok(())
+        })
     }
 
     fn cached_statements_size(&self) -> usize {
@@ -102,6 +277,16 @@ impl Connection for MySqlConnection {
         !self.stream.write_buffer().is_empty()
     }
 
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        self.stream.is_broken()
+    }
+
+    #[doc(hidden)]
+    fn set_poison_flag(&mut self, flag: Arc<AtomicBool>) {
+        self.stream.set_poison_flag(flag);
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -109,6 +294,16 @@ impl Connection for MySqlConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn shrink_buffers(&mut self) {
         self.stream.shrink_buffers();
     }
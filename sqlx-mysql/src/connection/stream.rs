@@ -1,13 +1,18 @@
 use std::collections::VecDeque;
+use std::io;
 use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
 
 use bytes::{Buf, Bytes, BytesMut};
+use futures_core::stream::BoxStream;
+use futures_util::{stream, StreamExt, TryStreamExt};
 
 use crate::collation::{CharSet, Collation};
 use crate::error::Error;
 use crate::io::MySqlBufExt;
 use crate::io::{Decode, Encode};
 use crate::net::{BufferedSocket, Socket};
+use crate::options::LocalInfileHandler;
 use crate::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
 use crate::protocol::{Capabilities, Packet};
 use crate::{MySqlConnectOptions, MySqlDatabaseError};
@@ -16,12 +21,23 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     // Wrapping the socket in `Box` allows us to unsize in-place.
     pub(crate) socket: BufferedSocket<S>,
     pub(crate) server_version: (u16, u16, u16),
+    // the server's id for this connection, sent in the initial handshake
+    pub(crate) connection_id: u32,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) waiting: VecDeque<Waiting>,
+    // status flags of the last OK or EOF packet, e.g. whether a transaction is open
+    pub(crate) status: Status,
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
-    pub(crate) is_tls: bool,
+    // the connection is encrypted with TLS or goes over a Unix socket, so a password can be sent
+    // in cleartext instead of going through the RSA exchange
+    pub(crate) is_secure: bool,
+    // set once the RSA password exchange of `sha256_password` or `caching_sha2_password` starts
+    pub(crate) rsa_exchange_started: bool,
+    pub(crate) allow_local_infile: bool,
+    pub(crate) local_infile_allowlist: Vec<PathBuf>,
+    pub(crate) local_infile_handler: Option<LocalInfileHandler>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -57,15 +73,28 @@ impl<S: Socket> MySqlStream<S> {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        if options.allow_local_infile {
+            capabilities |= Capabilities::LOCAL_FILES;
+        }
+
+        let mut socket = BufferedSocket::new(socket);
+        socket.set_timeouts(options.read_timeout, options.write_timeout);
+
         Self {
             waiting: VecDeque::new(),
+            status: Status::empty(),
             capabilities,
             server_version: (0, 0, 0),
+            connection_id: 0,
             sequence_id: 0,
             collation,
             charset,
-            socket: BufferedSocket::new(socket),
-            is_tls: false,
+            socket,
+            is_secure: false,
+            rsa_exchange_started: false,
+            allow_local_infile: options.allow_local_infile,
+            local_infile_allowlist: options.local_infile_allowlist.clone(),
+            local_infile_handler: options.local_infile_handler.clone(),
         }
     }
 
@@ -80,6 +109,7 @@ impl<S: Socket> MySqlStream<S> {
 
                 if !packet.is_empty() && packet[0] == 0xfe && packet.len() < 9 {
                     let eof = packet.eof(self.capabilities)?;
+                    self.status = eof.status;
 
                     if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         *self.waiting.front_mut().unwrap() = Waiting::Result;
@@ -94,10 +124,16 @@ impl<S: Socket> MySqlStream<S> {
 
                 if !packet.is_empty() && (packet[0] == 0x00 || packet[0] == 0xff) {
                     let ok = packet.ok()?;
+                    self.status = ok.status;
 
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         self.waiting.pop_front();
                     }
+                } else if !packet.is_empty() && packet[0] == 0xfb {
+                    // nobody is waiting on this query anymore; refuse the upload and
+                    // let the next iteration read the server's response
+                    self.write_packet(&[][..]);
+                    self.flush().await?;
                 } else {
                     *self.waiting.front_mut().unwrap() = Waiting::Row;
                     self.skip_result_metadata(packet).await?;
@@ -190,7 +226,10 @@ impl<S: Socket> MySqlStream<S> {
     }
 
     pub(crate) async fn recv_ok(&mut self) -> Result<OkPacket, Error> {
-        self.recv_packet().await?.ok()
+        let ok = self.recv_packet().await?.ok()?;
+        self.status = ok.status;
+
+        Ok(ok)
     }
 
     pub(crate) async fn maybe_recv_eof(&mut self) -> Result<Option<EofPacket>, Error> {
@@ -201,7 +240,98 @@ impl<S: Socket> MySqlStream<S> {
         }
     }
 
-    async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<(), Error> {
+    /// Responds to a `LOCAL INFILE` request from the server.
+    ///
+    /// Unless enabled with [`MySqlConnectOptions::allow_local_infile`] and the requested path
+    /// is permitted by the allowlist, the request is refused by sending an empty file. In that
+    /// case the server response is consumed and an error is returned, leaving the connection
+    /// ready for the next query. The same happens if the contents cannot be read part way
+    /// through.
+    pub(crate) async fn local_infile(&mut self, packet: Packet<Bytes>) -> Result<(), Error> {
+        // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_query_response_local_infile_request.html
+        let path = String::from_utf8_lossy(&packet[1..]).into_owned();
+
+        let mut contents = match self.local_infile_contents(path) {
+            Ok(contents) => contents,
+            Err(error) => return self.end_local_infile_with(error).await,
+        };
+
+        while let Some(chunk) = contents.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(error) => return self.end_local_infile_with(error).await,
+            };
+
+            // keep each chunk below the maximum payload so that it is sent as a single packet;
+            // an empty chunk must not be sent at all as it would terminate the file
+            for chunk in chunk.chunks(0xFF_FF_FF - 1) {
+                self.write_packet(chunk);
+            }
+
+            self.flush().await?;
+        }
+
+        self.write_packet(&[][..]);
+        self.flush().await?;
+
+        Ok(())
+    }
+
+    // ends the upload early with an empty packet and reads the server's response to it
+    async fn end_local_infile_with(&mut self, error: Error) -> Result<(), Error> {
+        // an empty packet terminates the file
+        self.write_packet(&[][..]);
+        self.flush().await?;
+
+        match self.recv_packet().await {
+            Ok(packet) => {
+                let ok = packet.ok()?;
+                self.status = ok.status;
+
+                if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                    self.waiting.pop_front();
+                }
+            }
+
+            // the server rejected the (empty) upload; this has already been
+            // accounted for in `recv_packet`
+            Err(Error::Database(_)) => {}
+
+            Err(e) => return Err(e),
+        }
+
+        Err(error)
+    }
+
+    fn local_infile_contents(
+        &self,
+        path: String,
+    ) -> Result<BoxStream<'static, Result<Bytes, Error>>, Error> {
+        if !self.allow_local_infile {
+            return Err(Error::Configuration(
+                format!(
+                    "server requested local file {path:?} but LOCAL INFILE is disabled; \
+                     see `MySqlConnectOptions::allow_local_infile()`"
+                )
+                .into(),
+            ));
+        }
+
+        if let Some(handler) = &self.local_infile_handler {
+            return Ok((handler.0)(&path).map_err(Error::Io).boxed());
+        }
+
+        let allowlist = self.local_infile_allowlist.clone();
+
+        Ok(stream::once(read_local_infile(path, allowlist))
+            .map_ok(Bytes::from)
+            .boxed())
+    }
+
+    pub(crate) async fn skip_result_metadata(
+        &mut self,
+        mut packet: Packet<Bytes>,
+    ) -> Result<(), Error> {
         let num_columns: u64 = packet.get_uint_lenenc(); // column count
 
         for _ in 0..num_columns {
@@ -217,16 +347,50 @@ impl<S: Socket> MySqlStream<S> {
         MySqlStream {
             socket: self.socket.boxed(),
             server_version: self.server_version,
+            connection_id: self.connection_id,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
+            status: self.status,
             charset: self.charset,
             collation: self.collation,
-            is_tls: self.is_tls,
+            is_secure: self.is_secure,
+            rsa_exchange_started: self.rsa_exchange_started,
+            allow_local_infile: self.allow_local_infile,
+            local_infile_allowlist: self.local_infile_allowlist,
+            local_infile_handler: self.local_infile_handler,
         }
     }
 }
 
+async fn read_local_infile(path: String, allowlist: Vec<PathBuf>) -> Result<Vec<u8>, Error> {
+    let requested = PathBuf::from(&path);
+
+    let contents = sqlx_core::rt::spawn_blocking(move || -> io::Result<Option<Vec<u8>>> {
+        let path = std::fs::canonicalize(requested)?;
+
+        if !allowlist.is_empty()
+            && !allowlist.iter().any(|allowed| {
+                std::fs::canonicalize(allowed).is_ok_and(|allowed| path.starts_with(allowed))
+            })
+        {
+            return Ok(None);
+        }
+
+        std::fs::read(path).map(Some)
+    })
+    .await?;
+
+    contents.ok_or_else(|| {
+        Error::Configuration(
+            format!(
+                "server requested local file {path:?} which is not in the LOCAL INFILE allowlist"
+            )
+            .into(),
+        )
+    })
+}
+
 impl<S> Deref for MySqlStream<S> {
     type Target = BufferedSocket<S>;
 
@@ -240,3 +404,222 @@ impl<S> DerefMut for MySqlStream<S> {
         &mut self.socket
     }
 }
+
+#[cfg(test)]
+pub(super) mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    use bytes::{BufMut, Bytes};
+    use futures_util::{stream, FutureExt, StreamExt};
+
+    use super::{MySqlStream, Waiting};
+    use crate::collation::{CharSet, Collation};
+    use crate::error::Error;
+    use crate::io::ReadBuf;
+    use crate::net::Socket;
+    use crate::protocol::text::Ping;
+    use crate::MySqlConnectOptions;
+
+    // replays canned server packets and records everything the client writes
+    pub(crate) struct MockSocket {
+        pub(crate) incoming: Vec<u8>,
+        pub(crate) outgoing: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Socket for MockSocket {
+        fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+            let len = self.incoming.len().min(buf.remaining_mut());
+            buf.put_slice(&self.incoming[..len]);
+            self.incoming.drain(..len);
+            Ok(len)
+        }
+
+        fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.outgoing.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn poll_read_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_write_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    pub(crate) fn packet(sequence_id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut buf = (payload.len() as u32).to_le_bytes();
+        buf[3] = sequence_id;
+
+        let mut packet = buf.to_vec();
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    pub(crate) const OK: &[u8] = b"\x00\x00\x00\x02\x00\x00\x00";
+
+    #[test]
+    fn it_refuses_local_infile_by_default() -> Result<(), Error> {
+        let mut incoming = packet(1, b"\xfb/etc/passwd");
+        // response to the empty upload
+        incoming.extend(packet(3, OK));
+        // response to the ping sent afterwards
+        incoming.extend(packet(1, OK));
+
+        let outgoing = Arc::new(Mutex::new(Vec::new()));
+
+        let mut stream = MySqlStream::with_socket(
+            CharSet::utf8mb4,
+            Collation::utf8mb4_unicode_ci,
+            &MySqlConnectOptions::new(),
+            MockSocket {
+                incoming,
+                outgoing: outgoing.clone(),
+            },
+        );
+
+        stream.waiting.push_back(Waiting::Result);
+
+        let request = stream.recv_packet().now_or_never().unwrap()?;
+        assert_eq!(request[0], 0xfb);
+
+        let err = stream.local_infile(request).now_or_never().unwrap();
+        assert!(
+            matches!(err, Err(Error::Configuration(_))),
+            "expected refusal, got {err:?}"
+        );
+
+        // an empty file was sent in place of the requested one
+        assert_eq!(*outgoing.lock().unwrap(), packet(2, b""));
+        assert!(stream.waiting.is_empty());
+
+        // the connection is still in sync with the server
+        outgoing.lock().unwrap().clear();
+        stream.send_packet(Ping).now_or_never().unwrap()?;
+        stream.recv_ok().now_or_never().unwrap()?;
+
+        assert_eq!(*outgoing.lock().unwrap(), packet(0, b"\x0e"));
+
+        Ok(())
+    }
+
+    fn local_infile_stream(
+        options: &MySqlConnectOptions,
+        incoming: Vec<u8>,
+    ) -> (MySqlStream<MockSocket>, Arc<Mutex<Vec<u8>>>) {
+        let outgoing = Arc::new(Mutex::new(Vec::new()));
+
+        let mut stream = MySqlStream::with_socket(
+            CharSet::utf8mb4,
+            Collation::utf8mb4_unicode_ci,
+            options,
+            MockSocket {
+                incoming,
+                outgoing: outgoing.clone(),
+            },
+        );
+
+        stream.waiting.push_back(Waiting::Result);
+
+        (stream, outgoing)
+    }
+
+    #[test]
+    fn it_sends_local_infile_from_the_handler() -> Result<(), Error> {
+        let requested = Arc::new(Mutex::new(Vec::new()));
+
+        let options = MySqlConnectOptions::new()
+            .allow_local_infile(true)
+            // not consulted when a handler is set
+            .local_infile_allowlist(["/nonexistent"])
+            .local_infile_handler({
+                let requested = requested.clone();
+
+                move |name| {
+                    requested.lock().unwrap().push(name.to_owned());
+
+                    stream::iter(["1\n", "", "2\n"].map(|chunk| Ok(Bytes::from(chunk)))).boxed()
+                }
+            });
+
+        let (mut stream, outgoing) = local_infile_stream(&options, packet(1, b"\xfbdata.csv"));
+
+        let request = stream.recv_packet().now_or_never().unwrap()?;
+        stream.local_infile(request).now_or_never().unwrap()?;
+
+        assert_eq!(*requested.lock().unwrap(), ["data.csv"]);
+
+        // the empty chunk is skipped as an empty packet ends the file
+        let mut expected = packet(2, b"1\n");
+        expected.extend(packet(3, b"2\n"));
+        expected.extend(packet(4, b""));
+
+        assert_eq!(*outgoing.lock().unwrap(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_ends_local_infile_when_the_handler_fails() -> Result<(), Error> {
+        let options = MySqlConnectOptions::new()
+            .allow_local_infile(true)
+            .local_infile_handler(|_| {
+                stream::iter([
+                    Ok(Bytes::from("1\n")),
+                    Err(io::Error::new(io::ErrorKind::Other, "disk on fire")),
+                ])
+                .boxed()
+            });
+
+        let mut incoming = packet(1, b"\xfbdata.csv");
+        // response to the upload that was cut short
+        incoming.extend(packet(4, OK));
+
+        let (mut stream, outgoing) = local_infile_stream(&options, incoming);
+
+        let request = stream.recv_packet().now_or_never().unwrap()?;
+        let err = stream.local_infile(request).now_or_never().unwrap();
+
+        assert!(
+            matches!(&err, Err(Error::Io(e)) if e.to_string() == "disk on fire"),
+            "expected the handler's error, got {err:?}"
+        );
+
+        let mut expected = packet(2, b"1\n");
+        expected.extend(packet(3, b""));
+
+        assert_eq!(*outgoing.lock().unwrap(), expected);
+        assert!(stream.waiting.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_call_the_local_infile_handler_unless_allowed() -> Result<(), Error> {
+        let options = MySqlConnectOptions::new()
+            .local_infile_handler(|_| panic!("the handler should not be called"));
+
+        let mut incoming = packet(1, b"\xfbdata.csv");
+        incoming.extend(packet(3, OK));
+
+        let (mut stream, outgoing) = local_infile_stream(&options, incoming);
+
+        let request = stream.recv_packet().now_or_never().unwrap()?;
+        let err = stream.local_infile(request).now_or_never().unwrap();
+
+        assert!(
+            matches!(err, Err(Error::Configuration(_))),
+            "expected refusal, got {err:?}"
+        );
+        assert_eq!(*outgoing.lock().unwrap(), packet(2, b""));
+
+        Ok(())
+    }
+}
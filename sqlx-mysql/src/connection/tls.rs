@@ -3,18 +3,28 @@ use crate::connection::{MySqlStream, Waiting};
 use crate::error::Error;
 use crate::net::tls::TlsConfig;
 use crate::net::{tls, BufferedSocket, Socket, WithSocket};
+use crate::options::LocalInfileHandler;
 use crate::protocol::connect::SslRequest;
+use crate::protocol::response::Status;
 use crate::protocol::Capabilities;
 use crate::{MySqlConnectOptions, MySqlSslMode};
 use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Duration;
 
 struct MapStream {
     server_version: (u16, u16, u16),
+    connection_id: u32,
     capabilities: Capabilities,
     sequence_id: u8,
     waiting: VecDeque<Waiting>,
     charset: CharSet,
     collation: Collation,
+    allow_local_infile: bool,
+    local_infile_allowlist: Vec<PathBuf>,
+    local_infile_handler: Option<LocalInfileHandler>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
 }
 
 pub(super) async fn maybe_upgrade<S: Socket>(
@@ -28,6 +38,14 @@ pub(super) async fn maybe_upgrade<S: Socket>(
         stream.capabilities.remove(Capabilities::SSL);
     }
 
+    if options.socket.is_some() {
+        // a Unix socket is a secure transport already; like the `mysql` client,
+        // don't negotiate TLS over it
+        stream.capabilities.remove(Capabilities::SSL);
+        stream.is_secure = true;
+        return Ok(stream.boxed_socket());
+    }
+
     // https://www.postgresql.org/docs/12/libpq-ssl.html#LIBPQ-SSL-SSLMODE-STATEMENTS
     match options.ssl_mode {
         MySqlSslMode::Disabled => return Ok(stream.boxed_socket()),
@@ -81,11 +99,17 @@ pub(super) async fn maybe_upgrade<S: Socket>(
         tls_config,
         MapStream {
             server_version: stream.server_version,
+            connection_id: stream.connection_id,
             capabilities: stream.capabilities,
             sequence_id: stream.sequence_id,
             waiting: stream.waiting,
             charset: stream.charset,
             collation: stream.collation,
+            allow_local_infile: stream.allow_local_infile,
+            local_infile_allowlist: stream.local_infile_allowlist,
+            local_infile_handler: stream.local_infile_handler,
+            read_timeout: options.read_timeout,
+            write_timeout: options.write_timeout,
         },
     )
     .await
@@ -95,15 +119,24 @@ impl WithSocket for MapStream {
     type Output = MySqlStream;
 
     fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
+        let mut socket = BufferedSocket::new(Box::new(socket) as Box<dyn Socket>);
+        socket.set_timeouts(self.read_timeout, self.write_timeout);
+
         MySqlStream {
-            socket: BufferedSocket::new(Box::new(socket)),
+            socket,
             server_version: self.server_version,
+            connection_id: self.connection_id,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
+            status: Status::empty(),
             charset: self.charset,
             collation: self.collation,
-            is_tls: true,
+            is_secure: true,
+            rsa_exchange_started: false,
+            allow_local_infile: self.allow_local_infile,
+            local_infile_allowlist: self.local_infile_allowlist,
+            local_infile_handler: self.local_infile_handler,
         }
     }
 }
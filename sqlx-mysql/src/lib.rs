@@ -11,6 +11,7 @@ pub(crate) use sqlx_core::driver_prelude::*;
 pub mod any;
 
 mod arguments;
+mod cancel;
 mod collation;
 mod column;
 mod connection;
@@ -18,6 +19,7 @@ mod database;
 mod error;
 mod io;
 mod options;
+mod placeholders;
 mod protocol;
 mod query_result;
 mod row;
@@ -34,11 +36,12 @@ mod migrate;
 mod testing;
 
 pub use arguments::MySqlArguments;
+pub use cancel::MySqlCancelToken;
 pub use column::MySqlColumn;
 pub use connection::MySqlConnection;
 pub use database::MySql;
 pub use error::MySqlDatabaseError;
-pub use options::{MySqlConnectOptions, MySqlSslMode};
+pub use options::{MySqlConnectOptions, MySqlSslMode, MySqlZeroDatePolicy};
 pub use query_result::MySqlQueryResult;
 pub use row::MySqlRow;
 pub use statement::MySqlStatement;
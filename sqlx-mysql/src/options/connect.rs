@@ -1,10 +1,10 @@
 use crate::connection::ConnectOptions;
 use crate::error::Error;
-use crate::executor::Executor;
 use crate::{MySqlConnectOptions, MySqlConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use sqlx_core::Url;
+use std::io;
 use std::time::Duration;
 
 impl ConnectOptions for MySqlConnectOptions {
@@ -19,49 +19,29 @@ impl ConnectOptions for MySqlConnectOptions {
         Self::Connection: Sized,
     {
         Box::pin(async move {
-            let mut conn = MySqlConnection::establish(self).await?;
+            let mut conn = match self.connect_timeout {
+                Some(timeout) => sqlx_core::rt::timeout(timeout, MySqlConnection::establish(self))
+                    .await
+                    .map_err(|_| connect_timed_out())??,
+                None => MySqlConnection::establish(self).await?,
+            };
 
             // After the connection is established, we initialize by configuring a few
             // connection parameters
+            let options = session_init_sql(
+                self,
+                conn.stream.charset.as_str(),
+                conn.stream.collation.as_str(),
+            );
 
-            // https://mariadb.com/kb/en/sql-mode/
-
-            // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
-            //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
-
-            // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
-            //                          not available, a warning is given and the default storage
-            //                          engine is used instead.
-
-            // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
-
-            // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
-
-            // --
-
-            // Setting the time zone allows us to assume that the output
-            // from a TIMESTAMP field is UTC
-
-            // --
-
-            // https://mathiasbynens.be/notes/mysql-utf8mb4
+            // resetting the session or changing the user reverts these, so they are kept
+            // to be run again
+            conn.session_init = options;
+            conn.init_session().await?;
 
-            let mut options = String::new();
-            if self.pipes_as_concat {
-                options.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
-            } else {
-                options.push_str(
-                    r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',NO_ENGINE_SUBSTITUTION')),"#,
-                );
+            for sql in &self.prepare_on_connect {
+                conn.get_or_prepare(sql, true).await?;
             }
-            options.push_str(r#"time_zone='+00:00',"#);
-            options.push_str(&format!(
-                r#"NAMES {} COLLATE {};"#,
-                conn.stream.charset.as_str(),
-                conn.stream.collation.as_str()
-            ));
-
-            conn.execute(&*options).await?;
 
             Ok(conn)
         })
@@ -76,4 +56,70 @@ impl ConnectOptions for MySqlConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_full_statements(mut self, enabled: bool) -> Self {
+        self.log_settings.log_full_statements(enabled);
+        self
+    }
+}
+
+fn session_init_sql(options: &MySqlConnectOptions, charset: &str, collation: &str) -> String {
+    // https://mariadb.com/kb/en/sql-mode/
+
+    // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
+    //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
+
+    // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
+    //                          not available, a warning is given and the default storage
+    //                          engine is used instead.
+
+    // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
+
+    // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
+
+    // --
+
+    // Setting the time zone allows us to assume that the output
+    // from a TIMESTAMP field is UTC
+
+    // --
+
+    // https://mathiasbynens.be/notes/mysql-utf8mb4
+
+    let mut sql = String::new();
+    if options.pipes_as_concat {
+        sql.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
+    } else {
+        sql.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',NO_ENGINE_SUBSTITUTION')),"#);
+    }
+    sql.push_str(r#"time_zone='+00:00'"#);
+    if options.set_names {
+        sql.push_str(&format!(r#",NAMES {charset} COLLATE {collation}"#));
+    }
+    sql.push(';');
+
+    if options.read_only {
+        sql.push_str("SET SESSION TRANSACTION READ ONLY;");
+    }
+
+    sql
+}
+
+fn connect_timed_out() -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::TimedOut,
+        "timed out while connecting to the server",
+    ))
+}
+
+#[test]
+fn test_session_init_sql_without_set_names() {
+    let options = MySqlConnectOptions::new();
+    let sql = session_init_sql(&options, "utf8mb4", "utf8mb4_unicode_ci");
+    assert!(sql.ends_with("time_zone='+00:00',NAMES utf8mb4 COLLATE utf8mb4_unicode_ci;"));
+
+    let options = options.set_names(false);
+    let sql = session_init_sql(&options, "utf8mb4", "utf8mb4_unicode_ci");
+    assert!(sql.ends_with("time_zone='+00:00';"));
+    assert!(!sql.contains("NAMES"));
 }
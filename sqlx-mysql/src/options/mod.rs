@@ -1,11 +1,22 @@
+use std::fmt::{self, Debug, Formatter};
+use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
 
 mod connect;
 mod parse;
 mod ssl_mode;
+mod zero_date_policy;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::error::Error;
+use crate::{connection::LogSettings, net::tls::CertificateInput, MySqlConnection};
 pub use ssl_mode::MySqlSslMode;
+pub use zero_date_policy::MySqlZeroDatePolicy;
 
 /// Options and flags which can be used to configure a MySQL connection.
 ///
@@ -29,6 +40,8 @@ pub use ssl_mode::MySqlSslMode;
 /// | `ssl-ca` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
 /// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. |
+/// | `connect-timeout` | `None` | Maximum time to wait while connecting, in seconds. Zero means wait indefinitely. |
+/// | `zero-date-policy` | `convert_to_null` | How dates such as `0000-00-00` are decoded: `reject`, `convert_to_null` or `round_to_min`. See [`MySqlZeroDatePolicy`]. |
 ///
 /// # Example
 ///
@@ -72,11 +85,27 @@ pub struct MySqlConnectOptions {
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) prepare_on_connect: Vec<String>,
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
     pub(crate) log_settings: LogSettings,
     pub(crate) pipes_as_concat: bool,
     pub(crate) enable_cleartext_plugin: bool,
+    pub(crate) read_only: bool,
+    pub(crate) allow_local_infile: bool,
+    pub(crate) local_infile_allowlist: Vec<PathBuf>,
+    pub(crate) local_infile_handler: Option<LocalInfileHandler>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) tcp_keepalive: Option<Duration>,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) write_timeout: Option<Duration>,
+    pub(crate) reset_on_release: bool,
+    pub(crate) cancel_on_drop: bool,
+    pub(crate) after_connect: Option<AfterConnect>,
+    pub(crate) rsa_exchange_retries: u32,
+    pub(crate) set_names: bool,
+    pub(crate) numbered_placeholders: bool,
+    pub(crate) zero_date_policy: MySqlZeroDatePolicy,
 }
 
 impl Default for MySqlConnectOptions {
@@ -102,9 +131,25 @@ impl MySqlConnectOptions {
             ssl_client_cert: None,
             ssl_client_key: None,
             statement_cache_capacity: 100,
+            prepare_on_connect: Vec::new(),
             log_settings: Default::default(),
             pipes_as_concat: true,
             enable_cleartext_plugin: false,
+            read_only: false,
+            allow_local_infile: false,
+            local_infile_allowlist: Vec::new(),
+            local_infile_handler: None,
+            connect_timeout: None,
+            tcp_keepalive: None,
+            read_timeout: None,
+            write_timeout: None,
+            reset_on_release: false,
+            cancel_on_drop: false,
+            after_connect: None,
+            rsa_exchange_retries: 2,
+            set_names: true,
+            numbered_placeholders: false,
+            zero_date_policy: MySqlZeroDatePolicy::ConvertZeroToNull,
         }
     }
 
@@ -128,6 +173,9 @@ impl MySqlConnectOptions {
     /// Pass a path to a Unix socket. This changes the connection stream from
     /// TCP to UDS.
     ///
+    /// TLS is never negotiated over a Unix socket, regardless of the
+    /// [SSL mode][Self::ssl_mode].
+    ///
     /// By default set to `None`.
     pub fn socket(mut self, path: impl AsRef<Path>) -> Self {
         self.socket = Some(path.as_ref().to_path_buf());
@@ -172,6 +220,8 @@ impl MySqlConnectOptions {
     /// By default, the SSL mode is [`Preferred`](MySqlSslMode::Preferred), and the client will
     /// first attempt an SSL connection but fallback to a non-SSL connection on failure.
     ///
+    /// Ignored for Unix domain socket communication.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -305,10 +355,36 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Sets a list of statements to prepare and store in the statement cache as soon as
+    /// the connection is established, so the first query to use one of them does not
+    /// pay the cost of preparing it.
+    ///
+    /// If any of the statements fails to prepare, the connection attempt fails with the
+    /// error returned for it, such as a [`Error::Database`][crate::Error::Database] for a
+    /// statement the server rejects.
+    ///
+    /// Statements are only retained if they fit within the
+    /// [statement cache capacity][Self::statement_cache_capacity].
+    pub fn prepare_on_connect<I, S>(mut self, statements: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.prepare_on_connect = statements.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Sets the character set for the connection.
     ///
     /// The default character set is `utf8mb4`. This is supported from MySQL 5.5.3.
     /// If you need to connect to an older version, we recommend you to change this to `utf8`.
+    ///
+    /// The character set is requested in the handshake, through the collation, and then
+    /// confirmed with `SET NAMES` (see [`set_names`][Self::set_names]). The server silently
+    /// falls back to its default character set if it does not know the one requested in the
+    /// handshake, but `SET NAMES` fails, so connecting fails instead. Character sets that
+    /// MySQL does not allow for clients (`ucs2`, `utf16`, `utf16le` and `utf32`) are rejected
+    /// before connecting.
     pub fn charset(mut self, charset: &str) -> Self {
         self.charset = charset.to_owned();
         self
@@ -317,12 +393,28 @@ impl MySqlConnectOptions {
     /// Sets the collation for the connection.
     ///
     /// The default collation is derived from the `charset`. Normally, you should only have to set
-    /// the `charset`.
+    /// the `charset`. The collation must belong to the `charset` (e.g. `utf8mb4_bin` for
+    /// `utf8mb4`), or connecting fails.
     pub fn collation(mut self, collation: &str) -> Self {
         self.collation = Some(collation.to_owned());
         self
     }
 
+    /// Sets whether `SET NAMES` is sent after connecting to apply the `charset` and `collation`.
+    ///
+    /// The default value is `true`. Some MySQL-compatible servers do not support `SET NAMES`
+    /// and fail to connect with it, so it needs to be set to `false` in such cases.
+    ///
+    /// Without it, the connection uses the character set the client requested during the
+    /// handshake for text sent to the server, but the server may still send results in its
+    /// own default character set. Since SQLx decodes all text as UTF-8, only disable this
+    /// when the server is known to send UTF-8 (e.g. `character_set_results` is `utf8mb4`),
+    /// or decoding strings may fail or produce garbled text.
+    pub fn set_names(mut self, flag_val: bool) -> Self {
+        self.set_names = flag_val;
+        self
+    }
+
     /// Sets the flag that enables or disables the `PIPES_AS_CONCAT` connection setting
     ///
     /// The default value is set to true, but some MySql databases such as PlanetScale
@@ -343,8 +435,308 @@ impl MySqlConnectOptions {
     ///
     /// It is strongly recommended to set `.ssl_mode` to `Required`,
     /// `VerifyCa`, or `VerifyIdentity` when enabling cleartext plugin.
+    ///
+    /// The plugin is refused unless the connection is over TLS or a Unix socket, so the password
+    /// is never sent in cleartext over an unencrypted connection.
     pub fn enable_cleartext_plugin(mut self, flag_val: bool) -> Self {
         self.enable_cleartext_plugin = flag_val;
         self
     }
+
+    /// Sets whether transactions on the connection are read-only.
+    ///
+    /// If enabled, `SET SESSION TRANSACTION READ ONLY` is issued when the connection is
+    /// established so the server rejects any statement that writes to a table, which is
+    /// useful as a safety net when connecting to a read replica.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets the maximum time to wait for a connection to be established, including
+    /// the TLS handshake and authentication.
+    ///
+    /// If the timeout elapses, connecting fails with an I/O error of kind
+    /// [`TimedOut`][std::io::ErrorKind::TimedOut].
+    ///
+    /// Defaults to `None`, which waits indefinitely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .connect_timeout(Duration::from_secs(10));
+    /// ```
+    pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
+    /// Sets the idle time after which TCP keepalive probes are sent on the connection.
+    ///
+    /// If the server host goes away without closing the connection (e.g. it loses power or a
+    /// firewall drops the connection), the operating system notices once the probes go
+    /// unanswered, and reads and writes on the connection fail instead of hanging forever.
+    /// The interval between probes and how many are sent are left to the operating system.
+    ///
+    /// Has no effect on Unix domain sockets. Defaults to `None`, which leaves keepalive
+    /// disabled.
+    pub fn tcp_keepalive(mut self, idle: impl Into<Option<Duration>>) -> Self {
+        self.tcp_keepalive = idle.into();
+        self
+    }
+
+    /// Sets the maximum time to wait for the server to send data once a response is expected.
+    ///
+    /// This bounds every single read, so it must be longer than the longest time the server may
+    /// take to start sending the results of a query (or the next rows of one). If it elapses,
+    /// the operation fails with an I/O error of kind [`TimedOut`][std::io::ErrorKind::TimedOut]
+    /// and the connection can no longer be used; a pool closes such connections.
+    ///
+    /// Defaults to `None`, which waits indefinitely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use sqlx_mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .tcp_keepalive(Duration::from_secs(60))
+    ///     .read_timeout(Duration::from_secs(30))
+    ///     .write_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn read_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.read_timeout = timeout.into();
+        self
+    }
+
+    /// Sets the maximum time to wait for the server to accept data sent to it.
+    ///
+    /// If it elapses, the operation fails with an I/O error of kind
+    /// [`TimedOut`][std::io::ErrorKind::TimedOut] and the connection can no longer be used.
+    ///
+    /// Defaults to `None`, which waits indefinitely.
+    pub fn write_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.write_timeout = timeout.into();
+        self
+    }
+
+    /// Sets whether the session is reset when the connection is returned to a pool.
+    ///
+    /// If enabled, [`MySqlConnection::reset_connection`][crate::MySqlConnection::reset_connection]
+    /// is called on release instead of a ping, so user-defined variables, temporary tables
+    /// and other session state do not leak from one checkout to the next.
+    ///
+    /// This requires MySQL 5.7.3 or MariaDB 10.2.4 or later; with older servers every
+    /// connection is closed on release. Since the reset deallocates prepared statements,
+    /// the statement cache is also cleared on every release.
+    ///
+    /// Defaults to `false`.
+    pub fn reset_on_release(mut self, reset_on_release: bool) -> Self {
+        self.reset_on_release = reset_on_release;
+        self
+    }
+
+    /// Sets whether a query is killed when its [`fetch()`][crate::Executor::fetch] stream
+    /// (or the future of another `Executor` method) is dropped before reading all of its results.
+    ///
+    /// The rest of an abandoned result is always read and discarded the next time the
+    /// connection is used. When enabled, `KILL QUERY` is sent at that point first (see
+    /// [`MySqlConnection::cancel_token()`][crate::MySqlConnection::cancel_token]), so a
+    /// long-running query stops early instead of delaying the next one until it completes.
+    /// The request is only sent while the abandoned result is being drained, and completes
+    /// before anything else is sent, so it cannot kill a later query.
+    ///
+    /// This opens a new connection to the server for each abandoned query.
+    ///
+    /// Defaults to `false`.
+    pub fn cancel_on_drop(mut self, cancel: bool) -> Self {
+        self.cancel_on_drop = cancel;
+        self
+    }
+
+    /// Sets whether Postgres-style numbered placeholders (`$1`, `$2`, ...) are accepted in
+    /// queries with bound arguments, so the same query text can be used with both databases.
+    ///
+    /// If enabled, numbered placeholders are rewritten to `?` before the query is prepared, and
+    /// the arguments are rearranged to match: a placeholder may be used more than once or in any
+    /// order. Placeholders inside string literals, quoted identifiers and comments are left
+    /// alone. A query may not mix `?` and numbered placeholders.
+    ///
+    /// This only applies to queries executed with arguments, not to
+    /// [`Executor::prepare`][crate::Executor::prepare] or the query macros.
+    ///
+    /// Defaults to `false`.
+    pub fn numbered_placeholders(mut self, enabled: bool) -> Self {
+        self.numbered_placeholders = enabled;
+        self
+    }
+
+    /// Sets how dates with zero components, such as `0000-00-00`, are decoded.
+    ///
+    /// See [`MySqlZeroDatePolicy`] for the available policies. This affects decoding `DATE`,
+    /// `DATETIME` and `TIMESTAMP` values with `chrono` and `time`; whether such dates can be
+    /// stored at all is up to the server's `NO_ZERO_DATE` and `NO_ZERO_IN_DATE` SQL modes.
+    ///
+    /// Defaults to [`MySqlZeroDatePolicy::ConvertZeroToNull`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_mysql::{MySqlConnectOptions, MySqlZeroDatePolicy};
+    /// let options = MySqlConnectOptions::new()
+    ///     .zero_date_policy(MySqlZeroDatePolicy::RejectZeroDates);
+    /// ```
+    pub fn zero_date_policy(mut self, policy: MySqlZeroDatePolicy) -> Self {
+        self.zero_date_policy = policy;
+        self
+    }
+
+    /// Sets how many times to retry the RSA password exchange if it fails on a transient
+    /// network error (e.g. the connection being reset).
+    ///
+    /// Without TLS, the `sha256_password` and `caching_sha2_password` plugins fetch the server's
+    /// public key and send the password encrypted with it, which takes extra round trips
+    /// during authentication. The server closes the connection if the exchange fails, so each
+    /// retry opens a new connection; failures at any other point of connecting are not
+    /// retried.
+    ///
+    /// Defaults to `2`.
+    pub fn rsa_exchange_retries(mut self, retries: u32) -> Self {
+        self.rsa_exchange_retries = retries;
+        self
+    }
+
+    /// Sets whether the server may request local files for `LOAD DATA LOCAL INFILE`.
+    ///
+    /// Security Note:
+    /// When a `LOCAL INFILE` statement is executed, it is the *server* that names the file
+    /// the client should upload. A malicious or compromised server can use this to read
+    /// any file the application has access to, at any point during a query.
+    ///
+    /// This is disabled by default, in which case every request is refused by sending
+    /// an empty file and the query returns an error. When enabling it, it is strongly
+    /// recommended to also restrict the readable files with
+    /// [`local_infile_allowlist`][Self::local_infile_allowlist].
+    pub fn allow_local_infile(mut self, flag_val: bool) -> Self {
+        self.allow_local_infile = flag_val;
+        self
+    }
+
+    /// Restricts `LOCAL INFILE` uploads to the given files or directories.
+    ///
+    /// A file requested by the server is only sent if its canonical path is one of these
+    /// paths or is inside one of these directories. If the list is empty, any file
+    /// readable by the application may be sent.
+    ///
+    /// This has no effect unless [`allow_local_infile`][Self::allow_local_infile] is enabled.
+    pub fn local_infile_allowlist<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.local_infile_allowlist = paths
+            .into_iter()
+            .map(|path| path.as_ref().to_path_buf())
+            .collect();
+        self
+    }
+
+    /// Sets a handler that supplies the contents of the files requested for `LOCAL INFILE`,
+    /// instead of reading them from the file system.
+    ///
+    /// The handler is called with the file name the server asked for, which is the one given in
+    /// the `LOAD DATA LOCAL INFILE` statement, and returns the contents as a stream of chunks
+    /// that are sent to the server as they arrive. As no file is read,
+    /// [`local_infile_allowlist`][Self::local_infile_allowlist] does not apply; the handler
+    /// decides what to send for each name.
+    ///
+    /// If the stream returns an error, the upload ends early and the query returns that error.
+    /// Like with the `mysql` client, whatever was sent up to that point is still loaded.
+    ///
+    /// This has no effect unless [`allow_local_infile`][Self::allow_local_infile] is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::bytes::Bytes;
+    /// # use futures_util::stream::{self, StreamExt};
+    /// # use sqlx_mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .allow_local_infile(true)
+    ///     .local_infile_handler(|name| {
+    ///         let contents = format!("1,{name}\n2,{name}\n");
+    ///         stream::once(async move { Ok(Bytes::from(contents)) }).boxed()
+    ///     });
+    /// ```
+    pub fn local_infile_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) -> BoxStream<'static, io::Result<Bytes>> + Send + Sync + 'static,
+    {
+        self.local_infile_handler = Some(LocalInfileHandler(Arc::new(handler)));
+        self
+    }
+
+    /// Sets an asynchronous action to run on every new connection, such as setting session
+    /// variables or the default schema.
+    ///
+    /// It runs right after the session settings SQLx applies itself, and again whenever the
+    /// session is reset by [`MySqlConnection::reset_connection`] (e.g. on release to a pool,
+    /// see [`reset_on_release`][Self::reset_on_release]) or
+    /// [`MySqlConnection::change_user`], which would otherwise discard what it set. If it
+    /// returns an error, so does the connection attempt or the reset.
+    ///
+    /// It is not run on the connections opened by [`MySqlCancelToken`][crate::MySqlCancelToken]
+    /// to cancel a query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::executor::Executor;
+    /// # use sqlx_mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .reset_on_release(true)
+    ///     .after_connect(|conn| Box::pin(async move {
+    ///         conn.execute("SET @@session.time_zone = 'Europe/Paris', @tenant = 'acme'")
+    ///             .await?;
+    ///
+    ///         Ok(())
+    ///     }));
+    /// ```
+    pub fn after_connect<F>(mut self, callback: F) -> Self
+    where
+        for<'c> F:
+            Fn(&'c mut MySqlConnection) -> BoxFuture<'c, Result<(), Error>> + Send + Sync + 'static,
+    {
+        self.after_connect = Some(AfterConnect(Arc::new(callback)));
+        self
+    }
+}
+
+type LocalInfileContents = BoxStream<'static, io::Result<Bytes>>;
+
+#[derive(Clone)]
+pub(crate) struct LocalInfileHandler(
+    pub(crate) Arc<dyn Fn(&str) -> LocalInfileContents + Send + Sync>,
+);
+
+impl Debug for LocalInfileHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalInfileHandler").finish_non_exhaustive()
+    }
+}
+
+type AfterConnectFuture<'c> = BoxFuture<'c, Result<(), Error>>;
+
+#[derive(Clone)]
+pub(crate) struct AfterConnect(
+    pub(crate) Arc<dyn for<'c> Fn(&'c mut MySqlConnection) -> AfterConnectFuture<'c> + Send + Sync>,
+);
+
+impl Debug for AfterConnect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AfterConnect").finish_non_exhaustive()
+    }
 }
@@ -4,6 +4,9 @@ use std::str::FromStr;
 /// Options for controlling the desired security state of the connection to the MySQL server.
 ///
 /// It is used by the [`ssl_mode`](super::MySqlConnectOptions::ssl_mode) method.
+///
+/// When parsed from a string, such as the `ssl-mode` URL parameter, the Postgres names
+/// `disable`, `prefer`, `require`, `verify-ca` and `verify-full` are accepted as well.
 #[derive(Debug, Clone, Copy)]
 pub enum MySqlSslMode {
     /// Establish an unencrypted connection.
@@ -41,11 +44,13 @@ impl FromStr for MySqlSslMode {
 
     fn from_str(s: &str) -> Result<Self, Error> {
         Ok(match &*s.to_ascii_lowercase() {
-            "disabled" => MySqlSslMode::Disabled,
-            "preferred" => MySqlSslMode::Preferred,
-            "required" => MySqlSslMode::Required,
-            "verify_ca" => MySqlSslMode::VerifyCa,
-            "verify_identity" => MySqlSslMode::VerifyIdentity,
+            // the Postgres (`libpq`) names are accepted as well,
+            // so the same URL parameter works for either database
+            "disabled" | "disable" => MySqlSslMode::Disabled,
+            "preferred" | "prefer" => MySqlSslMode::Preferred,
+            "required" | "require" => MySqlSslMode::Required,
+            "verify_ca" | "verify-ca" => MySqlSslMode::VerifyCa,
+            "verify_identity" | "verify-full" => MySqlSslMode::VerifyIdentity,
 
             _ => {
                 return Err(Error::Configuration(
@@ -0,0 +1,166 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::error::BoxDynError;
+#[cfg(any(feature = "chrono", feature = "time"))]
+use std::borrow::Cow;
+#[cfg(any(feature = "chrono", feature = "time"))]
+use std::cmp;
+
+/// How `DATE`, `DATETIME` and `TIMESTAMP` values with zero date components, such as
+/// `0000-00-00` or `2024-00-00`, are decoded.
+///
+/// MySQL accepts these dates unless the `NO_ZERO_DATE` and `NO_ZERO_IN_DATE` SQL modes are set,
+/// but they are not valid calendar dates and cannot be represented by the date types of
+/// `chrono` or `time`.
+///
+/// It is used by the [`zero_date_policy`](super::MySqlConnectOptions::zero_date_policy) method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MySqlZeroDatePolicy {
+    /// Fail to decode any date with a zero component.
+    RejectZeroDates,
+
+    /// Treat an all-zero date (`0000-00-00`, or `0000-00-00 00:00:00`) as `NULL`, so it decodes
+    /// to `None` for `Option<T>`. Dates with only some zero components are still rejected.
+    ///
+    /// This is the default if `zero_date_policy` is not specified.
+    #[default]
+    ConvertZeroToNull,
+
+    /// Replace a zero month or day with `1`, so `0000-00-00` decodes as `0000-01-01` and
+    /// `2024-05-00` as `2024-05-01`.
+    RoundToMin,
+}
+
+impl MySqlZeroDatePolicy {
+    /// Apply the policy to the components of a date, returning the components to decode.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub(crate) fn apply(self, year: u16, month: u8, day: u8) -> Result<(u16, u8, u8), BoxDynError> {
+        if month != 0 && day != 0 {
+            return Ok((year, month, day));
+        }
+
+        match self {
+            MySqlZeroDatePolicy::RoundToMin => Ok((year, cmp::max(month, 1), cmp::max(day, 1))),
+
+            _ => Err(zero_date_error(&format!("{year:04}-{month:02}-{day:02}"))),
+        }
+    }
+
+    /// Apply the policy to a date (or date and time) in the text format, `YYYY-MM-DD[ ...]`.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub(crate) fn apply_text(self, s: &str) -> Result<Cow<'_, str>, BoxDynError> {
+        let (Some(month), Some(day)) = (s.get(5..7), s.get(8..10)) else {
+            // not a date; leave it to the parser to complain
+            return Ok(Cow::Borrowed(s));
+        };
+
+        if month != "00" && day != "00" {
+            return Ok(Cow::Borrowed(s));
+        }
+
+        match self {
+            MySqlZeroDatePolicy::RoundToMin => {
+                let mut s = s.to_owned();
+
+                if month == "00" {
+                    s.replace_range(5..7, "01");
+                }
+
+                if day == "00" {
+                    s.replace_range(8..10, "01");
+                }
+
+                Ok(Cow::Owned(s))
+            }
+
+            _ => Err(zero_date_error(&s[..10])),
+        }
+    }
+
+    /// Returns `true` if a value in the text format should be treated as `NULL`.
+    pub(crate) fn is_null_text(self, value: &[u8]) -> bool {
+        self == MySqlZeroDatePolicy::ConvertZeroToNull
+            && value.starts_with(b"0000-00-00")
+            && value[10..]
+                .iter()
+                .all(|b| matches!(b, b'0' | b' ' | b':' | b'.'))
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn zero_date_error(date: &str) -> BoxDynError {
+    format!(
+        "cannot decode zero date {date}; use `MySqlConnectOptions::zero_date_policy()` \
+         to convert it to NULL or round it up to a valid date"
+    )
+    .into()
+}
+
+impl FromStr for MySqlZeroDatePolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "reject" => MySqlZeroDatePolicy::RejectZeroDates,
+            "convert_to_null" => MySqlZeroDatePolicy::ConvertZeroToNull,
+            "round_to_min" => MySqlZeroDatePolicy::RoundToMin,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `zero_date_policy`").into(),
+                ));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MySqlZeroDatePolicy;
+
+    #[test]
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn it_applies_zero_date_policy() {
+        use MySqlZeroDatePolicy::*;
+
+        for policy in [RejectZeroDates, ConvertZeroToNull, RoundToMin] {
+            assert_eq!(policy.apply(2024, 5, 6).unwrap(), (2024, 5, 6));
+            assert_eq!(policy.apply_text("2024-05-06").unwrap(), "2024-05-06");
+        }
+
+        assert!(RejectZeroDates.apply(0, 0, 0).is_err());
+        assert!(RejectZeroDates.apply_text("0000-00-00 00:00:00").is_err());
+        assert!(ConvertZeroToNull.apply(2024, 0, 6).is_err());
+        assert!(ConvertZeroToNull.apply_text("2024-05-00").is_err());
+
+        assert_eq!(RoundToMin.apply(0, 0, 0).unwrap(), (0, 1, 1));
+        assert_eq!(RoundToMin.apply(2024, 5, 0).unwrap(), (2024, 5, 1));
+        assert_eq!(
+            RoundToMin.apply_text("0000-00-00 00:00:00").unwrap(),
+            "0000-01-01 00:00:00"
+        );
+        assert_eq!(RoundToMin.apply_text("2024-00-06").unwrap(), "2024-01-06");
+    }
+
+    #[test]
+    fn it_treats_zero_dates_as_null() {
+        use MySqlZeroDatePolicy::*;
+
+        assert!(ConvertZeroToNull.is_null_text(b"0000-00-00"));
+        assert!(ConvertZeroToNull.is_null_text(b"0000-00-00 00:00:00.000000"));
+        assert!(!ConvertZeroToNull.is_null_text(b"0000-00-00 00:00:01"));
+        assert!(!ConvertZeroToNull.is_null_text(b"2024-00-00"));
+        assert!(!RejectZeroDates.is_null_text(b"0000-00-00"));
+    }
+
+    #[test]
+    fn it_parses_zero_date_policy() {
+        assert_eq!(
+            "convert_to_null".parse::<MySqlZeroDatePolicy>().unwrap(),
+            MySqlZeroDatePolicy::ConvertZeroToNull
+        );
+        assert!("sometimes".parse::<MySqlZeroDatePolicy>().is_err());
+    }
+}
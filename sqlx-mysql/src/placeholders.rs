@@ -0,0 +1,239 @@
+use crate::error::Error;
+use crate::MySqlArguments;
+
+/// Rewrite numbered placeholders (`$1`, `$2`, ...) in `sql` to MySQL's `?`.
+///
+/// Returns the rewritten query and, for each `?` in order, the index of the argument it refers
+/// to (counting from 0), or `None` if the query has no numbered placeholders.
+///
+/// String literals, quoted identifiers and comments are left untouched, as is anything that
+/// follows a placeholder (such as a `::` cast, which MySQL does not support either way).
+/// A `$` that continues an identifier (e.g. `price$1`) is not a placeholder.
+pub(crate) fn rewrite_numbered_placeholders(
+    sql: &str,
+) -> Result<Option<(String, Vec<usize>)>, Error> {
+    let bytes = sql.as_bytes();
+
+    let mut rewritten = String::with_capacity(sql.len());
+    let mut order = Vec::new();
+    let mut has_question_mark = false;
+
+    // start of the text not yet copied to `rewritten`
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+
+                while i < bytes.len() {
+                    if bytes[i] == b'\\' && quote != b'`' {
+                        i += 2;
+                    } else if bytes[i] == quote {
+                        // a doubled quote stands for the quote itself
+                        if bytes.get(i + 1) == Some(&quote) {
+                            i += 2;
+                        } else {
+                            break;
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+
+                i += 1;
+            }
+
+            b'-' if bytes.get(i + 1) == Some(&b'-')
+                && bytes.get(i + 2).is_none_or(u8::is_ascii_whitespace) =>
+            {
+                i = find_from(bytes, i, b"\n").map_or(bytes.len(), |end| end + 1);
+            }
+
+            b'#' => {
+                i = find_from(bytes, i, b"\n").map_or(bytes.len(), |end| end + 1);
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = find_from(bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+            }
+
+            b'?' => {
+                has_question_mark = true;
+                i += 1;
+            }
+
+            b'$' if bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !is_identifier_byte(bytes[i - 1])) =>
+            {
+                let digits = bytes[i + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+
+                let number: usize = sql[i + 1..i + 1 + digits]
+                    .parse()
+                    .ok()
+                    .filter(|&number| number > 0)
+                    .ok_or_else(|| {
+                        err_protocol!("invalid placeholder {:?}", &sql[i..i + 1 + digits])
+                    })?;
+
+                rewritten.push_str(&sql[copied..i]);
+                rewritten.push('?');
+                order.push(number - 1);
+
+                i += 1 + digits;
+                copied = i;
+            }
+
+            _ => i += 1,
+        }
+    }
+
+    if order.is_empty() {
+        return Ok(None);
+    }
+
+    if has_question_mark {
+        return Err(err_protocol!(
+            "query mixes `?` and numbered (`$N`) placeholders"
+        ));
+    }
+
+    rewritten.push_str(&sql[copied..]);
+
+    Ok(Some((rewritten, order)))
+}
+
+/// Rewrite numbered placeholders in `sql` and rearrange `arguments` to match,
+/// or `None` if the query has no numbered placeholders.
+pub(crate) fn bind_numbered_placeholders(
+    sql: &str,
+    arguments: &MySqlArguments,
+) -> Result<Option<(String, MySqlArguments)>, Error> {
+    let Some((sql, order)) = rewrite_numbered_placeholders(sql)? else {
+        return Ok(None);
+    };
+
+    if let Some(&missing) = order.iter().find(|&&index| index >= arguments.len()) {
+        return Err(err_protocol!(
+            "placeholder ${} has no argument; {} were bound",
+            missing + 1,
+            arguments.len()
+        ));
+    }
+
+    Ok(Some((sql, arguments.reorder(&order))))
+}
+
+fn find_from(haystack: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|pos| from + pos)
+}
+
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || !b.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bind_numbered_placeholders, rewrite_numbered_placeholders};
+    use crate::MySqlArguments;
+
+    fn rewrite(sql: &str) -> Option<(String, Vec<usize>)> {
+        rewrite_numbered_placeholders(sql).unwrap()
+    }
+
+    #[test]
+    fn it_rewrites_numbered_placeholders() {
+        assert_eq!(
+            rewrite("SELECT * FROM users WHERE id = $1 AND name = $2"),
+            Some((
+                "SELECT * FROM users WHERE id = ? AND name = ?".to_owned(),
+                vec![0, 1]
+            ))
+        );
+
+        // repeated placeholders bind the same argument twice
+        assert_eq!(
+            rewrite("SELECT $1 + $1"),
+            Some(("SELECT ? + ?".to_owned(), vec![0, 0]))
+        );
+
+        // placeholders may appear in any order
+        assert_eq!(
+            rewrite("INSERT INTO t (b, a, c) VALUES ($2, $1, $10)"),
+            Some((
+                "INSERT INTO t (b, a, c) VALUES (?, ?, ?)".to_owned(),
+                vec![1, 0, 9]
+            ))
+        );
+
+        // a cast is kept as-is
+        assert_eq!(
+            rewrite("SELECT $1::int"),
+            Some(("SELECT ?::int".to_owned(), vec![0]))
+        );
+    }
+
+    #[test]
+    fn it_ignores_placeholders_in_literals_and_comments() {
+        assert_eq!(
+            rewrite(r#"SELECT '$1', "$2", `$3`, 'it''s $4', 'a\'$5', $6 -- $7"#),
+            Some((
+                r#"SELECT '$1', "$2", `$3`, 'it''s $4', 'a\'$5', ? -- $7"#.to_owned(),
+                vec![5]
+            ))
+        );
+
+        assert_eq!(
+            rewrite("SELECT /* $1 */ $2 # $3\n, $1"),
+            Some(("SELECT /* $1 */ ? # $3\n, ?".to_owned(), vec![1, 0]))
+        );
+
+        // `$` is allowed in identifiers
+        assert_eq!(rewrite("SELECT price$1 FROM t"), None);
+
+        assert_eq!(rewrite("SELECT ? FROM t WHERE x = '$1'"), None);
+    }
+
+    #[test]
+    fn it_rearranges_arguments() {
+        let mut arguments = MySqlArguments::default();
+        arguments.add(1_i32);
+        arguments.add(None::<i32>);
+        arguments.add("three");
+
+        let (sql, arguments) = bind_numbered_placeholders("SELECT $3, $1, $2, $1", &arguments)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(sql, "SELECT ?, ?, ?, ?");
+
+        let mut expected = MySqlArguments::default();
+        expected.add("three");
+        expected.add(1_i32);
+        expected.add(None::<i32>);
+        expected.add(1_i32);
+
+        assert_eq!(arguments.values, expected.values);
+        assert_eq!(arguments.value_ends, expected.value_ends);
+        assert_eq!(arguments.null_bitmap, expected.null_bitmap);
+        assert_eq!(arguments.types, expected.types);
+
+        let mut arguments = MySqlArguments::default();
+        arguments.add(1_i32);
+        assert!(bind_numbered_placeholders("SELECT $2", &arguments).is_err());
+    }
+
+    #[test]
+    fn it_rejects_invalid_placeholders() {
+        assert!(rewrite_numbered_placeholders("SELECT $0").is_err());
+        assert!(rewrite_numbered_placeholders("SELECT $1, ?").is_err());
+    }
+}
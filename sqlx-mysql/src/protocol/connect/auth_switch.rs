@@ -24,7 +24,13 @@ impl Decode<'_, bool> for AuthSwitchRequest {
             ));
         }
 
-        let plugin = buf.get_str_nul()?.parse()?;
+        let name = buf.get_str_nul()?;
+        let plugin: AuthPlugin = name.parse().map_err(|_| {
+            err_protocol!(
+                "server requested a switch to unsupported authentication plugin {:?}",
+                name
+            )
+        })?;
 
         if matches!(plugin, AuthPlugin::MySqlClearPassword) && !enable_cleartext_plugin {
             return Err(err_protocol!("mysql_cleartext_plugin disabled"));
@@ -56,11 +62,11 @@ impl Decode<'_, bool> for AuthSwitchRequest {
 }
 
 #[derive(Debug)]
-pub struct AuthSwitchResponse(pub Vec<u8>);
+pub struct AuthSwitchResponse<'a>(pub &'a [u8]);
 
-impl Encode<'_, Capabilities> for AuthSwitchResponse {
+impl Encode<'_, Capabilities> for AuthSwitchResponse<'_> {
     fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
-        buf.extend_from_slice(&self.0);
+        buf.extend_from_slice(self.0);
     }
 }
 
@@ -95,3 +101,16 @@ fn test_decode_auth_switch_packet_no_data() {
     assert!(matches!(p.plugin, AuthPlugin::MySqlClearPassword));
     assert_eq!(p.data, Bytes::new());
 }
+
+#[test]
+fn test_decode_auth_switch_unsupported_plugin() {
+    const AUTH_SWITCH_DIALOG: &[u8] = b"\xfedialog\x00\x01Password: \x00";
+
+    let e = AuthSwitchRequest::decode_with(AUTH_SWITCH_DIALOG.into(), true).unwrap_err();
+
+    assert_eq!(
+        e.to_string(),
+        "encountered unexpected or invalid data: \
+         server requested a switch to unsupported authentication plugin \"dialog\""
+    );
+}
@@ -69,3 +69,18 @@ fn test_decode_err_packet_unknown_database() {
     assert_eq!(p.sql_state.as_deref(), Some("42000"));
     assert_eq!(&p.error_message, "Unknown database \'unknown\'");
 }
+
+#[test]
+fn test_decode_err_packet_duplicate_entry() {
+    const ERR_DUPLICATE_ENTRY: &[u8] =
+        b"\xff\x26\x04#23000Duplicate entry \'1\' for key \'tweet.PRIMARY\'";
+
+    let p = ErrPacket::decode_with(ERR_DUPLICATE_ENTRY.into(), Capabilities::PROTOCOL_41).unwrap();
+
+    assert_eq!(p.error_code, 1062);
+    assert_eq!(p.sql_state.as_deref(), Some("23000"));
+    assert_eq!(
+        &p.error_message,
+        "Duplicate entry \'1\' for key \'tweet.PRIMARY\'"
+    );
+}
@@ -0,0 +1,73 @@
+use crate::io::{BufMutExt, Encode};
+use crate::protocol::auth::AuthPlugin;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_com_change_user.html
+// https://mariadb.com/kb/en/com_change_user/
+
+#[derive(Debug)]
+pub(crate) struct ChangeUser<'a> {
+    /// Name of the SQL account to switch to
+    pub(crate) username: &'a str,
+
+    /// Opaque authentication response
+    pub(crate) auth_response: Option<&'a [u8]>,
+
+    /// Default schema for the session
+    pub(crate) database: Option<&'a str>,
+
+    /// Default collation for the session
+    pub(crate) collation: u8,
+
+    /// Authentication method used by the client
+    pub(crate) auth_plugin: Option<AuthPlugin>,
+}
+
+impl Encode<'_, Capabilities> for ChangeUser<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) {
+        buf.push(0x11); // COM_CHANGE_USER
+
+        buf.put_str_nul(self.username);
+
+        // unlike the handshake response, this is never length-encoded
+        let response = self.auth_response.unwrap_or_default();
+
+        if capabilities.contains(Capabilities::SECURE_CONNECTION) {
+            buf.push(response.len() as u8);
+            buf.extend(response);
+        } else {
+            buf.extend(response);
+            buf.push(0);
+        }
+
+        buf.put_str_nul(self.database.unwrap_or_default());
+
+        buf.extend(&(self.collation as u16).to_le_bytes());
+
+        if capabilities.contains(Capabilities::PLUGIN_AUTH) {
+            buf.put_str_nul(self.auth_plugin.map(AuthPlugin::name).unwrap_or_default());
+        }
+    }
+}
+
+#[test]
+fn test_encode_change_user() {
+    let mut buf = Vec::new();
+
+    ChangeUser {
+        username: "root",
+        auth_response: Some(&[0xaa, 0xbb]),
+        database: Some("sqlx"),
+        collation: 45,
+        auth_plugin: Some(AuthPlugin::MySqlNativePassword),
+    }
+    .encode_with(
+        &mut buf,
+        Capabilities::SECURE_CONNECTION | Capabilities::PLUGIN_AUTH,
+    );
+
+    assert_eq!(
+        buf,
+        &b"\x11root\0\x02\xaa\xbbsqlx\0\x2d\x00mysql_native_password\0"[..]
+    );
+}
@@ -1,11 +1,15 @@
+mod change_user;
 mod column;
 mod ping;
 mod query;
 mod quit;
+mod reset_connection;
 mod row;
 
+pub(crate) use change_user::ChangeUser;
 pub(crate) use column::{ColumnDefinition, ColumnFlags, ColumnType};
 pub(crate) use ping::Ping;
 pub(crate) use query::Query;
 pub(crate) use quit::Quit;
+pub(crate) use reset_connection::ResetConnection;
 pub(crate) use row::TextRow;
@@ -0,0 +1,13 @@
+use crate::io::Encode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_com_reset_connection.html
+
+#[derive(Debug)]
+pub(crate) struct ResetConnection;
+
+impl Encode<'_, Capabilities> for ResetConnection {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x1f); // COM_RESET_CONNECTION
+    }
+}
@@ -7,10 +7,21 @@ pub struct MySqlQueryResult {
 }
 
 impl MySqlQueryResult {
+    /// The value generated for an `AUTO_INCREMENT` column by the query, as reported in the `OK`
+    /// packet, or `0` if none was generated.
+    ///
+    /// If a single `INSERT` adds several rows, this is the value generated for the *first* of
+    /// them. If the query string contains more than one statement, this is the value reported
+    /// for the last one.
     pub fn last_insert_id(&self) -> u64 {
         self.last_insert_id
     }
 
+    /// The number of rows affected by the query, as reported in the `OK` packet.
+    ///
+    /// For an `UPDATE`, MySQL counts the rows that were actually changed, not every row matched
+    /// by the `WHERE` clause: rows that already had the new values are not included. If the
+    /// query string contains more than one statement, this is the sum for all of them.
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
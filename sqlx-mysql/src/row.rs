@@ -6,13 +6,18 @@ use crate::column::ColumnIndex;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::HashMap;
-use crate::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef};
+use crate::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef, MySqlZeroDatePolicy};
 
 /// Implementation of [`Row`] for MySQL.
+///
+/// Columns can be accessed by index or by name (the column alias, if any). If more than one
+/// column has the same name, as is common with joins, the name refers to the last of them;
+/// use an alias or the index to get at the others.
 #[derive(Debug)]
 pub struct MySqlRow {
     pub(crate) row: protocol::Row,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) zero_date_policy: MySqlZeroDatePolicy,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
 }
@@ -34,6 +39,7 @@ impl Row for MySqlRow {
 
         Ok(MySqlValueRef {
             format: self.format,
+            zero_date_policy: self.zero_date_policy,
             row: Some(&self.row.storage),
             type_info: column.type_info.clone(),
             value,
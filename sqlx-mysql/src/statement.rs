@@ -6,7 +6,7 @@ use crate::HashMap;
 use crate::{MySql, MySqlArguments, MySqlTypeInfo};
 use either::Either;
 use std::borrow::Cow;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 
 pub(crate) use sqlx_core::statement::*;
 
@@ -14,6 +14,27 @@ pub(crate) use sqlx_core::statement::*;
 pub struct MySqlStatement<'q> {
     pub(crate) sql: Cow<'q, str>,
     pub(crate) metadata: MySqlStatementMetadata,
+    // the statement `prepare()` created on the server, shared by every copy of this one
+    pub(crate) prepared: Option<Arc<MySqlPreparedStatement>>,
+}
+
+// a statement prepared on a connection apart from its statement cache; it stays prepared until
+// the last `MySqlStatement` referring to it is dropped, and is closed on the next use of the
+// connection after that
+#[derive(Debug)]
+pub(crate) struct MySqlPreparedStatement {
+    pub(crate) id: u32,
+    // statements of the connection waiting to be closed
+    pub(crate) dropped: Arc<Mutex<Vec<u32>>>,
+}
+
+impl Drop for MySqlPreparedStatement {
+    fn drop(&mut self) {
+        self.dropped
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(self.id);
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -30,6 +51,7 @@ impl<'q> Statement<'q> for MySqlStatement<'q> {
         MySqlStatement::<'static> {
             sql: Cow::Owned(self.sql.clone().into_owned()),
             metadata: self.metadata.clone(),
+            prepared: self.prepared.clone(),
         }
     }
 
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::connection::Waiting;
@@ -15,10 +17,18 @@ impl TransactionManager for MySqlTransactionManager {
     type Database = MySql;
 
     fn begin(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut MySqlConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.transaction_depth;
 
-            conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            conn.execute(&*begin_transaction_sql(depth, options)?)
+                .await?;
             conn.transaction_depth = depth + 1;
 
             Ok(())
@@ -64,3 +74,79 @@ impl TransactionManager for MySqlTransactionManager {
         }
     }
 }
+
+// https://dev.mysql.com/doc/refman/8.0/en/set-transaction.html
+fn begin_transaction_sql(
+    depth: usize,
+    options: TransactionOptions,
+) -> Result<Cow<'static, str>, Error> {
+    if options == TransactionOptions::default() {
+        return Ok(begin_ansi_transaction_sql(depth));
+    }
+
+    if depth > 0 {
+        return Err(Error::Configuration(
+            "transaction options cannot be applied to a savepoint".into(),
+        ));
+    }
+
+    if options.is_deferrable() {
+        return Err(Error::Configuration(
+            "MySQL does not support DEFERRABLE transactions".into(),
+        ));
+    }
+
+    let mut characteristics = Vec::new();
+
+    if let Some(level) = options.get_isolation_level() {
+        characteristics.push(format!("ISOLATION LEVEL {}", level.as_sql()));
+    }
+
+    if options.is_read_only() {
+        characteristics.push("READ ONLY".into());
+    }
+
+    // without `SESSION` or `GLOBAL`, this only applies to the next transaction
+    Ok(Cow::Owned(format!(
+        "SET TRANSACTION {}; START TRANSACTION",
+        characteristics.join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::begin_transaction_sql;
+    use sqlx_core::transaction::{IsolationLevel, TransactionOptions};
+
+    #[test]
+    fn it_builds_begin_with_options() {
+        let sql = |depth, options| begin_transaction_sql(depth, options).unwrap();
+
+        assert_eq!(sql(0, TransactionOptions::new()), "BEGIN");
+        assert_eq!(
+            sql(1, TransactionOptions::new()),
+            "SAVEPOINT _sqlx_savepoint_1"
+        );
+
+        assert_eq!(
+            sql(
+                0,
+                TransactionOptions::new().isolation_level(IsolationLevel::ReadCommitted)
+            ),
+            "SET TRANSACTION ISOLATION LEVEL READ COMMITTED; START TRANSACTION"
+        );
+
+        assert_eq!(
+            sql(
+                0,
+                TransactionOptions::new()
+                    .isolation_level(IsolationLevel::Serializable)
+                    .read_only(true)
+            ),
+            "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE, READ ONLY; START TRANSACTION"
+        );
+
+        assert!(begin_transaction_sql(1, TransactionOptions::new().read_only(true)).is_err());
+        assert!(begin_transaction_sql(0, TransactionOptions::new().deferrable(true)).is_err());
+    }
+}
@@ -22,6 +22,8 @@ impl Type<MySql> for [u8] {
                 | ColumnType::String
                 | ColumnType::VarString
                 | ColumnType::Enum
+                // `BIT(M)` values are sent as big-endian bytes in both protocols
+                | ColumnType::Bit
         )
     }
 }
@@ -83,3 +85,25 @@ impl Decode<'_, MySql> for Vec<u8> {
         <&[u8] as Decode<MySql>>::decode(value).map(ToOwned::to_owned)
     }
 }
+
+impl<const N: usize> Type<MySql> for [u8; N] {
+    fn type_info() -> MySqlTypeInfo {
+        <[u8] as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <&[u8] as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<const N: usize> Encode<'_, MySql> for [u8; N] {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        <&[u8] as Encode<MySql>>::encode(self.as_slice(), buf)
+    }
+}
+
+impl<const N: usize> Decode<'_, MySql> for [u8; N] {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(<&[u8] as Decode<MySql>>::decode(value)?.try_into()?)
+    }
+}
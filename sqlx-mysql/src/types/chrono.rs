@@ -5,11 +5,11 @@ use chrono::{
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::error::BoxDynError;
 use crate::protocol::text::ColumnType;
 use crate::type_info::MySqlTypeInfo;
 use crate::types::Type;
-use crate::{MySql, MySqlValueFormat, MySqlValueRef};
+use crate::{MySql, MySqlValueFormat, MySqlValueRef, MySqlZeroDatePolicy};
 
 impl Type<MySql> for DateTime<Utc> {
     fn type_info() -> MySqlTypeInfo {
@@ -155,12 +155,12 @@ impl<'r> Decode<'r, MySql> for NaiveDate {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.format() {
             MySqlValueFormat::Binary => {
-                decode_date(&value.as_bytes()?[1..])?.ok_or_else(|| UnexpectedNullError.into())
+                decode_date(&value.as_bytes()?[1..], value.zero_date_policy)
             }
 
             MySqlValueFormat::Text => {
-                let s = value.as_str()?;
-                NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(Into::into)
+                let s = value.as_date_str()?;
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(Into::into)
             }
         }
     }
@@ -215,7 +215,7 @@ impl<'r> Decode<'r, MySql> for NaiveDateTime {
                 let buf = value.as_bytes()?;
 
                 let len = buf[0];
-                let date = decode_date(&buf[1..])?.ok_or(UnexpectedNullError)?;
+                let date = decode_date(&buf[1..], value.zero_date_policy)?;
 
                 let dt = if len > 4 {
                     date.and_time(decode_time(len - 4, &buf[5..])?)
@@ -228,8 +228,8 @@ impl<'r> Decode<'r, MySql> for NaiveDateTime {
             }
 
             MySqlValueFormat::Text => {
-                let s = value.as_str()?;
-                NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").map_err(Into::into)
+                let s = value.as_date_str()?;
+                NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S%.f").map_err(Into::into)
             }
         }
     }
@@ -245,22 +245,21 @@ fn encode_date(date: &NaiveDate, buf: &mut Vec<u8>) {
     buf.push(date.day() as u8);
 }
 
-fn decode_date(mut buf: &[u8]) -> Result<Option<NaiveDate>, BoxDynError> {
-    match buf.len() {
+fn decode_date(
+    mut buf: &[u8],
+    zero_date_policy: MySqlZeroDatePolicy,
+) -> Result<NaiveDate, BoxDynError> {
+    let (year, month, day) = match buf.len() {
         // MySQL specifies that if there are no bytes, this is all zeros
-        0 => Ok(None),
-        4.. => {
-            let year = buf.get_u16_le() as i32;
-            let month = buf[0] as u32;
-            let day = buf[1] as u32;
+        0 => (0, 0, 0),
+        4.. => (buf.get_u16_le(), buf[0], buf[1]),
+        len => return Err(format!("expected at least 4 bytes for date, got {len}").into()),
+    };
 
-            let date = NaiveDate::from_ymd_opt(year, month, day)
-                .ok_or_else(|| format!("server returned invalid date: {year}/{month}/{day}"))?;
+    let (year, month, day) = zero_date_policy.apply(year, month, day)?;
 
-            Ok(Some(date))
-        }
-        len => Err(format!("expected at least 4 bytes for date, got {len}").into()),
-    }
+    NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+        .ok_or_else(|| format!("server returned invalid date: {year}/{month}/{day}").into())
 }
 
 fn encode_time(time: &NaiveTime, include_micros: bool, buf: &mut Vec<u8>) {
@@ -4,7 +4,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::protocol::text::ColumnType;
-use crate::types::{Json, Type};
+use crate::types::{Json, RawJson, Type};
 use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
 
 impl<T> Type<MySql> for Json<T> {
@@ -58,6 +58,12 @@ where
     }
 }
 
+impl Encode<'_, MySql> for RawJson<'_> {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        <&str as Encode<MySql>>::encode(self.0, buf)
+    }
+}
+
 impl<'r, T> Decode<'r, MySql> for Json<T>
 where
     T: 'r + Deserialize<'r>,
@@ -12,11 +12,11 @@
 //! | `u8`                                  | TINYINT UNSIGNED                                     |
 //! | `u16`                                 | SMALLINT UNSIGNED                                    |
 //! | `u32`                                 | INT UNSIGNED                                         |
-//! | `u64`                                 | BIGINT UNSIGNED                                      |
+//! | `u64`                                 | BIGINT UNSIGNED, BIT                                 |
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
-//! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`         | VARBINARY, BINARY, BLOB, BIT                         |
 //!
 //! ##### Note: `BOOLEAN`/`BOOL` Type
 //! MySQL and MariaDB treat `BOOLEAN` as an alias of the `TINYINT` type:
@@ -91,6 +91,7 @@
 //! | [`Json<T>`]                           | JSON                                                 |
 //! | `serde_json::JsonValue`               | JSON                                                 |
 //! | `&serde_json::value::RawValue`        | JSON                                                 |
+//! | [`RawJson<'_>`] (encode only)         | JSON                                                 |
 //!
 //! # Nullable
 //!
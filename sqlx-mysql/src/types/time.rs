@@ -5,11 +5,11 @@ use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::error::BoxDynError;
 use crate::protocol::text::ColumnType;
 use crate::type_info::MySqlTypeInfo;
 use crate::types::Type;
-use crate::{MySql, MySqlValueFormat, MySqlValueRef};
+use crate::{MySql, MySqlValueFormat, MySqlValueRef, MySqlZeroDatePolicy};
 
 impl Type<MySql> for OffsetDateTime {
     fn type_info() -> MySqlTypeInfo {
@@ -132,11 +132,11 @@ impl<'r> Decode<'r, MySql> for Date {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.format() {
             MySqlValueFormat::Binary => {
-                Ok(decode_date(&value.as_bytes()?[1..])?.ok_or(UnexpectedNullError)?)
+                decode_date(&value.as_bytes()?[1..], value.zero_date_policy)
             }
             MySqlValueFormat::Text => {
-                let s = value.as_str()?;
-                Date::parse(s, &format_description!("[year]-[month]-[day]")).map_err(Into::into)
+                let s = value.as_date_str()?;
+                Date::parse(&s, &format_description!("[year]-[month]-[day]")).map_err(Into::into)
             }
         }
     }
@@ -185,7 +185,7 @@ impl<'r> Decode<'r, MySql> for PrimitiveDateTime {
             MySqlValueFormat::Binary => {
                 let buf = value.as_bytes()?;
                 let len = buf[0];
-                let date = decode_date(&buf[1..])?.ok_or(UnexpectedNullError)?;
+                let date = decode_date(&buf[1..], value.zero_date_policy)?;
 
                 let dt = if len > 4 {
                     date.with_time(decode_time(len - 4, &buf[5..])?)
@@ -197,7 +197,7 @@ impl<'r> Decode<'r, MySql> for PrimitiveDateTime {
             }
 
             MySqlValueFormat::Text => {
-                let s = value.as_str()?;
+                let s = &*value.as_date_str()?;
 
                 // If there are no nanoseconds parse without them
                 if s.contains('.') {
@@ -230,19 +230,17 @@ fn encode_date(date: &Date, buf: &mut Vec<u8>) {
     buf.push(date.day());
 }
 
-fn decode_date(buf: &[u8]) -> Result<Option<Date>, BoxDynError> {
-    if buf.is_empty() {
-        // zero buffer means a zero date (null)
-        return Ok(None);
-    }
+fn decode_date(buf: &[u8], zero_date_policy: MySqlZeroDatePolicy) -> Result<Date, BoxDynError> {
+    let (year, month, day) = if buf.is_empty() {
+        // zero buffer means a zero date
+        (0, 0, 0)
+    } else {
+        (LittleEndian::read_u16(buf), buf[2], buf[3])
+    };
+
+    let (year, month, day) = zero_date_policy.apply(year, month, day)?;
 
-    Date::from_calendar_date(
-        LittleEndian::read_u16(buf) as i32,
-        time::Month::try_from(buf[2] as u8)?,
-        buf[3] as u8,
-    )
-    .map_err(Into::into)
-    .map(Some)
+    Date::from_calendar_date(year.into(), time::Month::try_from(month)?, day).map_err(Into::into)
 }
 
 fn encode_time(time: &Time, include_micros: bool, buf: &mut Vec<u8>) {
@@ -33,8 +33,13 @@ impl Decode<'_, MySql> for Uuid {
         // delegate to the &[u8] type to decode from MySQL
         let bytes = <&[u8] as Decode<MySql>>::decode(value)?;
 
-        // construct a Uuid from the returned bytes
-        Uuid::from_slice(bytes).map_err(Into::into)
+        // a `BINARY(16)` column holds the raw bytes, anything else (e.g. `CHAR(36)`)
+        // is expected to hold the textual form
+        if bytes.len() == 16 {
+            Uuid::from_slice(bytes).map_err(Into::into)
+        } else {
+            std::str::from_utf8(bytes)?.parse().map_err(Into::into)
+        }
     }
 }
 
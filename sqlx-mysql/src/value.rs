@@ -6,7 +6,7 @@ pub(crate) use sqlx_core::value::*;
 
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::protocol::text::ColumnType;
-use crate::{MySql, MySqlTypeInfo};
+use crate::{MySql, MySqlTypeInfo, MySqlZeroDatePolicy};
 
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
@@ -21,6 +21,7 @@ pub struct MySqlValue {
     value: Option<Bytes>,
     type_info: MySqlTypeInfo,
     format: MySqlValueFormat,
+    zero_date_policy: MySqlZeroDatePolicy,
 }
 
 /// Implementation of [`ValueRef`] for MySQL.
@@ -30,6 +31,7 @@ pub struct MySqlValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: MySqlTypeInfo,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) zero_date_policy: MySqlZeroDatePolicy,
 }
 
 impl<'r> MySqlValueRef<'r> {
@@ -47,6 +49,13 @@ impl<'r> MySqlValueRef<'r> {
     pub(crate) fn as_str(&self) -> Result<&'r str, BoxDynError> {
         Ok(from_utf8(self.as_bytes()?)?)
     }
+
+    /// The value as a date (or date and time) in the text format, with zero date components
+    /// handled according to the connection's [`MySqlZeroDatePolicy`].
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub(crate) fn as_date_str(&self) -> Result<Cow<'r, str>, BoxDynError> {
+        self.zero_date_policy.apply_text(self.as_str()?)
+    }
 }
 
 impl Value for MySqlValue {
@@ -58,6 +67,7 @@ impl Value for MySqlValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            zero_date_policy: self.zero_date_policy,
         }
     }
 
@@ -66,7 +76,12 @@ impl Value for MySqlValue {
     }
 
     fn is_null(&self) -> bool {
-        is_null(self.value.as_deref(), &self.type_info)
+        is_null(
+            self.value.as_deref(),
+            &self.type_info,
+            self.format,
+            self.zero_date_policy,
+        )
     }
 }
 
@@ -86,6 +101,7 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            zero_date_policy: self.zero_date_policy,
         }
     }
 
@@ -95,19 +111,34 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
 
     #[inline]
     fn is_null(&self) -> bool {
-        is_null(self.value.as_deref(), &self.type_info)
+        is_null(
+            self.value,
+            &self.type_info,
+            self.format,
+            self.zero_date_policy,
+        )
     }
 }
 
-fn is_null(value: Option<&[u8]>, ty: &MySqlTypeInfo) -> bool {
+fn is_null(
+    value: Option<&[u8]>,
+    ty: &MySqlTypeInfo,
+    format: MySqlValueFormat,
+    zero_date_policy: MySqlZeroDatePolicy,
+) -> bool {
     if let Some(value) = value {
-        // zero dates and date times should be treated the same as NULL
-        if matches!(
-            ty.r#type,
-            ColumnType::Date | ColumnType::Timestamp | ColumnType::Datetime
-        ) && value.get(0) == Some(&0)
+        // zero dates are treated the same as NULL if the policy says so
+        if zero_date_policy == MySqlZeroDatePolicy::ConvertZeroToNull
+            && matches!(
+                ty.r#type,
+                ColumnType::Date | ColumnType::Timestamp | ColumnType::Datetime
+            )
         {
-            return true;
+            return match format {
+                // in the binary protocol, a zero date is sent with a length of 0
+                MySqlValueFormat::Binary => value.first() == Some(&0),
+                MySqlValueFormat::Text => zero_date_policy.is_null_text(value),
+            };
         }
     }
 
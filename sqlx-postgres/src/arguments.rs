@@ -1,9 +1,11 @@
 use std::fmt::{self, Write};
 use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
 
 use crate::encode::{Encode, IsNull};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::io::AsyncRead;
 use crate::types::Type;
 use crate::{PgConnection, PgTypeInfo, Postgres};
 
@@ -46,6 +48,20 @@ pub struct PgArgumentBuffer {
     // function and can just ask postgres.
     //
     type_holes: Vec<(usize, UStr)>, // Vec<{ offset, type_name }>
+
+    // Values whose contents are not in the buffer but are read from a source while the
+    // arguments are sent, see `PgByteaStream`.
+    streams: Vec<PgStreamedValue>,
+}
+
+pub(crate) struct PgStreamedValue {
+    // Offset in the buffer where the contents would start, right after the length prefix
+    pub(crate) offset: usize,
+
+    pub(crate) len: u64,
+
+    // `None` if the source was already taken by an earlier query
+    pub(crate) source: Mutex<Option<Box<dyn AsyncRead + Send + Unpin>>>,
 }
 
 /// Implementation of [`Arguments`] for PostgreSQL.
@@ -135,7 +151,14 @@ impl PgArgumentBuffer {
 
         // encode the value into our buffer
         let len = if let IsNull::No = value.encode(self) {
-            (self.len() - offset - 4) as i32
+            match self.streams.last() {
+                // the contents are sent separately, so the buffer only holds the length
+                Some(stream) if stream.offset == offset + 4 => {
+                    i32::try_from(stream.len).unwrap_or(i32::MAX)
+                }
+
+                _ => (self.len() - offset - 4) as i32,
+            }
         } else {
             // Write a -1 to indicate NULL
             // NOTE: It is illegal for [encode] to write any data
@@ -159,6 +182,28 @@ impl PgArgumentBuffer {
         self.patches.push((offset, index, Box::new(callback)));
     }
 
+    // Marks the value being encoded as one whose `len` bytes of contents are read from `source`
+    // while the arguments are sent, instead of being written to the buffer
+    pub(crate) fn push_stream(
+        &mut self,
+        len: u64,
+        source: Option<Box<dyn AsyncRead + Send + Unpin>>,
+    ) {
+        self.streams.push(PgStreamedValue {
+            offset: self.len(),
+            len,
+            source: Mutex::new(source),
+        });
+    }
+
+    pub(crate) fn has_streams(&self) -> bool {
+        !self.streams.is_empty()
+    }
+
+    pub(crate) fn take_streams(&mut self) -> Vec<PgStreamedValue> {
+        std::mem::take(&mut self.streams)
+    }
+
     // Extends the inner buffer by enough space to have an OID
     // Remembers where the OID goes and type name for the OID
     pub(crate) fn patch_type_by_name(&mut self, type_name: &UStr) {
@@ -0,0 +1,67 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::connection::PgStream;
+use crate::error::Error;
+use crate::message::CancelRequest;
+use crate::{PgConnectOptions, PgConnection};
+
+/// A handle for cancelling the query currently running on a [`PgConnection`].
+///
+/// Obtained with [`PgConnection::cancel_token()`]. The token does not borrow the connection,
+/// so it can be used from another task (or the same task, between polls of a
+/// [`fetch()`][crate::Executor::fetch] stream) while a query is in progress.
+///
+/// Cancellation is best-effort: the request is sent over a new connection to the server,
+/// and if it arrives before the query completes, the query fails with an error
+/// (SQLSTATE `57014`, `query_canceled`). Otherwise it has no effect.
+///
+/// See [the Postgres manual, Section 55.2.7][PG.55.2.7] for details.
+///
+/// [PG.55.2.7]: https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS
+#[derive(Clone)]
+pub struct PgCancelToken {
+    options: Arc<PgConnectOptions>,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgCancelToken {
+    /// Ask the server to cancel the query currently running on the connection.
+    ///
+    /// This returns once the request has been sent; the server does not reply to it.
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let mut stream = PgStream::connect(&self.options).await?;
+
+        stream
+            .send(CancelRequest {
+                process_id: self.process_id,
+                secret_key: self.secret_key,
+            })
+            .await?;
+
+        // the server closes the connection after reading the request
+        stream.shutdown().await?;
+
+        Ok(())
+    }
+}
+
+impl PgConnection {
+    /// Get a token for cancelling the query currently running on this connection.
+    pub fn cancel_token(&self) -> PgCancelToken {
+        PgCancelToken {
+            options: Arc::clone(&self.connect_options),
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        }
+    }
+}
+
+impl Debug for PgCancelToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgCancelToken")
+            .field("process_id", &self.process_id)
+            .finish()
+    }
+}
@@ -8,7 +8,7 @@ use crate::type_info::{PgCustomType, PgType, PgTypeKind};
 use crate::types::Json;
 use crate::types::Oid;
 use crate::HashMap;
-use crate::{PgArguments, PgColumn, PgConnection, PgTypeInfo};
+use crate::{PgArguments, PgColumn, PgConnection, PgTypeInfo, PgValueFormat};
 use futures_core::future::BoxFuture;
 use std::fmt::Write;
 use std::sync::Arc;
@@ -183,15 +183,49 @@ impl PgConnection {
         }
     }
 
+    /// Returns the format to request for result columns of the given type.
+    ///
+    /// Binary is used wherever the server supports it, falling back to text for user-defined
+    /// types that lack a binary representation.
+    pub(crate) fn result_format(&self, ty: &PgTypeInfo) -> PgValueFormat {
+        let PgType::Custom(custom) = &ty.0 else {
+            return PgValueFormat::Binary;
+        };
+
+        match &custom.kind {
+            // these are sent using the format of the type they contain
+            PgTypeKind::Domain(inner) | PgTypeKind::Array(inner) | PgTypeKind::Range(inner) => {
+                self.result_format(inner)
+            }
+
+            PgTypeKind::Composite(fields)
+                if fields
+                    .iter()
+                    .any(|(_, ty)| self.result_format(ty) == PgValueFormat::Text) =>
+            {
+                PgValueFormat::Text
+            }
+
+            _ if self.cache_type_text_only.contains(&custom.oid) => PgValueFormat::Text,
+
+            _ => PgValueFormat::Binary,
+        }
+    }
+
     fn fetch_type_by_oid(&mut self, oid: Oid) -> BoxFuture<'_, Result<PgTypeInfo, Error>> {
         Box::pin(async move {
-            let (name, typ_type, category, relation_id, element, base_type): (String, i8, i8, Oid, Oid, Oid) = query_as(
-                "SELECT typname, typtype, typcategory, typrelid, typelem, typbasetype FROM pg_catalog.pg_type WHERE oid = $1",
+            let (name, typ_type, category, relation_id, element, base_type, has_send): (String, i8, i8, Oid, Oid, Oid, bool) = query_as(
+                "SELECT typname, typtype, typcategory, typrelid, typelem, typbasetype, typsend <> 0 FROM pg_catalog.pg_type WHERE oid = $1",
             )
             .bind(oid)
             .fetch_one(&mut *self)
             .await?;
 
+            // without a send function the server cannot return this type in the binary format
+            if !has_send {
+                self.cache_type_text_only.insert(oid);
+            }
+
             let typ_type = TypType::try_from(typ_type as u8);
             let category = TypCategory::try_from(category as u8);
 
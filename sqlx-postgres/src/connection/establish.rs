@@ -1,6 +1,8 @@
 use crate::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
 
-use crate::common::StatementCache;
+use crate::common::{LatencyEwma, StatementCache};
 use crate::connection::{sasl, stream::PgStream};
 use crate::error::Error;
 use crate::io::Decode;
@@ -15,9 +17,6 @@ use crate::{PgConnectOptions, PgConnection};
 
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
-        // Upgrade to TLS if we were asked to and the server supports it
-        let mut stream = PgStream::connect(options).await?;
-
         // To begin a session, a frontend opens a connection to the server
         // and sends a startup message.
 
@@ -44,6 +43,41 @@ impl PgConnection {
             params.push(("options", options));
         }
 
+        if options.read_only {
+            params.push(("default_transaction_read_only", "on"));
+        }
+
+        let search_path = options
+            .default_schema
+            .as_ref()
+            .map(|schema| format!("\"{}\", public", schema.replace('"', "\"\"")));
+
+        if let Some(ref search_path) = search_path {
+            params.push(("search_path", search_path));
+        }
+
+        for (key, value) in &options.startup_parameters {
+            if key == "user" || key == "database" {
+                return Err(Error::Configuration(
+                    format!("startup parameter {key:?} must be set with its own option").into(),
+                ));
+            }
+
+            params.push((key, value));
+        }
+
+        // the startup message is a list of null-terminated strings
+        for (key, value) in &params {
+            if key.contains('\0') || value.contains('\0') {
+                return Err(Error::Configuration(
+                    format!("startup parameter {key:?} contains a null byte").into(),
+                ));
+            }
+        }
+
+        // Upgrade to TLS if we were asked to and the server supports it
+        let mut stream = PgStream::connect(options).await?;
+
         stream
             .send(Startup {
                 username: Some(&options.username),
@@ -139,14 +173,20 @@ impl PgConnection {
             stream,
             process_id,
             secret_key,
+            connect_options: Arc::new(options.clone()),
             transaction_status,
             transaction_depth: 0,
             pending_ready_for_query_count: 0,
+            results_pending: false,
             next_statement_id: Oid(1),
             cache_statement: StatementCache::new(options.statement_cache_capacity),
+            dropped_statements: Arc::default(),
             cache_type_oid: HashMap::new(),
             cache_type_info: HashMap::new(),
+            cache_type_text_only: HashSet::new(),
             log_settings: options.log_settings.clone(),
+            latency: LatencyEwma::default(),
+            capture_query_plans: options.capture_query_plans,
         })
     }
 }
@@ -1,12 +1,15 @@
+use crate::arguments::PgStreamedValue;
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{run_until, stream_until, Execute, Executor};
+use crate::io::{AsyncRead, AsyncReadExt, Encode};
 use crate::logger::QueryLogger;
 use crate::message::{
     self, Bind, Close, CommandComplete, DataRow, MessageFormat, ParameterDescription, Parse, Query,
-    RowDescription,
+    RowDescription, TransactionStatus,
 };
-use crate::statement::PgStatementMetadata;
+use crate::pipeline::PipelinedQuery;
+use crate::statement::{PgPreparedStatement, PgStatementMetadata};
 use crate::type_info::PgType;
 use crate::types::Oid;
 use crate::{
@@ -18,7 +21,9 @@ use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
 use sqlx_core::Either;
-use std::{borrow::Cow, sync::Arc};
+use std::sync::PoisonError;
+use std::time::Instant;
+use std::{borrow::Cow, io, sync::Arc};
 
 async fn prepare(
     conn: &mut PgConnection,
@@ -125,6 +130,34 @@ async fn recv_desc_rows(conn: &mut PgConnection) -> Result<Option<RowDescription
     Ok(rows)
 }
 
+// the types we sent with `Parse` decide how the server reads untyped parameters (e.g.
+// `SELECT $1`); a statement prepared for other types would misread the arguments
+fn parameters_match(ours: &[PgTypeInfo], theirs: &[PgTypeInfo]) -> bool {
+    ours.iter()
+        .zip(theirs)
+        .all(|(ours, theirs)| match (ours.oid(), theirs.oid()) {
+            (Some(ours), Some(theirs)) => ours == theirs,
+            _ => true,
+        })
+}
+
+fn invalid_input(message: impl Into<String>) -> Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message.into()).into()
+}
+
+// whether `query` starts with one of the statements that `capture_query_plans` explains
+fn is_explainable(query: &str) -> bool {
+    let keyword = query
+        .trim_start()
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default();
+
+    ["SELECT", "INSERT", "UPDATE", "DELETE", "VALUES"]
+        .iter()
+        .any(|explainable| keyword.eq_ignore_ascii_case(explainable))
+}
+
 impl PgConnection {
     // wait for CloseComplete to indicate a statement was closed
     pub(super) async fn wait_for_close_complete(&mut self, mut count: usize) -> Result<(), Error> {
@@ -160,7 +193,7 @@ impl PgConnection {
         self.pending_ready_for_query_count += 1;
     }
 
-    async fn get_or_prepare<'a>(
+    pub(crate) async fn get_or_prepare<'a>(
         &mut self,
         sql: &str,
         parameters: &[PgTypeInfo],
@@ -171,7 +204,10 @@ impl PgConnection {
         metadata: Option<Arc<PgStatementMetadata>>,
     ) -> Result<(Oid, Arc<PgStatementMetadata>), Error> {
         if let Some(statement) = self.cache_statement.get_mut(sql) {
-            return Ok((*statement).clone());
+            // otherwise, it is prepared again and replaces the cached one
+            if parameters_match(parameters, &statement.1.parameters) {
+                return Ok((*statement).clone());
+            }
         }
 
         let statement = prepare(self, sql, parameters, metadata).await?;
@@ -191,51 +227,361 @@ impl PgConnection {
         Ok(statement)
     }
 
+    // the statement `prepare()` created for a query, if that was on this connection and it was
+    // prepared for the types of the arguments
+    fn owned_statement(
+        &self,
+        prepared: Option<&Arc<PgPreparedStatement>>,
+        metadata: Option<&Arc<PgStatementMetadata>>,
+        parameters: &[PgTypeInfo],
+    ) -> Option<(Oid, Arc<PgStatementMetadata>)> {
+        let prepared =
+            prepared.filter(|prepared| Arc::ptr_eq(&prepared.dropped, &self.dropped_statements))?;
+        let metadata =
+            metadata.filter(|metadata| parameters_match(parameters, &metadata.parameters))?;
+
+        Some((prepared.id, Arc::clone(metadata)))
+    }
+
+    // close a statement that did not end up in the cache (because it is disabled), as nothing
+    // else would until the connection closes
+    async fn close_uncached_statement(&mut self, sql: &str, statement: Oid) -> Result<(), Error> {
+        if self.cache_statement.get_mut(sql).map(|cached| cached.0) == Some(statement) {
+            return Ok(());
+        }
+
+        self.stream.write(Close::Statement(statement));
+        self.write_sync();
+
+        self.stream.flush().await?;
+
+        self.wait_for_close_complete(1).await?;
+        self.recv_ready_for_query().await?;
+
+        Ok(())
+    }
+
+    /// Run `query` through `EXPLAIN (ANALYZE, FORMAT JSON)` and roll back its effects.
+    ///
+    /// Returns `None` if the query is not a plain DML statement or could not be explained.
+    async fn capture_query_plan(
+        &mut self,
+        query: &str,
+        arguments: Option<&PgArguments>,
+    ) -> Result<Option<Arc<str>>, Error> {
+        // anything else (DDL, `CREATE TABLE .. AS`, `EXECUTE`, etc.) is not worth running twice
+        if !is_explainable(query) {
+            return Ok(None);
+        }
+
+        // a quoted name, so that it cannot be mistaken for a savepoint of the application
+        let (begin, rollback) = match self.transaction_status {
+            TransactionStatus::Idle => ("BEGIN", "ROLLBACK"),
+            TransactionStatus::Transaction => (
+                r#"SAVEPOINT "sqlx explain""#,
+                r#"ROLLBACK TO SAVEPOINT "sqlx explain"; RELEASE SAVEPOINT "sqlx explain""#,
+            ),
+            // nothing can be run until the transaction is rolled back
+            TransactionStatus::Error => return Ok(None),
+        };
+
+        self.queue_simple_query(begin);
+        self.wait_until_ready().await?;
+
+        let explain = format!("EXPLAIN (ANALYZE, FORMAT JSON) {query}");
+        let types = arguments.map_or(&[][..], |arguments| &arguments.types);
+
+        let plan = match self.run_explain(&explain, types, arguments).await {
+            Ok(plan) => Some(plan),
+            // the query is not explainable; execute it without a plan
+            Err(Error::Database(_)) => None,
+            Err(error) => return Err(error),
+        };
+
+        self.queue_simple_query(rollback);
+        self.wait_until_ready().await?;
+
+        Ok(plan)
+    }
+
+    async fn run_explain(
+        &mut self,
+        explain: &str,
+        types: &[PgTypeInfo],
+        arguments: Option<&PgArguments>,
+    ) -> Result<Arc<str>, Error> {
+        let (statement, _) = prepare(self, explain, types, None).await?;
+
+        self.stream.write(Bind {
+            portal: None,
+            statement,
+            formats: &[PgValueFormat::Binary],
+            num_params: types.len() as i16,
+            params: arguments.map_or(&[][..], |arguments| &*arguments.buffer),
+            result_formats: &[PgValueFormat::Text],
+        });
+
+        self.stream.write(message::Execute {
+            portal: None,
+            limit: 0,
+        });
+
+        self.write_sync();
+
+        // close the statement in its own sync so it happens even if the query fails
+        self.stream.write(Close::Statement(statement));
+        self.write_sync();
+
+        self.stream.flush().await?;
+
+        let mut plan = String::new();
+
+        loop {
+            let message = self.stream.recv().await?;
+
+            match message.format {
+                MessageFormat::DataRow => {
+                    let data: DataRow = message.decode()?;
+
+                    if let Some(line) = data.get(0) {
+                        plan.push_str(std::str::from_utf8(line).map_err(Error::protocol)?);
+                    }
+                }
+
+                MessageFormat::ReadyForQuery => {
+                    self.handle_ready_for_query(message)?;
+                    break;
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(plan.into())
+    }
+
+    // Writes a `Bind` whose parameters include streamed values, reading their contents from
+    // their sources straight into the write buffer and flushing it as it fills up.
+    async fn write_streamed_bind(
+        &mut self,
+        bind: Bind<'_>,
+        streams: Vec<PgStreamedValue>,
+    ) -> Result<(), Error> {
+        let mut pending = streams.into_iter().peekable();
+        let mut sources = Vec::new();
+        let mut offset = 0;
+
+        // only values bound on their own can be streamed, as the contents of one inside an array
+        // or record would be missing from the length of the enclosing value
+        for _ in 0..bind.num_params {
+            let len = i32::from_be_bytes([
+                bind.params[offset],
+                bind.params[offset + 1],
+                bind.params[offset + 2],
+                bind.params[offset + 3],
+            ]);
+
+            offset += 4;
+
+            if let Some(stream) = pending.next_if(|stream| stream.offset == offset) {
+                let source = stream
+                    .source
+                    .into_inner()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .ok_or_else(|| invalid_input("PgByteaStream was already bound to a query"))?;
+
+                sources.push((offset, stream.len, source));
+            } else if len > 0 {
+                offset += len as usize;
+            }
+        }
+
+        if pending.next().is_some() {
+            return Err(invalid_input(
+                "PgByteaStream cannot be bound inside an array or record",
+            ));
+        }
+
+        let mut message = Vec::new();
+        bind.encode(&mut message);
+
+        let len = sources
+            .iter()
+            .fold((message.len() - 1) as u64, |len, (_, streamed, _)| {
+                len.saturating_add(*streamed)
+            });
+
+        let len = i32::try_from(len)
+            .map_err(|_| invalid_input(format!("bind message of {len} bytes is too long")))?;
+
+        message[1..5].copy_from_slice(&len.to_be_bytes());
+
+        // nothing has been sent so far, but past this point a failure leaves the message
+        // incomplete and the server waiting for the rest of it
+        let params_offset = bind.params_offset();
+        let mut written = 0;
+
+        for (offset, len, source) in sources {
+            let offset = params_offset + offset;
+
+            self.stream
+                .write_buffer_mut()
+                .put_slice(&message[written..offset]);
+
+            written = offset;
+
+            if let Err(error) = self.write_streamed_value(source, len).await {
+                self.stream.desynced = true;
+                return Err(error);
+            }
+        }
+
+        self.stream
+            .write_buffer_mut()
+            .put_slice(&message[written..]);
+
+        Ok(())
+    }
+
+    async fn write_streamed_value(
+        &mut self,
+        source: Box<dyn AsyncRead + Send + Unpin>,
+        len: u64,
+    ) -> Result<(), Error> {
+        let mut source = source.take(len);
+        let mut remaining = len;
+
+        while remaining > 0 {
+            // start from an empty buffer so a read has room for more than a few bytes
+            self.stream.flush().await?;
+
+            let buf = self.stream.write_buffer_mut();
+
+            let read = source.read(buf.init_remaining_mut()).await?;
+
+            if read == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("PgByteaStream ended {remaining} bytes short of its length of {len}"),
+                )
+                .into());
+            }
+
+            buf.advance(read);
+            remaining -= read as u64;
+        }
+
+        // the value would otherwise be silently truncated
+        if source.into_inner().read(&mut [0]).await? != 0 {
+            return Err(invalid_input(format!(
+                "PgByteaStream has more than its length of {len} bytes"
+            )));
+        }
+
+        Ok(())
+    }
+
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         query: &'q str,
         arguments: Option<PgArguments>,
-        limit: u8,
+        limit: u32,
         persistent: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
+        prepared: Option<Arc<PgPreparedStatement>>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
-        let mut logger = QueryLogger::new(query, self.log_settings.clone());
+        let mut logger = QueryLogger::new(
+            query,
+            "postgresql",
+            Some(self.process_id),
+            self.log_settings.clone(),
+        );
 
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
 
         let mut metadata: Arc<PgStatementMetadata>;
+        let mut query_plan: Option<Arc<str>> = None;
+
+        let formats = if let Some(mut arguments) = arguments {
+            let owned =
+                self.owned_statement(prepared.as_ref(), metadata_opt.as_ref(), &arguments.types);
+            let is_owned = owned.is_some();
 
-        let format = if let Some(mut arguments) = arguments {
             // prepare the statement if this our first time executing it
             // always return the statement ID here
-            let (statement, metadata_) = self
-                .get_or_prepare(query, &arguments.types, persistent, metadata_opt)
-                .await?;
+            let (statement, metadata_) = match owned {
+                Some(owned) => owned,
+                None => {
+                    self.get_or_prepare(query, &arguments.types, persistent, metadata_opt)
+                        .await?
+                }
+            };
 
             metadata = metadata_;
 
+            // a statement that did not end up in the cache (not persistent, or the cache is
+            // disabled) would otherwise stay prepared on the server until the connection closes
+            let close_statement = !is_owned
+                && self.cache_statement.get_mut(query).map(|cached| cached.0) != Some(statement);
+
+            // the server would reject a mismatched `Bind` anyway, but patching the arguments
+            // below assumes that each one has a matching parameter
+            if arguments.types.len() != metadata.parameters.len() {
+                return Err(err_protocol!(
+                    "expected {} arguments for query but {} were bound",
+                    metadata.parameters.len(),
+                    arguments.types.len()
+                ));
+            }
+
             // patch holes created during encoding
             arguments.apply_patches(self, &metadata.parameters).await?;
 
             // consume messages till `ReadyForQuery` before bind and execute
             self.wait_until_ready().await?;
 
+            // explaining the query would consume the sources of any streamed values
+            if self.capture_query_plans && !arguments.buffer.has_streams() {
+                query_plan = self.capture_query_plan(query, Some(&arguments)).await?;
+            }
+
+            // prefer binary results, but some types can only be sent as text
+            let result_formats: Vec<_> = metadata
+                .columns
+                .iter()
+                .map(|column| self.result_format(&column.type_info))
+                .collect();
+
+            let formats: Arc<[PgValueFormat]> = if result_formats.contains(&PgValueFormat::Text) {
+                result_formats.into()
+            } else {
+                Arc::new([PgValueFormat::Binary])
+            };
+
+            let streams = arguments.buffer.take_streams();
+
             // bind to attach the arguments to the statement and create a portal
-            self.stream.write(Bind {
+            let bind = Bind {
                 portal: None,
                 statement,
                 formats: &[PgValueFormat::Binary],
                 num_params: arguments.types.len() as i16,
                 params: &*arguments.buffer,
-                result_formats: &[PgValueFormat::Binary],
-            });
+                result_formats: &formats,
+            };
+
+            if streams.is_empty() {
+                self.stream.write(bind);
+            } else {
+                self.write_streamed_bind(bind, streams).await?;
+            }
 
             // executes the portal up to the passed limit
             // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
             self.stream.write(message::Execute {
                 portal: None,
-                limit: limit.into(),
+                limit,
             });
             // From https://www.postgresql.org/docs/current/protocol-flow.html:
             //
@@ -255,9 +601,19 @@ impl PgConnection {
             // termed batching might suit this.
             self.write_sync();
 
-            // prepared statements are binary
-            PgValueFormat::Binary
+            if close_statement {
+                // close the statement in its own sync so it happens even if the query fails;
+                // the extra `ReadyForQuery` is consumed by the next `wait_until_ready()`
+                self.stream.write(Close::Statement(statement));
+                self.write_sync();
+            }
+
+            formats
         } else {
+            if self.capture_query_plans {
+                query_plan = self.capture_query_plan(query, None).await?;
+            }
+
             // Query will trigger a ReadyForQuery
             self.stream.write(Query(query));
             self.pending_ready_for_query_count += 1;
@@ -266,14 +622,28 @@ impl PgConnection {
             metadata = Arc::new(PgStatementMetadata::default());
 
             // and unprepared statements are text
-            PgValueFormat::Text
+            Arc::new([PgValueFormat::Text])
         };
 
         self.stream.flush().await?;
+        let mut sent = Some(Instant::now());
+        self.results_pending = true;
 
         Ok(try_stream! {
             loop {
-                let message = self.stream.recv().await?;
+                let message = match self.stream.recv().await {
+                    Ok(message) => message,
+
+                    // the query is over, the server only has `ReadyForQuery` left to send
+                    Err(error) => {
+                        self.results_pending = false;
+                        return Err(error);
+                    }
+                };
+
+                if let Some(sent) = sent.take() {
+                    self.latency.record(sent.elapsed());
+                }
 
                 match message.format {
                     MessageFormat::BindComplete
@@ -298,6 +668,7 @@ impl PgConnection {
                         logger.increase_rows_affected(rows_affected);
                         r#yield!(Either::Left(PgQueryResult {
                             rows_affected,
+                            query_plan: query_plan.clone(),
                         }));
                     }
 
@@ -330,7 +701,7 @@ impl PgConnection {
                         let data: DataRow = message.decode()?;
                         let row = PgRow {
                             data,
-                            format,
+                            formats: Arc::clone(&formats),
                             metadata: Arc::clone(&metadata),
                         };
 
@@ -339,15 +710,13 @@ impl PgConnection {
 
                     MessageFormat::ReadyForQuery => {
                         // processing of the query string is complete
+                        self.results_pending = false;
                         self.handle_ready_for_query(message)?;
                         break;
                     }
 
                     _ => {
-                        return Err(err_protocol!(
-                            "execute: unexpected message: {:?}",
-                            message.format
-                        ));
+                        return Err(self.handle_desync("execute", message.format).await);
                     }
                 }
             }
@@ -355,6 +724,309 @@ impl PgConnection {
             Ok(())
         })
     }
+
+    /// Execute a simple (unprepared) query and discard any rows it returns without decoding them.
+    ///
+    /// This is useful for statements like `INSERT ... RETURNING` or `SELECT pg_notify(..)`
+    /// where the results are not needed. Every message up to the final `ReadyForQuery` is read,
+    /// so the connection is immediately ready for the next query.
+    ///
+    /// The query string may contain multiple statements; the returned
+    /// [`PgQueryResult`] covers all of them.
+    pub async fn execute_drain(&mut self, query: &str) -> Result<PgQueryResult, Error> {
+        let mut logger = QueryLogger::new(
+            query,
+            "postgresql",
+            Some(self.process_id),
+            self.log_settings.clone(),
+        );
+
+        self.wait_until_ready().await?;
+
+        self.stream.write(Query(query));
+        self.pending_ready_for_query_count += 1;
+        self.stream.flush().await?;
+
+        let mut rows_affected = 0;
+
+        loop {
+            let message = self.stream.recv().await?;
+
+            match message.format {
+                MessageFormat::RowDescription | MessageFormat::EmptyQueryResponse => {}
+
+                MessageFormat::DataRow => {
+                    logger.increment_rows_returned();
+                }
+
+                MessageFormat::CommandComplete => {
+                    let cc: CommandComplete = message.decode()?;
+
+                    logger.increase_rows_affected(cc.rows_affected());
+                    rows_affected += cc.rows_affected();
+                }
+
+                MessageFormat::ReadyForQuery => {
+                    self.handle_ready_for_query(message)?;
+                    break;
+                }
+
+                _ => {
+                    return Err(self.handle_desync("execute_drain", message.format).await);
+                }
+            }
+        }
+
+        Ok(PgQueryResult {
+            rows_affected,
+            query_plan: None,
+        })
+    }
+
+    pub(crate) async fn run_pipeline(
+        &mut self,
+        mut queries: Vec<PipelinedQuery<'_>>,
+    ) -> Result<Vec<PgQueryResult>, Error> {
+        self.wait_until_ready().await?;
+
+        // every statement is prepared before anything is written, as preparing one takes a
+        // round trip of its own
+        let mut statements = Vec::with_capacity(queries.len());
+
+        for query in &mut queries {
+            let owned = self.owned_statement(
+                query.prepared.as_ref(),
+                query.metadata.as_ref(),
+                &query.arguments.types,
+            );
+            let is_owned = owned.is_some();
+
+            let (statement, metadata) = match owned {
+                Some(owned) => owned,
+                None => {
+                    self.get_or_prepare(
+                        query.sql,
+                        &query.arguments.types,
+                        query.persistent,
+                        query.metadata.take(),
+                    )
+                    .await?
+                }
+            };
+
+            if query.arguments.types.len() != metadata.parameters.len() {
+                return Err(err_protocol!(
+                    "expected {} arguments for query but {} were bound",
+                    metadata.parameters.len(),
+                    query.arguments.types.len()
+                ));
+            }
+
+            query
+                .arguments
+                .apply_patches(self, &metadata.parameters)
+                .await?;
+
+            let cached = self
+                .cache_statement
+                .get_mut(query.sql)
+                .map(|cached| cached.0)
+                == Some(statement);
+
+            statements.push((statement, metadata, cached, is_owned));
+        }
+
+        let mut close_statements = Vec::new();
+
+        for (query, (statement, metadata, cached, is_owned)) in queries.iter().zip(&mut statements)
+        {
+            // an owned statement is never evicted, and is closed once its last owner is dropped
+            if *is_owned
+                || self
+                    .cache_statement
+                    .get_mut(query.sql)
+                    .map(|cached| cached.0)
+                    == Some(*statement)
+            {
+                continue;
+            }
+
+            if *cached {
+                // preparing a later query evicted this one from a full cache, which closed it
+                *statement = prepare(
+                    self,
+                    query.sql,
+                    &query.arguments.types,
+                    Some(metadata.clone()),
+                )
+                .await?
+                .0;
+            }
+
+            // like in `run()`, a statement that is not cached is closed once executed
+            close_statements.push(*statement);
+        }
+
+        self.wait_until_ready().await?;
+
+        let mut loggers = Vec::with_capacity(queries.len());
+
+        for (query, (statement, ..)) in queries.iter_mut().zip(&statements) {
+            loggers.push(QueryLogger::new(
+                query.sql,
+                "postgresql",
+                Some(self.process_id),
+                self.log_settings.clone(),
+            ));
+
+            let streams = query.arguments.buffer.take_streams();
+
+            let bind = Bind {
+                portal: None,
+                statement: *statement,
+                formats: &[PgValueFormat::Binary],
+                num_params: query.arguments.types.len() as i16,
+                params: &*query.arguments.buffer,
+                // the rows are discarded, and every type can be sent as text
+                result_formats: &[PgValueFormat::Text],
+            };
+
+            if streams.is_empty() {
+                self.stream.write(bind);
+            } else {
+                self.write_streamed_bind(bind, streams).await?;
+            }
+
+            self.stream.write(message::Execute {
+                portal: None,
+                limit: 0,
+            });
+        }
+
+        self.stream.write(message::Close::Portal(None));
+
+        // a single [Sync] for every query is what saves the round trips; it also makes the
+        // server run them in one implicit transaction and skip the rest once one fails
+        self.write_sync();
+
+        if !close_statements.is_empty() {
+            for statement in close_statements {
+                self.stream.write(Close::Statement(statement));
+            }
+
+            self.write_sync();
+        }
+
+        self.stream.flush().await?;
+        let mut sent = Some(Instant::now());
+
+        let mut results = Vec::with_capacity(queries.len());
+
+        loop {
+            let message = self.stream.recv().await?;
+
+            if let Some(sent) = sent.take() {
+                self.latency.record(sent.elapsed());
+            }
+
+            match message.format {
+                MessageFormat::BindComplete
+                | MessageFormat::CloseComplete
+                | MessageFormat::RowDescription => {}
+
+                // an empty query string completes without a command tag
+                MessageFormat::EmptyQueryResponse => results.push(PgQueryResult::default()),
+
+                MessageFormat::DataRow => {
+                    if let Some(logger) = loggers.get_mut(results.len()) {
+                        logger.increment_rows_returned();
+                    }
+                }
+
+                MessageFormat::CommandComplete => {
+                    let cc: CommandComplete = message.decode()?;
+
+                    if let Some(logger) = loggers.get_mut(results.len()) {
+                        logger.increase_rows_affected(cc.rows_affected());
+                    }
+
+                    results.push(PgQueryResult {
+                        rows_affected: cc.rows_affected(),
+                        query_plan: None,
+                    });
+                }
+
+                MessageFormat::ReadyForQuery => {
+                    self.handle_ready_for_query(message)?;
+                    break;
+                }
+
+                _ => {
+                    return Err(self.handle_desync("pipeline", message.format).await);
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+impl PgConnection {
+    /// Execute a query and return at most its first `limit` rows.
+    ///
+    /// Unlike adding a `LIMIT` to the query, this asks the server to stop executing the query
+    /// once `limit` rows have been sent, using the row limit of the extended query protocol; no
+    /// further rows are produced or sent, and the suspended portal is then closed. The query is
+    /// always executed as a prepared statement, even without arguments, and must be a single
+    /// statement.
+    ///
+    /// A `limit` of `0` returns no rows without executing the query.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let rows = conn
+    ///     .fetch_limited(sqlx::query("SELECT * FROM tweet ORDER BY id"), 10)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_limited<'q, E>(
+        &mut self,
+        mut query: E,
+        limit: u32,
+    ) -> Result<Vec<PgRow>, Error>
+    where
+        E: Execute<'q, Postgres> + 'q,
+    {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let sql = query.sql();
+        let metadata = query.statement().map(|s| &s.metadata).cloned();
+        let prepared = query.statement().and_then(|s| s.prepared.clone());
+        // the row limit only exists in the extended protocol
+        let arguments = query.take_arguments().unwrap_or_default();
+        let persistent = query.persistent();
+
+        run_until(query.deadline(), async move {
+            let s = self
+                .run(sql, Some(arguments), limit, persistent, metadata, prepared)
+                .await?;
+            pin_mut!(s);
+
+            let mut rows = Vec::new();
+
+            while let Some(v) = s.try_next().await? {
+                if let Either::Right(row) = v {
+                    rows.push(row);
+                }
+            }
+
+            Ok(rows)
+        })
+        .await
+    }
 }
 
 impl<'c> Executor<'c> for &'c mut PgConnection {
@@ -370,19 +1042,24 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
     {
         let sql = query.sql();
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let prepared = query.statement().and_then(|s| s.prepared.clone());
         let arguments = query.take_arguments();
         let persistent = query.persistent();
+        let deadline = query.deadline();
 
-        Box::pin(try_stream! {
-            let s = self.run(sql, arguments, 0, persistent, metadata).await?;
-            pin_mut!(s);
+        stream_until(
+            deadline,
+            Box::pin(try_stream! {
+                let s = self.run(sql, arguments, 0, persistent, metadata, prepared).await?;
+                pin_mut!(s);
 
-            while let Some(v) = s.try_next().await? {
-                r#yield!(v);
-            }
+                while let Some(v) = s.try_next().await? {
+                    r#yield!(v);
+                }
 
-            Ok(())
-        })
+                Ok(())
+            }),
+        )
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -395,21 +1072,33 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
     {
         let sql = query.sql();
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let prepared = query.statement().and_then(|s| s.prepared.clone());
         let arguments = query.take_arguments();
         let persistent = query.persistent();
+        let deadline = query.deadline();
 
-        Box::pin(async move {
-            let s = self.run(sql, arguments, 1, persistent, metadata).await?;
+        Box::pin(run_until(deadline, async move {
+            // fetch one more row than needed to detect if there are too many
+            let s = self
+                .run(sql, arguments, 2, persistent, metadata, prepared)
+                .await?;
             pin_mut!(s);
 
+            let mut row = None;
+
             while let Some(s) = s.try_next().await? {
                 if let Either::Right(r) = s {
-                    return Ok(Some(r));
+                    if row.is_some() {
+                        // the rest of the response is discarded on the next use of the connection
+                        return Err(Error::TooManyRows);
+                    }
+
+                    row = Some(r);
                 }
             }
 
-            Ok(None)
-        })
+            Ok(row)
+        }))
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -423,11 +1112,17 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         Box::pin(async move {
             self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, parameters, true, None).await?;
+            // prepared apart from the statement cache, so nothing but dropping the returned
+            // statement closes it
+            let (id, metadata) = prepare(self, sql, parameters, None).await?;
 
             Ok(PgStatement {
                 sql: Cow::Borrowed(sql),
                 metadata,
+                prepared: Some(Arc::new(PgPreparedStatement {
+                    id,
+                    dropped: Arc::clone(&self.dropped_statements),
+                })),
             })
         })
     }
@@ -446,6 +1141,8 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
 
             let nullable = self.get_nullable_for_columns(stmt_id, &metadata).await?;
 
+            self.close_uncached_statement(sql, stmt_id).await?;
+
             Ok(Describe {
                 columns: metadata.columns.clone(),
                 nullable,
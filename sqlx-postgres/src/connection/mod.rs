@@ -1,11 +1,15 @@
+use std::collections::HashSet;
 use std::fmt::{self, Debug, Formatter};
-use std::sync::Arc;
+use std::mem;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
 use crate::HashMap;
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 
-use crate::common::StatementCache;
+use crate::common::{LatencyEwma, StatementCache};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::io::Decode;
@@ -13,9 +17,9 @@ use crate::message::{
     Close, Message, MessageFormat, Query, ReadyForQuery, Terminate, TransactionStatus,
 };
 use crate::statement::PgStatementMetadata;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use crate::types::Oid;
-use crate::{PgConnectOptions, PgTypeInfo, Postgres};
+use crate::{PgConnectOptions, PgDatabaseError, PgSeverity, PgTypeInfo, Postgres};
 
 pub(crate) use sqlx_core::connection::*;
 
@@ -37,13 +41,15 @@ pub struct PgConnection {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
-    process_id: u32,
+    pub(crate) process_id: u32,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
-    secret_key: u32,
+    pub(crate) secret_key: u32,
+
+    // options this connection was established with
+    // used to open the connection that cancel requests are sent on
+    pub(crate) connect_options: Arc<PgConnectOptions>,
 
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
@@ -52,18 +58,34 @@ pub struct PgConnection {
     // cache statement by query string to the id and columns
     cache_statement: StatementCache<(Oid, Arc<PgStatementMetadata>)>,
 
+    // statements created by `prepare()` that are no longer referred to, to be closed
+    pub(crate) dropped_statements: Arc<Mutex<Vec<Oid>>>,
+
     // cache user-defined types by id <-> info
     cache_type_info: HashMap<Oid, PgTypeInfo>,
     cache_type_oid: HashMap<UStr, Oid>,
 
+    // user-defined types that the server can only send in the text format
+    cache_type_text_only: HashSet<Oid>,
+
     // number of ReadyForQuery messages that we are currently expecting
     pub(crate) pending_ready_for_query_count: usize,
 
+    // set while a stream reads the results of a query; still set on the next use of the
+    // connection if the stream was dropped before reaching the end
+    pub(crate) results_pending: bool,
+
     // current transaction status
     transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
 
     log_settings: LogSettings,
+
+    // observed round-trip latency
+    pub(crate) latency: LatencyEwma,
+
+    // run each query through `EXPLAIN ANALYZE` first and attach the plan to its results
+    capture_query_plans: bool,
 }
 
 impl PgConnection {
@@ -72,14 +94,76 @@ impl PgConnection {
         self.stream.server_version_num
     }
 
+    /// An exponentially-weighted moving average of the round-trip latency to the server, or
+    /// `None` if no round trip has been measured yet.
+    ///
+    /// A sample is taken on every [`ping()`][Connection::ping] and on every query, from sending
+    /// the query to receiving the first response from the server; this includes the time the
+    /// server takes to start responding, but not the time taken to transfer or read the results.
+    /// Useful for preferring faster connections, e.g. when balancing load across replicas.
+    ///
+    /// See [`LatencyEwma`] for how query and ping samples are mixed into the average.
+    pub fn latency_ewma(&self) -> Option<Duration> {
+        self.latency.get()
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
+        if self.stream.desynced {
+            return Err(err_protocol!(
+                "connection is out of sync with the server and can no longer be used"
+            ));
+        }
+
+        let dropped = mem::take(
+            &mut *self
+                .dropped_statements
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+
+        if !dropped.is_empty() {
+            for statement in dropped {
+                self.stream.write(Close::Statement(statement));
+            }
+
+            self.write_sync();
+        }
+
         if !self.stream.write_buffer_mut().is_empty() {
             self.stream.flush().await?;
         }
 
+        // the query may still be running; stop it rather than wait for it to complete
+        if mem::take(&mut self.results_pending) && self.connect_options.cancel_on_drop {
+            if let Err(error) = self.cancel_token().cancel().await {
+                tracing::debug!(%error, "failed to cancel an abandoned query");
+            }
+        }
+
         while self.pending_ready_for_query_count > 0 {
-            let message = self.stream.recv().await?;
+            let message = match self.stream.recv().await {
+                Ok(message) => message,
+
+                // the rest of a query that was abandoned (e.g. a `fetch()` stream that was
+                // dropped after cancelling it) may still end in an error, which no longer
+                // concerns anyone; the server follows it with `ReadyForQuery` as usual
+                //
+                // a `FATAL` error is different: the server is about to close the connection
+                // (e.g. it was terminated by an administrator) and will never be ready again
+                Err(Error::Database(error))
+                    if error
+                        .try_downcast_ref::<PgDatabaseError>()
+                        .map_or(true, |error| {
+                            !matches!(error.severity(), PgSeverity::Fatal | PgSeverity::Panic)
+                        }) =>
+                {
+                    tracing::debug!(%error, "discarding error from an abandoned query");
+                    continue;
+                }
+
+                Err(error) => return Err(error),
+            };
 
             if let MessageFormat::ReadyForQuery = message.format {
                 self.handle_ready_for_query(message)?;
@@ -89,7 +173,23 @@ impl PgConnection {
         Ok(())
     }
 
-    async fn recv_ready_for_query(&mut self) -> Result<(), Error> {
+    // called when the server sent a message that makes no sense at this point in the protocol;
+    // reads up to the `ReadyForQuery` we are still owed so the server is left in a known state
+    // and then marks the connection as dead so it is closed instead of being reused
+    pub(crate) async fn handle_desync(&mut self, context: &str, format: MessageFormat) -> Error {
+        let _ = self.wait_until_ready().await;
+
+        self.stream.desynced = true;
+
+        err_protocol!(
+            "{}: unexpected message: {:?}; the connection is out of sync with the server \
+             and can no longer be used",
+            context,
+            format
+        )
+    }
+
+    pub(crate) async fn recv_ready_for_query(&mut self) -> Result<(), Error> {
         let r: ReadyForQuery = self
             .stream
             .recv_expect(MessageFormat::ReadyForQuery)
@@ -157,9 +257,19 @@ impl Connection for PgConnection {
         // self.execute("/* SQLx ping */").map_ok(|_| ()).boxed()
 
         Box::pin(async move {
+            // only the ping itself is timed, not the rest of any queries still in flight
+            let measure = self.pending_ready_for_query_count == 0;
+            let start = Instant::now();
+
             // The simplest call-and-response that's possible.
             self.write_sync();
-            self.wait_until_ready().await
+            self.wait_until_ready().await?;
+
+            if measure {
+                self.latency.record(start.elapsed());
+            }
+
+            Ok(())
         })
     }
 
@@ -170,6 +280,16 @@ impl Connection for PgConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.cache_statement.len()
     }
@@ -212,6 +332,35 @@ impl Connection for PgConnection {
     fn should_flush(&self) -> bool {
         !self.stream.write_buffer().is_empty()
     }
+
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        self.stream.is_broken() || self.stream.desynced
+    }
+
+    #[doc(hidden)]
+    fn set_poison_flag(&mut self, flag: Arc<AtomicBool>) {
+        self.stream.set_poison_flag(flag);
+    }
+
+    #[doc(hidden)]
+    fn before_release(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.ping().await?;
+
+            // a transaction opened with a raw `BEGIN` (or a leaked `Transaction`) would otherwise
+            // be inherited by whoever acquires the connection next
+            if !matches!(self.transaction_status, TransactionStatus::Idle) {
+                tracing::warn!("rolling back a transaction left open on a released connection");
+
+                self.queue_simple_query("ROLLBACK");
+                self.wait_until_ready().await?;
+                self.transaction_depth = 0;
+            }
+
+            Ok(())
+        })
+    }
 }
 
 // Implement `AsMut<Self>` so that `PgConnection` can be wrapped in
@@ -6,12 +6,12 @@ use crate::message::{
 use crate::PgConnectOptions;
 use hmac::{Hmac, Mac};
 use rand::Rng;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use std::borrow::Cow;
 use stringprep::saslprep;
 
 use base64::prelude::{Engine as _, BASE64_STANDARD};
 
-const GS2_HEADER: &str = "n,,";
 const CHANNEL_ATTR: &str = "c";
 const USERNAME_ATTR: &str = "n";
 const CLIENT_PROOF_ATTR: &str = "p";
@@ -49,17 +49,33 @@ pub(crate) async fn authenticate(
         ));
     }
 
-    // channel-binding = "c=" base64
+    // over TLS, bind the exchange to the certificate of the server (tls-server-end-point),
+    // so that it cannot be relayed by a man in the middle
+    let certificate_hash = stream
+        .peer_certificate()
+        .map(|certificate| certificate_hash(&certificate));
+
+    // gs2-header = gs2-cbind-flag "," [ authzid ] ","
+    let (gs2_header, cbind_data) = match certificate_hash {
+        Some(Some(hash)) if has_sasl_plus => ("p=tls-server-end-point,,", hash),
+
+        // binding is possible, but the server does not offer it; saying so ("y") lets the
+        // server detect an attacker who removed SCRAM-SHA-256-PLUS from its offer
+        Some(Some(_)) => ("y,,", Vec::new()),
+
+        // no TLS, or the hash for the certificate is undefined
+        _ => ("n,,", Vec::new()),
+    };
+
+    // channel-binding = "c=" base64(gs2-header [ cbind-data ])
     let mut channel_binding = format!("{CHANNEL_ATTR}=");
-    BASE64_STANDARD.encode_string(GS2_HEADER, &mut channel_binding);
+    BASE64_STANDARD.encode_string(
+        [gs2_header.as_bytes(), &cbind_data].concat(),
+        &mut channel_binding,
+    );
 
     // "n=" saslname ;; Usernames are prepared using SASLprep.
-    let username = format!("{}={}", USERNAME_ATTR, options.username);
-    let username = match saslprep(&username) {
-        Ok(v) => v,
-        // TODO(danielakhterov): Remove panic when we have proper support for configuration errors
-        Err(_) => panic!("Failed to saslprep username"),
-    };
+    let username = format!("{}={}", USERNAME_ATTR, saslname(&options.username));
 
     // nonce = "r=" c-nonce [s-nonce] ;; Second part provided by server.
     let nonce = gen_nonce();
@@ -67,12 +83,12 @@ pub(crate) async fn authenticate(
     // client-first-message-bare = [reserved-mext ","] username "," nonce ["," extensions]
     let client_first_message_bare = format!("{username},{nonce}");
 
-    let client_first_message = format!("{GS2_HEADER}{client_first_message_bare}");
+    let client_first_message = format!("{gs2_header}{client_first_message_bare}");
 
     stream
         .send(SaslInitialResponse {
             response: &client_first_message,
-            plus: false,
+            plus: !cbind_data.is_empty(),
         })
         .await?;
 
@@ -87,22 +103,6 @@ pub(crate) async fn authenticate(
         }
     };
 
-    // SaltedPassword := Hi(Normalize(password), salt, i)
-    let salted_password = hi(
-        options.password.as_deref().unwrap_or_default(),
-        &cont.salt,
-        cont.iterations,
-    )?;
-
-    // ClientKey := HMAC(SaltedPassword, "Client Key")
-    let mut mac = Hmac::<Sha256>::new_from_slice(&salted_password).map_err(Error::protocol)?;
-    mac.update(b"Client Key");
-
-    let client_key = mac.finalize().into_bytes();
-
-    // StoredKey := H(ClientKey)
-    let stored_key = Sha256::digest(&client_key);
-
     // client-final-message-without-proof
     let client_final_message_wo_proof = format!(
         "{channel_binding},r={nonce}",
@@ -118,9 +118,59 @@ pub(crate) async fn authenticate(
         client_final_message_wo_proof = client_final_message_wo_proof
     );
 
+    let (client_proof, mac) = client_proof(
+        options.password.as_deref().unwrap_or_default(),
+        &cont.salt,
+        cont.iterations,
+        &auth_message,
+    )?;
+
+    // client-final-message = client-final-message-without-proof "," proof
+    let mut client_final_message = format!("{client_final_message_wo_proof},{CLIENT_PROOF_ATTR}=");
+    BASE64_STANDARD.encode_string(client_proof, &mut client_final_message);
+
+    stream.send(SaslResponse(&client_final_message)).await?;
+
+    let data = match stream.recv_expect(MessageFormat::Authentication).await? {
+        Authentication::SaslFinal(data) => data,
+
+        auth => {
+            return Err(err_protocol!("expected SASLFinal but received {:?}", auth));
+        }
+    };
+
+    // authentication is only considered valid if this verification passes
+    mac.verify_slice(&data.verifier).map_err(Error::protocol)?;
+
+    Ok(())
+}
+
+// Computes the ClientProof for `auth_message`, along with the MAC the server's signature
+// must match.
+fn client_proof(
+    password: &str,
+    salt: &[u8],
+    iterations: u32,
+    auth_message: &str,
+) -> Result<(Vec<u8>, Hmac<Sha256>), Error> {
+    // SaltedPassword := Hi(Normalize(password), salt, i)
+    //
+    // Like the server, fall back to the raw password if it cannot be prepared
+    let password = saslprep(password).unwrap_or(Cow::Borrowed(password));
+    let salted_password = hi(&password, salt, iterations)?;
+
+    // ClientKey := HMAC(SaltedPassword, "Client Key")
+    let mut mac = Hmac::<Sha256>::new_from_slice(&salted_password).map_err(Error::protocol)?;
+    mac.update(b"Client Key");
+
+    let client_key = mac.finalize().into_bytes();
+
+    // StoredKey := H(ClientKey)
+    let stored_key = Sha256::digest(&client_key);
+
     // ClientSignature := HMAC(StoredKey, AuthMessage)
     let mut mac = Hmac::<Sha256>::new_from_slice(&stored_key).map_err(Error::protocol)?;
-    mac.update(&auth_message.as_bytes());
+    mac.update(auth_message.as_bytes());
 
     let client_signature = mac.finalize().into_bytes();
 
@@ -139,26 +189,110 @@ pub(crate) async fn authenticate(
 
     // ServerSignature := HMAC(ServerKey, AuthMessage)
     let mut mac = Hmac::<Sha256>::new_from_slice(&server_key).map_err(Error::protocol)?;
-    mac.update(&auth_message.as_bytes());
+    mac.update(auth_message.as_bytes());
 
-    // client-final-message = client-final-message-without-proof "," proof
-    let mut client_final_message = format!("{client_final_message_wo_proof},{CLIENT_PROOF_ATTR}=");
-    BASE64_STANDARD.encode_string(client_proof, &mut client_final_message);
+    Ok((client_proof, mac))
+}
 
-    stream.send(SaslResponse(&client_final_message)).await?;
+// The hash of a DER-encoded certificate for the tls-server-end-point channel binding
+// (RFC 5929): the hash function of its signature algorithm, or SHA-256 if that is MD5 or SHA-1.
+//
+// `None` if the signature algorithm does not use a single hash function (e.g. Ed25519),
+// for which the binding is undefined.
+fn certificate_hash(certificate: &[u8]) -> Option<Vec<u8>> {
+    const RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01]; // 1.2.840.113549.1.1
+    const ECDSA: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04]; // 1.2.840.10045.4
+
+    let algorithm = signature_algorithm(certificate)?;
+
+    let hash = if let Some(rsa) = algorithm.strip_prefix(RSA) {
+        match rsa {
+            // {md5,sha1,sha256}WithRSAEncryption
+            [4 | 5 | 11] => digest::<Sha256>,
+            [12] => digest::<Sha384>,
+            [13] => digest::<Sha512>,
+            [14] => digest::<Sha224>,
+            _ => return None,
+        }
+    } else if let Some(ecdsa) = algorithm.strip_prefix(ECDSA) {
+        match ecdsa {
+            // ecdsa-with-{SHA1,SHA256}
+            [1] | [3, 2] => digest::<Sha256>,
+            [3, 1] => digest::<Sha224>,
+            [3, 3] => digest::<Sha384>,
+            [3, 4] => digest::<Sha512>,
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
 
-    let data = match stream.recv_expect(MessageFormat::Authentication).await? {
-        Authentication::SaslFinal(data) => data,
+    Some(hash(certificate))
+}
 
-        auth => {
-            return Err(err_protocol!("expected SASLFinal but received {:?}", auth));
+fn digest<D: Digest>(data: &[u8]) -> Vec<u8> {
+    D::digest(data).to_vec()
+}
+
+// Certificate ::= SEQUENCE {
+//     tbsCertificate       TBSCertificate,
+//     signatureAlgorithm   AlgorithmIdentifier,
+//     signatureValue       BIT STRING }
+//
+// AlgorithmIdentifier ::= SEQUENCE {
+//     algorithm            OBJECT IDENTIFIER,
+//     parameters           ANY DEFINED BY algorithm OPTIONAL }
+fn signature_algorithm(certificate: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const OBJECT_IDENTIFIER: u8 = 0x06;
+
+    let (certificate, _) = der_element(certificate, SEQUENCE)?;
+    let (_, rest) = der_element(certificate, SEQUENCE)?;
+    let (algorithm_identifier, _) = der_element(rest, SEQUENCE)?;
+    let (algorithm, _) = der_element(algorithm_identifier, OBJECT_IDENTIFIER)?;
+
+    Some(algorithm)
+}
+
+// Splits the contents of the DER element with `tag` at the start of `der` from what follows it.
+fn der_element(der: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&actual_tag, rest) = der.split_first()?;
+
+    if actual_tag != tag {
+        return None;
+    }
+
+    let (&len, mut rest) = rest.split_first()?;
+
+    // lengths over 127 are sent as a count of big-endian bytes, then the bytes
+    let len = if len < 0x80 {
+        usize::from(len)
+    } else {
+        let count = usize::from(len & 0x7f);
+
+        if count > 4 || rest.len() < count {
+            return None;
         }
+
+        let (len, contents) = rest.split_at(count);
+        rest = contents;
+
+        len.iter()
+            .fold(0, |len, &byte| len << 8 | usize::from(byte))
     };
 
-    // authentication is only considered valid if this verification passes
-    mac.verify_slice(&data.verifier).map_err(Error::protocol)?;
+    (rest.len() >= len).then(|| rest.split_at(len))
+}
 
-    Ok(())
+// saslname = 1*(value-safe-char / "=2C" / "=3D")
+//
+// Postgres takes the user from the startup message and ignores this one, so a username
+// that cannot be prepared is sent as-is rather than failing the connection
+fn saslname(username: &str) -> String {
+    saslprep(username)
+        .unwrap_or(Cow::Borrowed(username))
+        .replace('=', "=3D")
+        .replace(',', "=2C")
 }
 
 // nonce is a sequence of random printable bytes
@@ -207,3 +341,118 @@ fn hi<'a>(s: &'a str, salt: &'a [u8], iter_count: u32) -> Result<[u8; 32], Error
 
     Ok(hi.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://datatracker.ietf.org/doc/html/rfc7677#section-3
+    const CLIENT_FIRST_MESSAGE_BARE: &str = "n=user,r=rOprNGfwEbeRWgbNEkqO";
+    const SERVER_FIRST_MESSAGE: &str =
+        "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL_MESSAGE_WO_PROOF: &str =
+        "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+
+    fn proof(password: &str) -> (String, Hmac<Sha256>) {
+        let auth_message = format!(
+            "{CLIENT_FIRST_MESSAGE_BARE},{SERVER_FIRST_MESSAGE},{CLIENT_FINAL_MESSAGE_WO_PROOF}"
+        );
+        let salt = BASE64_STANDARD.decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap();
+
+        let (client_proof, mac) = client_proof(password, &salt, 4096, &auth_message).unwrap();
+
+        (BASE64_STANDARD.encode(client_proof), mac)
+    }
+
+    #[test]
+    fn test_client_proof() {
+        let (client_proof, mac) = proof("pencil");
+
+        assert_eq!(client_proof, "dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=");
+
+        let verifier = BASE64_STANDARD
+            .decode("6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=")
+            .unwrap();
+        mac.verify_slice(&verifier).unwrap();
+    }
+
+    #[test]
+    fn test_client_proof_prepares_password() {
+        // U+00AD SOFT HYPHEN is mapped to nothing by SASLprep
+        assert_eq!(proof("pen\u{00AD}cil").0, proof("pencil").0);
+
+        // a password that cannot be prepared is used as-is
+        assert_ne!(proof("pencil\u{0007}").0, proof("pencil").0);
+    }
+
+    // a certificate with only the fields the signature algorithm is found by
+    fn certificate(algorithm: &[u8]) -> Vec<u8> {
+        let mut algorithm_identifier = vec![0x30, algorithm.len() as u8 + 4, 0x06];
+        algorithm_identifier.push(algorithm.len() as u8);
+        algorithm_identifier.extend_from_slice(algorithm);
+        algorithm_identifier.extend_from_slice(&[0x05, 0x00]);
+
+        let contents = [
+            &[0x30, 0x00][..],
+            &algorithm_identifier,
+            &[0x03, 0x01, 0x00],
+        ]
+        .concat();
+
+        [&[0x30, contents.len() as u8][..], &contents].concat()
+    }
+
+    #[test]
+    fn test_certificate_hash() {
+        const SHA1_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x05];
+        const SHA384_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+        const ECDSA_WITH_SHA512: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04];
+        const ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+
+        // MD5 and SHA-1 are replaced by SHA-256
+        let cert = certificate(SHA1_WITH_RSA);
+        assert_eq!(
+            certificate_hash(&cert),
+            Some(Sha256::digest(&cert).to_vec())
+        );
+
+        let cert = certificate(SHA384_WITH_RSA);
+        assert_eq!(
+            certificate_hash(&cert),
+            Some(Sha384::digest(&cert).to_vec())
+        );
+
+        let cert = certificate(ECDSA_WITH_SHA512);
+        assert_eq!(
+            certificate_hash(&cert),
+            Some(Sha512::digest(&cert).to_vec())
+        );
+
+        assert_eq!(certificate_hash(&certificate(ED25519)), None);
+
+        let cert = certificate(SHA384_WITH_RSA);
+        assert_eq!(certificate_hash(&cert[..cert.len() - 10]), None);
+        assert_eq!(certificate_hash(b"not a certificate"), None);
+    }
+
+    #[test]
+    fn test_der_element_long_length() {
+        let mut der = vec![0x04, 0x82, 0x01, 0x00];
+        der.extend_from_slice(&[0xaa; 0x100]);
+        der.push(0xbb);
+
+        let (contents, rest) = der_element(&der, 0x04).unwrap();
+        assert_eq!(contents, &[0xaa; 0x100][..]);
+        assert_eq!(rest, &[0xbb]);
+
+        assert_eq!(der_element(&der[..100], 0x04), None);
+        assert_eq!(der_element(&der, 0x30), None);
+    }
+
+    #[test]
+    fn test_saslname() {
+        assert_eq!(saslname("user"), "user");
+        assert_eq!(saslname("a=b,c"), "a=3Db=2Cc");
+        assert_eq!(saslname("user\u{0007}"), "user\u{0007}");
+    }
+}
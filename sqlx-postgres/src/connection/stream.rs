@@ -11,7 +11,7 @@ use crate::connection::tls::MaybeUpgradeTls;
 use crate::error::Error;
 use crate::io::{Decode, Encode};
 use crate::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
-use crate::net::{self, BufferedSocket, Socket};
+use crate::net::{self, BufferedSocket, Socket, SocketIntoBox};
 use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
@@ -36,22 +36,39 @@ pub struct PgStream {
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
+
+    // set once the server sent a message we did not expect at that point in the protocol;
+    // we no longer know where one response ends and the next begins, so the connection
+    // must not be used for another query
+    pub(crate) desynced: bool,
 }
 
 impl PgStream {
-    pub(super) async fn connect(options: &PgConnectOptions) -> Result<Self, Error> {
-        let socket_future = match options.fetch_socket() {
-            Some(ref path) => net::connect_uds(path, MaybeUpgradeTls(options)).await?,
-            None => net::connect_tcp(&options.host, options.port, MaybeUpgradeTls(options)).await?,
+    pub(crate) async fn connect(options: &PgConnectOptions) -> Result<Self, Error> {
+        let socket = match options.fetch_socket() {
+            // like libpq, we never negotiate TLS over a Unix domain socket
+            Some(ref path) => net::connect_uds(path, SocketIntoBox).await?,
+            None => {
+                net::connect_tcp(
+                    &options.host,
+                    options.port,
+                    options.tcp_keepalive,
+                    MaybeUpgradeTls(options),
+                )
+                .await?
+                .await?
+            }
         };
 
-        let socket = socket_future.await?;
+        let mut inner = BufferedSocket::new(socket);
+        inner.set_timeouts(options.read_timeout, options.write_timeout);
 
         Ok(Self {
-            inner: BufferedSocket::new(socket),
+            inner,
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
+            desynced: false,
         })
     }
 
@@ -72,6 +89,8 @@ impl PgStream {
         let message = self.recv().await?;
 
         if message.format != format {
+            self.desynced = true;
+
             return Err(err_protocol!(
                 "expecting {:?} but received {:?}",
                 format,
@@ -85,8 +104,20 @@ impl PgStream {
     pub(crate) async fn recv_unchecked(&mut self) -> Result<Message, Error> {
         // all packets in postgres start with a 5-byte header
         // this header contains the message type and the total length of the message
-        let mut header: Bytes = self.inner.read(5).await?;
+        let header: Bytes = self.inner.read(5).await?;
+
+        self.recv_contents(header).await
+    }
+
+    // Wait for the next message while no response is pending, e.g. for a notification;
+    // the read timeout doesn't apply until the message starts to arrive
+    pub(crate) async fn recv_idle(&mut self) -> Result<Message, Error> {
+        let header = self.inner.read_buffered_idle(5).await?.freeze();
+
+        self.recv_contents(header).await
+    }
 
+    async fn recv_contents(&mut self, mut header: Bytes) -> Result<Message, Error> {
         let format = MessageFormat::try_from_u8(header.get_u8())?;
         let size = (header.get_u32() - 4) as usize;
 
@@ -27,8 +27,8 @@ impl PgConnection {
     /// https://www.postgresql.org/docs/current/sql-copy.html
     ///
     /// ### Note
-    /// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the connection
-    /// will return an error the next time it is used.
+    /// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the `COPY`
+    /// will be aborted the next time the connection is used.
     pub async fn copy_in_raw(&mut self, statement: &str) -> Result<PgCopyIn<&mut Self>> {
         PgCopyIn::begin(self, statement).await
     }
@@ -79,8 +79,8 @@ pub trait PgPoolCopyExt {
     /// https://www.postgresql.org/docs/current/sql-copy.html
     ///
     /// ### Note
-    /// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the connection
-    /// will return an error the next time it is used.
+    /// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the `COPY`
+    /// will be aborted the next time the connection is used.
     fn copy_in_raw<'a>(
         &'a self,
         statement: &'a str,
@@ -132,9 +132,9 @@ impl PgPoolCopyExt for Pool<Postgres> {
 /// Created by [PgConnection::copy_in_raw] or [Pool::copy_out_raw].
 ///
 /// ### Note
-/// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the connection
-/// will return an error the next time it is used.
-#[must_use = "connection will error on next use if `.finish()` or `.abort()` is not called"]
+/// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the `COPY`
+/// will be aborted the next time the connection is used.
+#[must_use = "the `COPY` is aborted if `.finish()` is not called"]
 pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
     conn: Option<C>,
     response: CopyResponse,
@@ -142,16 +142,7 @@ pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
 
 impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
     async fn begin(mut conn: C, statement: &str) -> Result<Self> {
-        conn.wait_until_ready().await?;
-        conn.stream.send(Query(statement)).await?;
-
-        let response = match conn.stream.recv_expect(MessageFormat::CopyInResponse).await {
-            Ok(res) => res,
-            Err(e) => {
-                conn.stream.recv().await?;
-                return Err(e);
-            }
-        };
+        let response = begin_copy(&mut conn, statement, MessageFormat::CopyInResponse).await?;
 
         Ok(PgCopyIn {
             conn: Some(conn),
@@ -280,9 +271,7 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
                 match e.code() {
                     Some(Cow::Borrowed("57014")) => {
                         // postgres abort received error code
-                        conn.stream
-                            .recv_expect(MessageFormat::ReadyForQuery)
-                            .await?;
+                        conn.recv_ready_for_query().await?;
                         Ok(())
                     }
                     _ => Err(Error::Database(e)),
@@ -302,22 +291,13 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             .expect("CopyWriter::finish: conn taken illegally");
 
         conn.stream.send(CopyDone).await?;
-        let cc: CommandComplete = match conn
+        let cc: CommandComplete = conn
             .stream
             .recv_expect(MessageFormat::CommandComplete)
-            .await
-        {
-            Ok(cc) => cc,
-            Err(e) => {
-                conn.stream.recv().await?;
-                return Err(e);
-            }
-        };
-
-        conn.stream
-            .recv_expect(MessageFormat::ReadyForQuery)
             .await?;
 
+        conn.recv_ready_for_query().await?;
+
         Ok(cc.rows_affected())
     }
 }
@@ -336,13 +316,7 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
     mut conn: C,
     statement: &str,
 ) -> Result<BoxStream<'c, Result<Bytes>>> {
-    conn.wait_until_ready().await?;
-    conn.stream.send(Query(statement)).await?;
-
-    let _: CopyResponse = conn
-        .stream
-        .recv_expect(MessageFormat::CopyOutResponse)
-        .await?;
+    let _: CopyResponse = begin_copy(&mut conn, statement, MessageFormat::CopyOutResponse).await?;
 
     let stream: TryAsyncStream<'c, Bytes> = try_stream! {
         loop {
@@ -352,7 +326,7 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
                 MessageFormat::CopyDone => {
                     let _ = msg.decode::<CopyDone>()?;
                     conn.stream.recv_expect(MessageFormat::CommandComplete).await?;
-                    conn.stream.recv_expect(MessageFormat::ReadyForQuery).await?;
+                    conn.recv_ready_for_query().await?;
                     return Ok(())
                 },
                 _ => return Err(err_protocol!("unexpected message format during copy out: {:?}", msg.format))
@@ -362,3 +336,35 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
 
     Ok(Box::pin(stream))
 }
+
+async fn begin_copy(
+    conn: &mut PgConnection,
+    statement: &str,
+    expected: MessageFormat,
+) -> Result<CopyResponse> {
+    conn.wait_until_ready().await?;
+    conn.stream.send(Query(statement)).await?;
+
+    // the statement ends with `ReadyForQuery` however it goes; if the `COPY` is not seen
+    // through (it failed, was the wrong kind of statement, or was dropped part way through),
+    // the rest of its output is discarded the next time the connection is used
+    conn.pending_ready_for_query_count += 1;
+
+    let message = conn.stream.recv().await?;
+
+    if message.format != expected {
+        if message.format == MessageFormat::CopyInResponse {
+            // otherwise the server would wait for data that is never coming
+            conn.stream
+                .write(CopyFail::new("expected a `COPY ... TO STDOUT` statement"));
+        }
+
+        return Err(err_protocol!(
+            "expecting {:?} but received {:?}",
+            expected,
+            message.format
+        ));
+    }
+
+    message.decode()
+}
@@ -208,6 +208,10 @@ impl DatabaseError for PgDatabaseError {
         self.table()
     }
 
+    fn column(&self) -> Option<&str> {
+        self.column()
+    }
+
     fn kind(&self) -> ErrorKind {
         match self.code() {
             error_codes::UNIQUE_VIOLATION => ErrorKind::UniqueViolation,
@@ -7,6 +7,7 @@ use crate::executor::Executor;
 
 mod advisory_lock;
 mod arguments;
+mod cancel;
 mod column;
 mod connection;
 mod copy;
@@ -16,13 +17,16 @@ mod io;
 mod listener;
 mod message;
 mod options;
+mod pipeline;
 mod query_result;
 mod row;
 mod statement;
+mod statement_timeout;
 mod transaction;
 mod type_info;
 pub mod types;
 mod value;
+mod versioned_update;
 
 #[cfg(feature = "any")]
 pub mod any;
@@ -37,6 +41,7 @@ pub(crate) use sqlx_core::driver_prelude::*;
 
 pub use advisory_lock::{PgAdvisoryLock, PgAdvisoryLockGuard, PgAdvisoryLockKey};
 pub use arguments::{PgArgumentBuffer, PgArguments};
+pub use cancel::PgCancelToken;
 pub use column::PgColumn;
 pub use connection::PgConnection;
 pub use copy::PgCopyIn;
@@ -45,6 +50,7 @@ pub use error::{PgDatabaseError, PgErrorPosition};
 pub use listener::{PgListener, PgNotification};
 pub use message::PgSeverity;
 pub use options::{PgConnectOptions, PgSslMode};
+pub use pipeline::PgPipeline;
 pub use query_result::PgQueryResult;
 pub use row::PgRow;
 pub use statement::PgStatement;
@@ -52,6 +58,7 @@ pub use transaction::PgTransactionManager;
 pub use type_info::{PgTypeInfo, PgTypeKind};
 pub use types::PgHasArrayType;
 pub use value::{PgValue, PgValueFormat, PgValueRef};
+pub use versioned_update::{PgVersionedUpdate, PgVersionedUpdateError};
 
 /// An alias for [`Pool`][crate::pool::Pool], specialized for Postgres.
 pub type PgPool = crate::pool::Pool<Postgres>;
@@ -101,7 +101,11 @@ impl PgListener {
             .execute(&*format!(r#"LISTEN "{}""#, ident(channel)))
             .await?;
 
-        self.channels.push(channel.to_owned());
+        // `LISTEN` on a channel we already listen to is a no-op, so don't track it twice;
+        // otherwise a single `unlisten()` would leave it to be re-subscribed on reconnect
+        if !self.channels.iter().any(|c| c == channel) {
+            self.channels.push(channel.to_owned());
+        }
 
         Ok(())
     }
@@ -112,7 +116,16 @@ impl PgListener {
         channels: impl IntoIterator<Item = &str>,
     ) -> Result<(), Error> {
         let beg = self.channels.len();
-        self.channels.extend(channels.into_iter().map(|s| s.into()));
+
+        for channel in channels {
+            if !self.channels.iter().any(|c| c == channel) {
+                self.channels.push(channel.to_owned());
+            }
+        }
+
+        if self.channels.len() == beg {
+            return Ok(());
+        }
 
         let query = build_listen_all_query(&self.channels[beg..]);
         self.connection().await?.execute(&*query).await?;
@@ -247,7 +260,17 @@ impl PgListener {
         let mut close_event = (!self.ignore_close_event).then(|| self.pool.close_event());
 
         loop {
-            let next_message = self.connection().await?.stream.recv_unchecked();
+            let conn = self.connection().await?;
+
+            // while no response is pending, a notification may take arbitrarily long to arrive,
+            // so don't let the read timeout break the connection
+            let next_message = async move {
+                if conn.pending_ready_for_query_count == 0 {
+                    conn.stream.recv_idle().await
+                } else {
+                    conn.stream.recv_unchecked().await
+                }
+            };
 
             let res = if let Some(ref mut close_event) = close_event {
                 // cancels the wait and returns `Err(PoolClosed)` if the pool is closed
@@ -264,7 +287,8 @@ impl PgListener {
                 // update self state, and loop to try again.
                 Err(Error::Io(err))
                     if (err.kind() == io::ErrorKind::ConnectionAborted
-                        || err.kind() == io::ErrorKind::UnexpectedEof) =>
+                        || err.kind() == io::ErrorKind::UnexpectedEof
+                        || err.kind() == io::ErrorKind::TimedOut) =>
                 {
                     self.buffer_tx = self.connection().await?.stream.notifications.take();
                     self.connection = None;
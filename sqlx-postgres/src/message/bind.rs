@@ -42,24 +42,86 @@ impl Encode<'_> for Bind<'_> {
 
             buf.put_statement_name(self.statement);
 
-            buf.extend(&(self.formats.len() as i16).to_be_bytes());
-
-            for &format in self.formats {
-                buf.extend(&(format as i16).to_be_bytes());
-            }
+            put_formats(buf, self.formats);
 
             buf.extend(&self.num_params.to_be_bytes());
 
             buf.extend(self.params);
 
-            buf.extend(&(self.result_formats.len() as i16).to_be_bytes());
-
-            for &format in self.result_formats {
-                buf.extend(&(format as i16).to_be_bytes());
-            }
+            put_formats(buf, self.result_formats);
         });
     }
 }
 
-// TODO: Unit Test Bind
+impl Bind<'_> {
+    /// The offset of the parameter values in the encoded message.
+    pub(crate) fn params_offset(&self) -> usize {
+        let mut buf = Vec::new();
+
+        Bind {
+            params: &[],
+            result_formats: &[],
+            ..*self
+        }
+        .encode(&mut buf);
+
+        // the (empty) list of result formats follows the parameter values
+        buf.len() - 2
+    }
+}
+
+// a list of format codes that are all the same is sent as that one code,
+// which the server applies to every parameter (or result column)
+fn put_formats(buf: &mut Vec<u8>, formats: &[PgValueFormat]) {
+    let formats = match formats {
+        [first, rest @ ..] if rest.iter().all(|format| format == first) => &formats[..1],
+        _ => formats,
+    };
+
+    buf.extend(&(formats.len() as i16).to_be_bytes());
+
+    for &format in formats {
+        buf.extend(&(format as i16).to_be_bytes());
+    }
+}
+
+#[test]
+fn test_encode_bind_coalesces_uniform_formats() {
+    let mut buf = Vec::new();
+
+    Bind {
+        portal: None,
+        statement: Oid(1),
+        formats: &[PgValueFormat::Binary; 3],
+        num_params: 0,
+        params: &[],
+        result_formats: &[PgValueFormat::Binary, PgValueFormat::Text],
+    }
+    .encode(&mut buf);
+
+    assert_eq!(
+        buf,
+        b"B\0\0\0\x1a\0sqlx_s_1\0\0\x01\0\x01\0\0\0\x02\0\x01\0\0"
+    );
+}
+
+#[test]
+fn test_bind_params_offset() {
+    let params = b"\0\0\0\x04\0\0\0\x2a";
+    let bind = Bind {
+        portal: None,
+        statement: Oid(1),
+        formats: &[PgValueFormat::Binary],
+        num_params: 1,
+        params,
+        result_formats: &[PgValueFormat::Text],
+    };
+
+    let mut buf = Vec::new();
+    bind.encode(&mut buf);
+
+    let offset = bind.params_offset();
+    assert_eq!(&buf[offset..offset + params.len()], params);
+}
+
 // TODO: Benchmark Bind
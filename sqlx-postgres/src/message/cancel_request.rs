@@ -0,0 +1,33 @@
+use crate::io::Encode;
+
+// https://www.postgresql.org/docs/current/protocol-message-formats.html#PROTOCOL-MESSAGE-FORMATS-CANCELREQUEST
+
+pub struct CancelRequest {
+    pub process_id: u32,
+    pub secret_key: u32,
+}
+
+impl Encode<'_> for CancelRequest {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        buf.extend(&16_u32.to_be_bytes());
+        buf.extend(&(((1234 << 16) | 5678) as u32).to_be_bytes());
+        buf.extend(&self.process_id.to_be_bytes());
+        buf.extend(&self.secret_key.to_be_bytes());
+    }
+}
+
+#[test]
+fn test_encode_cancel_request() {
+    let mut buf = Vec::new();
+
+    CancelRequest {
+        process_id: 10182,
+        secret_key: 2303903019,
+    }
+    .encode(&mut buf);
+
+    assert_eq!(
+        buf,
+        b"\x00\x00\x00\x10\x04\xd2\x16\x2e\x00\x00\x27\xc6\x89\x52\xc5\x2b"
+    );
+}
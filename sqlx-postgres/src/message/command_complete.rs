@@ -58,6 +58,15 @@ fn test_decode_command_complete_for_update() {
     assert_eq!(cc.rows_affected(), 5);
 }
 
+#[test]
+fn test_decode_command_complete_for_delete() {
+    const DATA: &[u8] = b"DELETE 2\0";
+
+    let cc = CommandComplete::decode(Bytes::from_static(DATA)).unwrap();
+
+    assert_eq!(cc.rows_affected(), 2);
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_decode_command_complete(b: &mut test::Bencher) {
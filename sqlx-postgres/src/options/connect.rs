@@ -4,6 +4,7 @@ use crate::{PgConnectOptions, PgConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use sqlx_core::Url;
+use std::io;
 use std::time::Duration;
 
 impl ConnectOptions for PgConnectOptions {
@@ -17,7 +18,20 @@ impl ConnectOptions for PgConnectOptions {
     where
         Self::Connection: Sized,
     {
-        Box::pin(PgConnection::establish(self))
+        Box::pin(async move {
+            let mut conn = match self.connect_timeout {
+                Some(timeout) => sqlx_core::rt::timeout(timeout, PgConnection::establish(self))
+                    .await
+                    .map_err(|_| connect_timed_out())??,
+                None => PgConnection::establish(self).await?,
+            };
+
+            for sql in &self.prepare_on_connect {
+                conn.get_or_prepare(sql, &[], true, None).await?;
+            }
+
+            Ok(conn)
+        })
     }
 
     fn log_statements(mut self, level: LevelFilter) -> Self {
@@ -29,4 +43,16 @@ impl ConnectOptions for PgConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_full_statements(mut self, enabled: bool) -> Self {
+        self.log_settings.log_full_statements(enabled);
+        self
+    }
+}
+
+fn connect_timed_out() -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::TimedOut,
+        "timed out while connecting to the server",
+    ))
 }
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::env::var;
 use std::fmt::{Display, Write};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub use ssl_mode::PgSslMode;
 
@@ -41,6 +42,7 @@ mod ssl_mode;
 /// | `port` | `5432` | Port number to connect to at the server host, or socket file name extension for Unix-domain connections. |
 /// | `dbname` | `None` | The database name. |
 /// | `options` | `None` | The runtime parameters to send to the server at connection start. |
+/// | `connect_timeout` | `None` | Maximum time to wait while connecting, in seconds. Zero or negative means wait indefinitely. |
 ///
 /// The URL scheme designator can be either `postgresql://` or `postgres://`.
 /// Each of the URL parts is optional.
@@ -98,10 +100,20 @@ pub struct PgConnectOptions {
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) prepare_on_connect: Vec<String>,
+    pub(crate) capture_query_plans: bool,
+    pub(crate) cancel_on_drop: bool,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
+    pub(crate) startup_parameters: Vec<(String, String)>,
+    pub(crate) read_only: bool,
+    pub(crate) default_schema: Option<String>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) tcp_keepalive: Option<Duration>,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) write_timeout: Option<Duration>,
 }
 
 impl Default for PgConnectOptions {
@@ -164,10 +176,20 @@ impl PgConnectOptions {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
             statement_cache_capacity: 100,
+            prepare_on_connect: Vec::new(),
+            capture_query_plans: false,
+            cancel_on_drop: false,
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("2".into()),
             log_settings: Default::default(),
             options: var("PGOPTIONS").ok(),
+            startup_parameters: Vec::new(),
+            read_only: false,
+            default_schema: None,
+            connect_timeout: None,
+            tcp_keepalive: None,
+            read_timeout: None,
+            write_timeout: None,
         }
     }
 
@@ -238,6 +260,12 @@ impl PgConnectOptions {
     /// Sets a custom path to a directory containing a unix domain socket,
     /// switching the connection method from TCP to the corresponding socket.
     ///
+    /// The socket file within the directory is named after the [port][Self::port],
+    /// e.g. `/var/run/postgresql/.s.PGSQL.5432`.
+    ///
+    /// TLS is never negotiated over a Unix domain socket, regardless of the
+    /// [SSL mode][Self::ssl_mode].
+    ///
     /// By default set to `None`.
     pub fn socket(mut self, path: impl AsRef<Path>) -> Self {
         self.socket = Some(path.as_ref().to_path_buf());
@@ -453,6 +481,85 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets a list of statements to prepare and store in the statement cache as soon as
+    /// the connection is established, so the first query to use one of them does not
+    /// pay the cost of preparing it.
+    ///
+    /// If any of the statements fails to prepare, the connection attempt fails with the
+    /// error returned for it, such as a [`Error::Database`][crate::Error::Database] for a
+    /// statement the server rejects.
+    ///
+    /// Statements are only retained if they fit within the
+    /// [statement cache capacity][Self::statement_cache_capacity].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .prepare_on_connect(["SELECT * FROM users WHERE id = $1"]);
+    /// ```
+    pub fn prepare_on_connect<I, S>(mut self, statements: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.prepare_on_connect = statements.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets whether the plan of each query should be captured alongside its result.
+    ///
+    /// When enabled, every `SELECT`, `INSERT`, `UPDATE`, `DELETE` or `VALUES` statement is first
+    /// run as `EXPLAIN (ANALYZE, FORMAT JSON) <query>` inside a transaction (or a savepoint, if a
+    /// transaction is already open) which is then rolled back, so that writes have no lasting
+    /// effect. The query itself is then executed as usual and the plan is available from
+    /// [`PgQueryResult::query_plan`][crate::PgQueryResult::query_plan].
+    ///
+    /// Other statements, including those starting with a `WITH` clause or a comment, and
+    /// queries which cannot be explained (e.g. multiple statements) are executed without a plan.
+    /// Queries are not explained inside a transaction which has failed.
+    ///
+    /// ### Note: Double Execution
+    /// `EXPLAIN ANALYZE` really executes the query, so this roughly doubles the cost of every
+    /// query along with adding several round-trips to the server. Side effects outside of the
+    /// transaction, such as calls to `nextval()` or `pg_notify()`, are not rolled back.
+    /// This is intended for diagnosing slow queries and should not be left enabled in production.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .capture_query_plans(true);
+    /// ```
+    pub fn capture_query_plans(mut self, capture: bool) -> Self {
+        self.capture_query_plans = capture;
+        self
+    }
+
+    /// Sets whether a query is cancelled when its [`fetch()`][crate::Executor::fetch] stream
+    /// (or the future of another `Executor` method) is dropped before reading all of its results.
+    ///
+    /// The rest of an abandoned result is always read and discarded the next time the
+    /// connection is used. When enabled, a cancel request (see
+    /// [`PgConnection::cancel_token()`][crate::PgConnection::cancel_token]) is sent at that
+    /// point first, so a long-running query stops early instead of delaying the next one until
+    /// it completes. The request is only sent while the abandoned result is being drained,
+    /// and the connection waits for it to be sent before anything else, so it cannot cancel
+    /// a later query.
+    ///
+    /// This opens a new connection to the server for each abandoned query. Inside a
+    /// transaction, a cancelled query fails the transaction, as any other error would.
+    ///
+    /// Defaults to `false`.
+    pub fn cancel_on_drop(mut self, cancel: bool) -> Self {
+        self.cancel_on_drop = cancel;
+        self
+    }
+
     /// Sets the application name. Defaults to None
     ///
     /// # Example
@@ -547,6 +654,174 @@ impl PgConnectOptions {
         self
     }
 
+    /// Set additional parameters to send in the startup message, as a list of key-value pairs.
+    ///
+    /// Unlike running `SET` after connecting, these take effect before the first query,
+    /// e.g. `application_name` is visible in `pg_stat_activity` as soon as the connection
+    /// is established. They are sent after the parameters SQLx sets by default, so they can
+    /// override them.
+    ///
+    /// Connecting fails with a configuration error if a key or value contains a null byte,
+    /// or if the key is `user` or `database` (use [`username`][Self::username] and
+    /// [`database`][Self::database] instead).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .startup_parameters([("search_path", "app,public"), ("statement_timeout", "5min")]);
+    /// ```
+    pub fn startup_parameters<K, V, I>(mut self, parameters: I) -> Self
+    where
+        K: Display,
+        V: Display,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.startup_parameters.extend(
+            parameters
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        );
+        self
+    }
+
+    /// Sets whether transactions on the connection are read-only by default.
+    ///
+    /// If enabled, `default_transaction_read_only` is set at connection startup so the server
+    /// rejects any statement that writes to a table, which is useful as a safety net
+    /// when connecting to a read replica.
+    ///
+    /// Note that a transaction can still explicitly opt out with `SET TRANSACTION READ WRITE`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .read_only(true);
+    /// ```
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets the schema that unqualified table (and other object) names resolve to.
+    ///
+    /// The `search_path` of the connection is set to the schema, followed by `public` so
+    /// extensions installed there keep working. The schema name is quoted, so it is used
+    /// exactly as given (including case). Objects created without a schema are created in it.
+    ///
+    /// The `search_path` is sent in the startup message rather than with `SET` after
+    /// connecting. This makes it the session default, which matters with a pool: SQLx does not
+    /// reset session state when a connection is returned, so a `SET search_path` made by one
+    /// user of a connection is still in effect for the next. `RESET search_path` (or
+    /// `RESET ALL` / `DISCARD ALL`) restores the schema set here, not the server default,
+    /// so it is safe to run in
+    /// [`PoolOptions::after_release`][sqlx_core::pool::PoolOptions::after_release]:
+    ///
+    /// ```rust,no_run
+    /// # async fn f() -> Result<(), sqlx_core::error::Error> {
+    /// # use sqlx_core::executor::Executor;
+    /// # use sqlx_core::postgres::{PgConnectOptions, PgPoolOptions};
+    /// let pool = PgPoolOptions::new()
+    ///     .after_release(|conn, _meta| {
+    ///         Box::pin(async move {
+    ///             conn.execute("RESET search_path").await?;
+    ///             Ok(true)
+    ///         })
+    ///     })
+    ///     .connect_with(PgConnectOptions::new().default_schema("tenant_1"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// A `search_path` passed to [`startup_parameters`][Self::startup_parameters] takes
+    /// precedence over this.
+    pub fn default_schema(mut self, schema: impl Into<String>) -> Self {
+        self.default_schema = Some(schema.into());
+        self
+    }
+
+    /// Get the default schema, if one was set with [`default_schema`][Self::default_schema].
+    pub fn get_default_schema(&self) -> Option<&str> {
+        self.default_schema.as_deref()
+    }
+
+    /// Sets the maximum time to wait for a connection to be established, including
+    /// the TLS handshake and authentication.
+    ///
+    /// If the timeout elapses, connecting fails with an I/O error of kind
+    /// [`TimedOut`][std::io::ErrorKind::TimedOut].
+    ///
+    /// Defaults to `None`, which waits indefinitely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .connect_timeout(Duration::from_secs(10));
+    /// ```
+    pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
+    /// Sets the idle time after which TCP keepalive probes are sent on the connection.
+    ///
+    /// If the server host goes away without closing the connection (e.g. it loses power or a
+    /// firewall drops the connection), the operating system notices once the probes go
+    /// unanswered, and reads and writes on the connection fail instead of hanging forever.
+    /// The interval between probes and how many are sent are left to the operating system.
+    ///
+    /// Has no effect on Unix domain sockets. Defaults to `None`, which leaves keepalive
+    /// disabled.
+    pub fn tcp_keepalive(mut self, idle: impl Into<Option<Duration>>) -> Self {
+        self.tcp_keepalive = idle.into();
+        self
+    }
+
+    /// Sets the maximum time to wait for the server to send data once a response is expected.
+    ///
+    /// This bounds every single read, so it must be longer than the longest time the server may
+    /// take to start sending the results of a query (or the next rows of one). If it elapses,
+    /// the operation fails with an I/O error of kind [`TimedOut`][std::io::ErrorKind::TimedOut]
+    /// and the connection can no longer be used; a pool closes such connections.
+    ///
+    /// It does not apply while a [`PgListener`][crate::PgListener] waits for notifications with
+    /// no query in progress.
+    ///
+    /// Defaults to `None`, which waits indefinitely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .tcp_keepalive(Duration::from_secs(60))
+    ///     .read_timeout(Duration::from_secs(30))
+    ///     .write_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn read_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.read_timeout = timeout.into();
+        self
+    }
+
+    /// Sets the maximum time to wait for the server to accept data sent to it.
+    ///
+    /// If it elapses, the operation fails with an I/O error of kind
+    /// [`TimedOut`][std::io::ErrorKind::TimedOut] and the connection can no longer be used.
+    ///
+    /// Defaults to `None`, which waits indefinitely.
+    pub fn write_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.write_timeout = timeout.into();
+        self
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
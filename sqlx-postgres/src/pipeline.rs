@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::error::Result;
+use crate::executor::{run_until, Execute};
+use crate::statement::{PgPreparedStatement, PgStatementMetadata};
+use crate::{PgArguments, PgConnection, PgQueryResult, Postgres};
+
+impl PgConnection {
+    /// Begin a [`PgPipeline`] to send several queries to the server in a single round trip.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let results = conn
+    ///     .pipeline()
+    ///     .push(sqlx::query("UPDATE account SET balance = balance - $1 WHERE id = $2").bind(10).bind(1))
+    ///     .push(sqlx::query("UPDATE account SET balance = balance + $1 WHERE id = $2").bind(10).bind(2))
+    ///     .execute()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pipeline<'q>(&mut self) -> PgPipeline<'_, 'q> {
+        PgPipeline {
+            conn: self,
+            queries: Vec::new(),
+        }
+    }
+}
+
+/// A batch of queries sent to Postgres together and executed in order.
+///
+/// Created by [`PgConnection::pipeline()`]. The `Bind` and `Execute` messages of every query are
+/// written before a single `Sync`, so the server runs all of them without waiting on the client
+/// in between. This saves a round trip per query, which matters most over high-latency links.
+///
+/// As one `Sync` ends the batch, the queries run in a single implicit transaction unless one is
+/// already open: if any of them fails, the effects of the others are rolled back and the error
+/// of the failed query is returned.
+///
+/// Each query is executed as a prepared statement, even without arguments, and must be a single
+/// statement. Statements not yet prepared on the connection are prepared first, one round trip
+/// each; once cached, a repeated pipeline is sent all at once. Rows returned by the queries are
+/// discarded.
+pub struct PgPipeline<'c, 'q> {
+    conn: &'c mut PgConnection,
+    queries: Vec<PipelinedQuery<'q>>,
+}
+
+pub(crate) struct PipelinedQuery<'q> {
+    pub(crate) sql: &'q str,
+    pub(crate) arguments: PgArguments,
+    pub(crate) persistent: bool,
+    pub(crate) metadata: Option<Arc<PgStatementMetadata>>,
+    pub(crate) prepared: Option<Arc<PgPreparedStatement>>,
+    pub(crate) deadline: Option<Instant>,
+}
+
+impl<'c, 'q> PgPipeline<'c, 'q> {
+    /// Add a query to the end of the pipeline.
+    pub fn push<E>(&mut self, mut query: E) -> &mut Self
+    where
+        E: Execute<'q, Postgres>,
+    {
+        self.queries.push(PipelinedQuery {
+            sql: query.sql(),
+            metadata: query.statement().map(|s| Arc::clone(&s.metadata)),
+            prepared: query.statement().and_then(|s| s.prepared.clone()),
+            // pipelining is only possible with the extended query protocol
+            arguments: query.take_arguments().unwrap_or_default(),
+            persistent: query.persistent(),
+            deadline: query.deadline(),
+        });
+
+        self
+    }
+
+    /// The number of queries in the pipeline.
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// Returns `true` if no queries were added to the pipeline.
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    /// Send every query in the pipeline and wait for all of them to complete.
+    ///
+    /// Returns one [`PgQueryResult`] per query, in the order they were added. The pipeline is
+    /// left empty, whether or not it succeeds.
+    pub async fn execute(&mut self) -> Result<Vec<PgQueryResult>> {
+        let queries = std::mem::take(&mut self.queries);
+
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // the pipeline completes as a whole, so it has to meet the earliest deadline of its queries
+        let deadline = queries.iter().filter_map(|query| query.deadline).min();
+
+        run_until(deadline, self.conn.run_pipeline(queries)).await
+    }
+}
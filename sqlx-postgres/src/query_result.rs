@@ -1,20 +1,52 @@
 use std::iter::{Extend, IntoIterator};
+use std::sync::Arc;
 
 #[derive(Debug, Default)]
 pub struct PgQueryResult {
     pub(super) rows_affected: u64,
+    pub(super) query_plan: Option<Arc<str>>,
 }
 
 impl PgQueryResult {
+    /// The number of rows inserted, updated, deleted, selected, etc. by the query, as reported
+    /// in its command tag (e.g. `INSERT 0 5`, `UPDATE 3` or `DELETE 2`).
+    ///
+    /// Commands that do not report a count, such as `CREATE TABLE`, affect `0` rows. If the
+    /// query string contains more than one statement, this is the sum for all of them.
+    ///
+    /// Postgres does not report generated keys here; to get the id of an inserted row, add a
+    /// `RETURNING` clause and fetch it instead:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let id: i64 = sqlx::query_scalar("INSERT INTO tweet (text) VALUES ($1) RETURNING id")
+    ///     .bind("Hello, world!")
+    ///     .fetch_one(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// The plan of the query as returned by `EXPLAIN (ANALYZE, FORMAT JSON)`.
+    ///
+    /// Only available if [`PgConnectOptions::capture_query_plans`][crate::PgConnectOptions::capture_query_plans]
+    /// is enabled and the query could be explained.
+    pub fn query_plan(&self) -> Option<&str> {
+        self.query_plan.as_deref()
+    }
 }
 
 impl Extend<PgQueryResult> for PgQueryResult {
     fn extend<T: IntoIterator<Item = PgQueryResult>>(&mut self, iter: T) {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
+
+            if elem.query_plan.is_some() {
+                self.query_plan = elem.query_plan;
+            }
         }
     }
 }
@@ -11,7 +11,8 @@ pub(crate) use sqlx_core::row::Row;
 /// Implementation of [`Row`] for PostgreSQL.
 pub struct PgRow {
     pub(crate) data: DataRow,
-    pub(crate) format: PgValueFormat,
+    // the format of each column, or a single format for all of them
+    pub(crate) formats: Arc<[PgValueFormat]>,
     pub(crate) metadata: Arc<PgStatementMetadata>,
 }
 
@@ -29,9 +30,13 @@ impl Row for PgRow {
         let index = index.index(self)?;
         let column = &self.metadata.columns[index];
         let value = self.data.get(index);
+        let format = match *self.formats {
+            [format] => format,
+            ref formats => formats[index],
+        };
 
         Ok(PgValueRef {
-            format: self.format,
+            format,
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
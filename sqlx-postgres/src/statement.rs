@@ -2,9 +2,10 @@ use super::{PgColumn, PgTypeInfo};
 use crate::column::ColumnIndex;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::types::Oid;
 use crate::{PgArguments, Postgres};
 use std::borrow::Cow;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 
 pub(crate) use sqlx_core::statement::Statement;
 use sqlx_core::{Either, HashMap};
@@ -13,6 +14,27 @@ use sqlx_core::{Either, HashMap};
 pub struct PgStatement<'q> {
     pub(crate) sql: Cow<'q, str>,
     pub(crate) metadata: Arc<PgStatementMetadata>,
+    // the statement `prepare()` created on the server, shared by every copy of this one
+    pub(crate) prepared: Option<Arc<PgPreparedStatement>>,
+}
+
+// a statement prepared on a connection apart from its statement cache; it stays prepared until
+// the last `PgStatement` referring to it is dropped, and is closed on the next use of the
+// connection after that
+#[derive(Debug)]
+pub(crate) struct PgPreparedStatement {
+    pub(crate) id: Oid,
+    // statements of the connection waiting to be closed
+    pub(crate) dropped: Arc<Mutex<Vec<Oid>>>,
+}
+
+impl Drop for PgPreparedStatement {
+    fn drop(&mut self) {
+        self.dropped
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(self.id);
+    }
 }
 
 #[derive(Debug, Default)]
@@ -31,6 +53,7 @@ impl<'q> Statement<'q> for PgStatement<'q> {
         PgStatement::<'static> {
             sql: Cow::Owned(self.sql.clone().into_owned()),
             metadata: self.metadata.clone(),
+            prepared: self.prepared.clone(),
         }
     }
 
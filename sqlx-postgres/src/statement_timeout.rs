@@ -0,0 +1,91 @@
+use std::cmp;
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+use crate::{PgConnection, Postgres};
+use sqlx_core::connection::Connection;
+use sqlx_core::transaction::Transaction;
+
+impl PgConnection {
+    /// Execute the function with a server-side `statement_timeout`, so that any statement
+    /// running for longer than `timeout` is cancelled by the server itself and fails with
+    /// SQLSTATE `57014` (`query_canceled`).
+    ///
+    /// The timeout is set with `SET LOCAL` in a new transaction, or a savepoint if a transaction
+    /// is already open, which is rolled back if the function returns an error and committed
+    /// (or released) otherwise. This keeps the setting from leaking into the rest of the
+    /// session: outside of a transaction it ends with the implicit one, and inside of one
+    /// the previous value is restored before the savepoint is released.
+    ///
+    /// The timeout is rounded down to whole milliseconds, with a minimum of one millisecond.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use sqlx::postgres::{PgConnection, PgRow};
+    ///
+    /// # pub async fn _f(conn: &mut PgConnection) -> sqlx::Result<Vec<PgRow>> {
+    /// conn.with_statement_timeout(Duration::from_secs(5), |conn| Box::pin(async move {
+    ///     sqlx::query("select * from ..").fetch_all(conn).await
+    /// })).await
+    /// # }
+    /// ```
+    pub async fn with_statement_timeout<F, R, E>(
+        &mut self,
+        timeout: Duration,
+        callback: F,
+    ) -> Result<R, E>
+    where
+        for<'c> F: FnOnce(&'c mut PgConnection) -> BoxFuture<'c, Result<R, E>>,
+        E: From<Error>,
+    {
+        let mut transaction = self.begin().await?;
+
+        // `SET LOCAL` lasts until the end of the whole transaction, not just the savepoint
+        let previous: Option<String> = if transaction.transaction_depth > 1 {
+            Some(
+                crate::query_scalar::query_scalar("SELECT current_setting('statement_timeout')")
+                    .fetch_one(&mut *transaction)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let millis = cmp::max(timeout.as_millis(), 1);
+        set_local_statement_timeout(&mut transaction, &format!("{millis}ms")).await?;
+
+        match callback(&mut transaction).await {
+            Ok(ret) => {
+                if let Some(previous) = previous {
+                    set_local_statement_timeout(&mut transaction, &previous).await?;
+                }
+
+                transaction.commit().await?;
+
+                Ok(ret)
+            }
+            Err(err) => {
+                // rolling back also reverts the `SET LOCAL`
+                transaction.rollback().await?;
+
+                Err(err)
+            }
+        }
+    }
+}
+
+async fn set_local_statement_timeout(
+    transaction: &mut Transaction<'_, Postgres>,
+    value: &str,
+) -> Result<(), Error> {
+    crate::query::query("SELECT set_config('statement_timeout', $1, true)")
+        .bind(value)
+        .execute(&mut **transaction)
+        .await?;
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::error::Error;
@@ -14,9 +16,16 @@ impl TransactionManager for PgTransactionManager {
     type Database = Postgres;
 
     fn begin(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut PgConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
+            let query = begin_transaction_sql(conn.transaction_depth, options)?;
             let rollback = Rollback::new(conn);
-            let query = begin_ansi_transaction_sql(rollback.conn.transaction_depth);
             rollback.conn.queue_simple_query(&query);
             rollback.conn.transaction_depth += 1;
             rollback.conn.wait_until_ready().await?;
@@ -61,6 +70,38 @@ impl TransactionManager for PgTransactionManager {
     }
 }
 
+// https://www.postgresql.org/docs/current/sql-begin.html
+fn begin_transaction_sql(
+    depth: usize,
+    options: TransactionOptions,
+) -> Result<Cow<'static, str>, Error> {
+    if options == TransactionOptions::default() {
+        return Ok(begin_ansi_transaction_sql(depth));
+    }
+
+    if depth > 0 {
+        return Err(Error::Configuration(
+            "transaction options cannot be applied to a savepoint".into(),
+        ));
+    }
+
+    let mut modes = Vec::new();
+
+    if let Some(level) = options.get_isolation_level() {
+        modes.push(format!("ISOLATION LEVEL {}", level.as_sql()));
+    }
+
+    if options.is_read_only() {
+        modes.push("READ ONLY".into());
+    }
+
+    if options.is_deferrable() {
+        modes.push("DEFERRABLE".into());
+    }
+
+    Ok(Cow::Owned(format!("BEGIN {}", modes.join(", "))))
+}
+
 struct Rollback<'c> {
     conn: &'c mut PgConnection,
     defuse: bool,
@@ -85,3 +126,46 @@ impl<'c> Rollback<'c> {
         self.defuse = true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::begin_transaction_sql;
+    use sqlx_core::transaction::{IsolationLevel, TransactionOptions};
+
+    #[test]
+    fn it_builds_begin_with_options() {
+        let sql = |depth, options| begin_transaction_sql(depth, options).unwrap();
+
+        assert_eq!(sql(0, TransactionOptions::new()), "BEGIN");
+        assert_eq!(
+            sql(1, TransactionOptions::new()),
+            "SAVEPOINT _sqlx_savepoint_1"
+        );
+
+        assert_eq!(
+            sql(
+                0,
+                TransactionOptions::new().isolation_level(IsolationLevel::RepeatableRead)
+            ),
+            "BEGIN ISOLATION LEVEL REPEATABLE READ"
+        );
+
+        assert_eq!(
+            sql(0, TransactionOptions::new().read_only(true)),
+            "BEGIN READ ONLY"
+        );
+
+        assert_eq!(
+            sql(
+                0,
+                TransactionOptions::new()
+                    .isolation_level(IsolationLevel::Serializable)
+                    .read_only(true)
+                    .deferrable(true)
+            ),
+            "BEGIN ISOLATION LEVEL SERIALIZABLE, READ ONLY, DEFERRABLE"
+        );
+
+        assert!(begin_transaction_sql(1, TransactionOptions::new().read_only(true)).is_err());
+    }
+}
@@ -246,14 +246,28 @@ where
 
                 // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
 
+                // explicit bounds are only printed if the lower bound is not 1, e.g. `[0:1]={1,2}`
+                if s.starts_with('[') {
+                    return Err(format!("encountered an array with explicit bounds {:?}; only arrays starting at one are supported", s.split('=').next().unwrap_or_default()).into());
+                }
+
                 // trim the wrapping braces
-                let s = &s[1..(s.len() - 1)];
+                let s = s
+                    .strip_prefix('{')
+                    .and_then(|s| s.strip_suffix('}'))
+                    .ok_or_else(|| {
+                        format!("expected an array literal wrapped in braces, got {s:?}")
+                    })?;
 
                 if s.is_empty() {
                     // short-circuit empty arrays up here
                     return Ok(Vec::new());
                 }
 
+                if s.starts_with('{') {
+                    return Err("encountered a multi-dimensional array; only one-dimensional arrays are supported".into());
+                }
+
                 // NOTE: Nearly *all* types use ',' as the sequence delimiter. Yes, there is one
                 //       that does not. The BOX (not PostGIS) type uses ';' as a delimiter.
 
@@ -264,6 +278,8 @@ where
                 let mut done = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
+                // an element that was quoted is never NULL, even if its contents read `NULL`
+                let mut quoted = false;
                 let mut value = String::with_capacity(10);
                 let mut chars = s.chars();
                 let mut elements = Vec::with_capacity(4);
@@ -279,6 +295,7 @@ where
 
                                 '"' => {
                                     in_quotes = !in_quotes;
+                                    quoted = true;
                                 }
 
                                 '\\' => {
@@ -301,7 +318,7 @@ where
                         }
                     }
 
-                    let value_opt = if value == "NULL" {
+                    let value_opt = if value == "NULL" && !quoted {
                         None
                     } else {
                         Some(value.as_bytes())
@@ -315,6 +332,7 @@ where
                     })?);
 
                     value.clear();
+                    quoted = false;
                 }
 
                 Ok(elements)
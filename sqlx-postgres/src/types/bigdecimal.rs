@@ -26,13 +26,13 @@ impl TryFrom<PgNumeric> for BigDecimal {
     type Error = BoxDynError;
 
     fn try_from(numeric: PgNumeric) -> Result<Self, BoxDynError> {
-        let (digits, sign, weight) = match numeric {
+        let (digits, sign, weight, scale) = match numeric {
             PgNumeric::Number {
                 digits,
                 sign,
                 weight,
-                ..
-            } => (digits, sign, weight),
+                scale,
+            } => (digits, sign, weight, scale),
 
             PgNumeric::NotANumber => {
                 return Err("BigDecimal does not support NaN values".into());
@@ -49,8 +49,9 @@ impl TryFrom<PgNumeric> for BigDecimal {
             PgNumericSign::Negative => Sign::Minus,
         };
 
-        // weight is 0 if the decimal point falls after the first base-10000 digit
-        let scale = (digits.len() as i64 - weight as i64 - 1) * 4;
+        // weight is 0 if the decimal point falls after the first base-10000 digit; a negative
+        // exponent stands for trailing zero digits that were left out, e.g. for `12000000000`
+        let exponent = (digits.len() as i64 - weight as i64 - 1) * 4;
 
         // no optimized algorithm for base-10 so use base-100 for faster processing
         let mut cents = Vec::with_capacity(digits.len() * 2);
@@ -62,7 +63,9 @@ impl TryFrom<PgNumeric> for BigDecimal {
         let bigint = BigInt::from_radix_be(sign, &cents, 100)
             .ok_or("PgNumeric contained an out-of-range digit")?;
 
-        Ok(BigDecimal::new(bigint, scale))
+        // the padding of the last digit only adds zeroes past the display scale, which is
+        // never negative in a value sent by Postgres
+        Ok(BigDecimal::new(bigint, exponent).with_scale(cmp::max(scale, 0).into()))
     }
 }
 
@@ -160,7 +163,10 @@ impl Decode<'_, Postgres> for BigDecimal {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         match value.format() {
             PgValueFormat::Binary => PgNumeric::decode(value.as_bytes()?)?.try_into(),
-            PgValueFormat::Text => Ok(value.as_str()?.parse::<BigDecimal>()?),
+            PgValueFormat::Text => match value.as_str()? {
+                "NaN" => Err("BigDecimal does not support NaN values".into()),
+                s => Ok(s.parse::<BigDecimal>()?),
+            },
         }
     }
 }
@@ -428,3 +434,96 @@ mod bigdecimal_to_pgnumeric {
         );
     }
 }
+
+#[cfg(test)]
+mod pgnumeric_to_bigdecimal {
+    use super::{BigDecimal, PgNumeric, PgNumericSign};
+    use std::convert::TryFrom;
+
+    fn decode(sign: PgNumericSign, digits: Vec<i16>, weight: i16, scale: i16) -> String {
+        BigDecimal::try_from(PgNumeric::Number {
+            sign,
+            digits,
+            weight,
+            scale,
+        })
+        .unwrap()
+        .to_string()
+    }
+
+    #[test]
+    fn large_integer() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![120], 2, 0),
+            "12000000000"
+        );
+    }
+
+    #[test]
+    fn negative_large_integer() {
+        assert_eq!(
+            decode(PgNumericSign::Negative, vec![1, 2345], 3, 0),
+            "-1234500000000"
+        );
+    }
+
+    #[test]
+    fn tiny_fraction() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1000], -2, 5),
+            "0.00001"
+        );
+    }
+
+    #[test]
+    fn tiny_fraction_with_integer_part() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1, 0, 1000], 0, 5),
+            "1.00001"
+        );
+    }
+
+    #[test]
+    fn power_of_ten_thousand() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![1], 1, 0), "10000");
+    }
+
+    #[test]
+    fn power_of_ten_thousand_squared() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![1], 2, 0), "100000000");
+    }
+
+    #[test]
+    fn negative_power_of_ten_thousand() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![1], -1, 4), "0.0001");
+    }
+
+    #[test]
+    fn negative_power_of_ten_thousand_squared() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1], -2, 8),
+            "0.00000001"
+        );
+    }
+
+    #[test]
+    fn leading_zero_digit() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![0, 5], 0, 4), "0.0005");
+    }
+
+    #[test]
+    fn trailing_zero_digits() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1, 0, 0], 2, 0),
+            "100000000"
+        );
+    }
+
+    #[test]
+    fn scale_past_digits() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1, 5000], 0, 3),
+            "1.500"
+        );
+    }
+}
@@ -15,7 +15,8 @@ impl Type<Postgres> for BitVec {
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
-        *ty == PgTypeInfo::BIT || *ty == PgTypeInfo::VARBIT
+        // `BOOL[]` can be decoded into a `BitVec` but not encoded from one
+        *ty == PgTypeInfo::BIT || *ty == PgTypeInfo::VARBIT || *ty == PgTypeInfo::BOOL_ARRAY
     }
 }
 
@@ -44,6 +45,10 @@ impl Encode<'_, Postgres> for BitVec {
 
 impl Decode<'_, Postgres> for BitVec {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.type_info == PgTypeInfo::BOOL_ARRAY {
+            return decode_bool_array(value);
+        }
+
         match value.format() {
             PgValueFormat::Binary => {
                 let mut bytes = value.as_bytes()?;
@@ -99,3 +104,23 @@ impl Decode<'_, Postgres> for BitVec {
         }
     }
 }
+
+/// Pack a `BOOL[]` into a `BitVec`, one bit per element.
+fn decode_bool_array(value: PgValueRef<'_>) -> Result<BitVec, BoxDynError> {
+    let elements: Vec<Option<bool>> = Decode::<Postgres>::decode(value)?;
+    let mut bit_vec = BitVec::with_capacity(elements.len());
+
+    for (i, element) in elements.into_iter().enumerate() {
+        let Some(bit) = element else {
+            return Err(format!(
+                "cannot decode BOOL[] with a NULL element at index {i} into `BitVec`; \
+                 decode into `Vec<Option<bool>>` instead"
+            )
+            .into());
+        };
+
+        bit_vec.push(bit);
+    }
+
+    Ok(bit_vec)
+}
@@ -1,8 +1,12 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::io::AsyncRead;
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Mutex, PoisonError};
+use std::{cmp, io};
 
 impl PgHasArrayType for u8 {
     fn array_type_info() -> PgTypeInfo {
@@ -116,3 +120,164 @@ impl<const N: usize> Decode<'_, Postgres> for [u8; N] {
         Ok(bytes)
     }
 }
+
+/// Reads the contents of a `BYTEA` value directly from the row, without copying them into
+/// a new buffer.
+///
+/// Decoding to `&[u8]` also borrows the bytes from the row, but only works with the binary
+/// format used by prepared queries. This type additionally supports the hex text format
+/// (used by simple queries), which is decoded a chunk at a time as it is read.
+///
+/// Since this implements [`std::io::Read`], a large value can be fed into a hasher or other
+/// [`std::io::Write`] implementation with [`std::io::copy()`] without ever being
+/// materialized as a `Vec<u8>`.
+#[derive(Debug, Clone)]
+pub struct PgByteaReader<'r> {
+    remaining: &'r [u8],
+    format: PgValueFormat,
+}
+
+impl PgByteaReader<'_> {
+    /// The number of bytes left to read.
+    pub fn remaining_len(&self) -> usize {
+        match self.format {
+            PgValueFormat::Binary => self.remaining.len(),
+            PgValueFormat::Text => self.remaining.len() / 2,
+        }
+    }
+}
+
+impl Type<Postgres> for PgByteaReader<'_> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgByteaReader<'r> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let format = value.format();
+
+        let remaining = match format {
+            PgValueFormat::Binary => value.as_bytes()?,
+            PgValueFormat::Text => {
+                let hex = text_hex_decode_input(value)?;
+
+                if hex.len() % 2 != 0 {
+                    return Err("odd number of hex digits in BYTEA text".into());
+                }
+
+                hex
+            }
+        };
+
+        Ok(PgByteaReader { remaining, format })
+    }
+}
+
+impl io::Read for PgByteaReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = cmp::min(self.remaining_len(), buf.len());
+
+        match self.format {
+            PgValueFormat::Binary => {
+                let (chunk, rest) = self.remaining.split_at(len);
+
+                buf[..len].copy_from_slice(chunk);
+                self.remaining = rest;
+            }
+
+            PgValueFormat::Text => {
+                let (chunk, rest) = self.remaining.split_at(len * 2);
+
+                hex::decode_to_slice(chunk, &mut buf[..len])
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                self.remaining = rest;
+            }
+        }
+
+        Ok(len)
+    }
+}
+
+/// A `BYTEA` parameter whose contents are read from an [`AsyncRead`] while the query is sent,
+/// so that a large value (e.g. a file) never has to be held in memory in its entirety.
+///
+/// Postgres needs the length of a parameter before its contents, so it must be known up front.
+/// Sending the query fails if `source` ends early or has more than `len` bytes to give; as the
+/// query is then only partly sent, the connection is left unusable and is closed rather than
+/// returned to the pool.
+///
+/// The source is consumed by the first query it is bound to, and it can only be bound as a
+/// parameter on its own, not as part of an array or record. Queries binding one are not
+/// explained when capturing query plans, as that would consume it as well.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgByteaStream;
+///
+/// let file = tokio::fs::File::open("upload.bin").await?;
+/// let len = file.metadata().await?.len();
+///
+/// sqlx::query("INSERT INTO uploads (data) VALUES ($1)")
+///     .bind(PgByteaStream::new(file, len))
+///     .execute(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Note: Runtime Features
+/// This type uses the `AsyncRead` trait which is re-exported from either Tokio or `async-std`
+/// depending on which runtime feature is used, like [`PgCopyIn::read_from`][crate::PgCopyIn::read_from].
+pub struct PgByteaStream {
+    source: Mutex<Option<Box<dyn AsyncRead + Send + Unpin>>>,
+    len: u64,
+}
+
+impl PgByteaStream {
+    /// Stream exactly `len` bytes from `source`.
+    pub fn new(source: impl AsyncRead + Send + Unpin + 'static, len: u64) -> Self {
+        PgByteaStream {
+            source: Mutex::new(Some(Box::new(source))),
+            len,
+        }
+    }
+
+    /// The number of bytes that will be read from the source.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the value is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Debug for PgByteaStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgByteaStream")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Type<Postgres> for PgByteaStream {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA
+    }
+}
+
+impl Encode<'_, Postgres> for PgByteaStream {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        let source = self
+            .source
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        buf.push_stream(self.len, source);
+
+        IsNull::No
+    }
+}
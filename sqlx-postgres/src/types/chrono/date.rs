@@ -36,7 +36,11 @@ impl<'r> Decode<'r, Postgres> for NaiveDate {
             PgValueFormat::Binary => {
                 // DATE is encoded as the days since epoch
                 let days: i32 = Decode::<Postgres>::decode(value)?;
-                postgres_epoch_date() + Duration::days(days.into())
+                postgres_epoch_date()
+                    .checked_add_signed(Duration::days(days.into()))
+                    .ok_or_else(|| {
+                        format!("value {days} is out of range for NaiveDate (infinity?)")
+                    })?
             }
 
             PgValueFormat::Text => NaiveDate::parse_from_str(value.as_str()?, "%Y-%m-%d")?,
@@ -53,8 +53,12 @@ impl<'r> Decode<'r, Postgres> for NaiveDateTime {
         Ok(match value.format() {
             PgValueFormat::Binary => {
                 // TIMESTAMP is encoded as the microseconds since the epoch
-                let us = Decode::<Postgres>::decode(value)?;
-                postgres_epoch_datetime() + Duration::microseconds(us)
+                let us: i64 = Decode::<Postgres>::decode(value)?;
+                postgres_epoch_datetime()
+                    .checked_add_signed(Duration::microseconds(us))
+                    .ok_or_else(|| {
+                        format!("value {us} is out of range for NaiveDateTime (infinity?)")
+                    })?
             }
 
             PgValueFormat::Text => {
@@ -45,14 +45,278 @@ impl<'de> Decode<'de, Postgres> for PgInterval {
                 })
             }
 
-            // TODO: Implement parsing of text mode
             PgValueFormat::Text => {
-                Err("not implemented: decode `INTERVAL` in text mode (unprepared queries)".into())
+                let s = value.as_str()?;
+
+                parse_interval(s).ok_or_else(|| format!("invalid interval: {s:?}").into())
             }
         }
     }
 }
 
+// Parses the text output of an interval in any `IntervalStyle`:
+// https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-INTERVAL-OUTPUT
+//
+// | `IntervalStyle`    | example                                             |
+// |--------------------|-----------------------------------------------------|
+// | `postgres`         | `-1 years -2 mons +3 days -04:05:06`                |
+// | `postgres_verbose` | `@ 1 year 2 mons -3 days 4 hours 5 mins 6 secs ago` |
+// | `sql_standard`     | `-1-2 +3 -4:05:06`                                  |
+// | `iso_8601`         | `P-1Y-2M3DT-4H-5M-6S`                               |
+fn parse_interval(s: &str) -> Option<PgInterval> {
+    if let Some(s) = s.strip_prefix('P') {
+        parse_iso_8601(s)
+    } else if let Some(s) = s.strip_prefix('@') {
+        parse_postgres(s)
+    } else if s.contains(|c: char| c.is_ascii_alphabetic()) {
+        parse_postgres(s)
+    } else {
+        parse_sql_standard(s)
+    }
+}
+
+// `postgres` and `postgres_verbose`: numbers followed by their unit, and in `postgres`
+// the time as `[-+]HH:MM:SS[.ffffff]`
+fn parse_postgres(s: &str) -> Option<PgInterval> {
+    let mut tokens: Vec<&str> = s.split_whitespace().collect();
+
+    // `postgres_verbose` negates the whole interval with a trailing `ago`
+    let ago = tokens.last() == Some(&"ago");
+
+    if ago {
+        tokens.pop();
+    }
+
+    let mut parts = IntervalParts::default();
+    let mut tokens = tokens.into_iter();
+
+    while let Some(token) = tokens.next() {
+        if token.contains(':') {
+            parts.add_microseconds(parse_time(token)?, 1)?;
+            continue;
+        }
+
+        let Some(unit) = tokens.next() else {
+            // a zero interval is `@ 0` in `postgres_verbose`
+            return if token == "0" {
+                parts.finish(ago)
+            } else {
+                None
+            };
+        };
+
+        match unit.strip_suffix('s').unwrap_or(unit) {
+            "year" => parts.add_months(parse_int(token)?, 12)?,
+            "mon" => parts.add_months(parse_int(token)?, 1)?,
+            "day" => parts.add_days(parse_int(token)?, 1)?,
+            "hour" => parts.add_microseconds(parse_int(token)?, 3_600_000_000)?,
+            "min" => parts.add_microseconds(parse_int(token)?, 60_000_000)?,
+            "sec" => parts.add_microseconds(parse_seconds(token)?, 1)?,
+            _ => return None,
+        }
+    }
+
+    parts.finish(ago)
+}
+
+// `sql_standard`: `[-+]Y-M`, `[-+]D` and `[-+]H:MM:SS[.ffffff]`
+fn parse_sql_standard(s: &str) -> Option<PgInterval> {
+    let tokens: Vec<&str> = s.split_whitespace().collect();
+
+    // a leading `-` applies to every field, unless they are all signed individually
+    let signed_individually = tokens
+        .iter()
+        .skip(1)
+        .any(|token| token.starts_with(['-', '+']));
+
+    let negative = !signed_individually && tokens.first()?.starts_with('-');
+
+    let mut parts = IntervalParts::default();
+
+    for (i, &token) in tokens.iter().enumerate() {
+        let token = if negative && i == 0 {
+            &token[1..]
+        } else {
+            token
+        };
+
+        if token.contains(':') {
+            parts.add_microseconds(parse_time(token)?, 1)?;
+        } else if let Some((years, months)) = split_sign(token).1.split_once('-') {
+            let sign = if split_sign(token).0 { -1 } else { 1 };
+
+            parts.add_months(sign * parse_digits(years)?, 12)?;
+            parts.add_months(sign * parse_digits(months)?, 1)?;
+        } else {
+            parts.add_days(parse_int(token)?, 1)?;
+        }
+    }
+
+    parts.finish(negative)
+}
+
+// `iso_8601`: `P[nY][nM][nW][nD][T[nH][nM][nS]]` (after the `P`)
+fn parse_iso_8601(s: &str) -> Option<PgInterval> {
+    let (date, time) = s.split_once('T').unwrap_or((s, ""));
+
+    if date.is_empty() && time.is_empty() {
+        return None;
+    }
+
+    let mut parts = IntervalParts::default();
+
+    for (value, designator) in designators(date)? {
+        match designator {
+            'Y' => parts.add_months(parse_int(value)?, 12)?,
+            'M' => parts.add_months(parse_int(value)?, 1)?,
+            'W' => parts.add_days(parse_int(value)?, 7)?,
+            'D' => parts.add_days(parse_int(value)?, 1)?,
+            _ => return None,
+        }
+    }
+
+    for (value, designator) in designators(time)? {
+        match designator {
+            'H' => parts.add_microseconds(parse_int(value)?, 3_600_000_000)?,
+            'M' => parts.add_microseconds(parse_int(value)?, 60_000_000)?,
+            'S' => parts.add_microseconds(parse_seconds(value)?, 1)?,
+            _ => return None,
+        }
+    }
+
+    parts.finish(false)
+}
+
+// splits `1Y-2M3D` into `("1", 'Y')`, `("-2", 'M')` and `("3", 'D')`
+fn designators(s: &str) -> Option<Vec<(&str, char)>> {
+    let mut designators = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        if c.is_ascii_alphabetic() {
+            if i == start {
+                return None;
+            }
+
+            designators.push((&s[start..i], c));
+            start = i + 1;
+        }
+    }
+
+    (start == s.len()).then_some(designators)
+}
+
+// the fields are summed up in `i128`, so the most negative values (whose magnitude does not fit
+// the field type) can be negated at the end
+#[derive(Default)]
+struct IntervalParts {
+    months: i128,
+    days: i128,
+    microseconds: i128,
+}
+
+impl IntervalParts {
+    fn add_months(&mut self, value: i128, scale: i128) -> Option<()> {
+        self.months = self.months.checked_add(value.checked_mul(scale)?)?;
+        Some(())
+    }
+
+    fn add_days(&mut self, value: i128, scale: i128) -> Option<()> {
+        self.days = self.days.checked_add(value.checked_mul(scale)?)?;
+        Some(())
+    }
+
+    fn add_microseconds(&mut self, value: i128, scale: i128) -> Option<()> {
+        self.microseconds = self.microseconds.checked_add(value.checked_mul(scale)?)?;
+        Some(())
+    }
+
+    fn finish(self, negative: bool) -> Option<PgInterval> {
+        let sign = if negative { -1 } else { 1 };
+
+        Some(PgInterval {
+            months: (sign * self.months).try_into().ok()?,
+            days: (sign * self.days).try_into().ok()?,
+            microseconds: (sign * self.microseconds).try_into().ok()?,
+        })
+    }
+}
+
+// `[-+]HH:MM[:SS[.ffffff]]` in microseconds
+fn parse_time(s: &str) -> Option<i128> {
+    let (negative, s) = split_sign(s);
+    let mut fields = s.splitn(3, ':');
+
+    let hours = parse_digits(fields.next()?)?;
+    let minutes = parse_digits(fields.next()?)?;
+    let seconds = fields.next().map_or(Some(0), parse_unsigned_seconds)?;
+
+    let microseconds = hours
+        .checked_mul(3_600_000_000)?
+        .checked_add(minutes.checked_mul(60_000_000)?)?
+        .checked_add(seconds)?;
+
+    Some(if negative {
+        -microseconds
+    } else {
+        microseconds
+    })
+}
+
+// `[-+]S[.ffffff]` in microseconds
+fn parse_seconds(s: &str) -> Option<i128> {
+    let (negative, s) = split_sign(s);
+    let microseconds = parse_unsigned_seconds(s)?;
+
+    Some(if negative {
+        -microseconds
+    } else {
+        microseconds
+    })
+}
+
+fn parse_unsigned_seconds(s: &str) -> Option<i128> {
+    let (seconds, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    let mut microseconds = parse_digits(seconds)?.checked_mul(1_000_000)?;
+
+    if !fraction.is_empty() {
+        // the server never prints more than microseconds
+        if fraction.len() > 6 {
+            return None;
+        }
+
+        let scale = 10_i128.pow(6 - fraction.len() as u32);
+        microseconds = microseconds.checked_add(parse_digits(fraction)? * scale)?;
+    }
+
+    Some(microseconds)
+}
+
+// `[-+]N`
+fn parse_int(s: &str) -> Option<i128> {
+    let (negative, s) = split_sign(s);
+    let value = parse_digits(s)?;
+
+    Some(if negative { -value } else { value })
+}
+
+fn parse_digits(s: &str) -> Option<i128> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+fn split_sign(s: &str) -> (bool, &str) {
+    if let Some(s) = s.strip_prefix('-') {
+        (true, s)
+    } else {
+        (false, s.strip_prefix('+').unwrap_or(s))
+    }
+}
+
 impl Encode<'_, Postgres> for PgInterval {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         buf.extend(&self.microseconds.to_be_bytes());
@@ -390,3 +654,112 @@ fn test_pginterval_time() {
     assert!(PgInterval::try_from(time::Duration::seconds(10_000_000_000_000)).is_err());
     assert!(PgInterval::try_from(time::Duration::seconds(-10_000_000_000_000)).is_err());
 }
+
+#[test]
+fn test_decode_interval_text() {
+    fn interval(months: i32, days: i32, microseconds: i64) -> Option<PgInterval> {
+        Some(PgInterval {
+            months,
+            days,
+            microseconds,
+        })
+    }
+
+    // the same intervals in every `IntervalStyle`, as printed by the server
+    let cases = [
+        (
+            interval(14, 3, 14_706_789_000),
+            [
+                "1 year 2 mons 3 days 04:05:06.789",
+                "@ 1 year 2 mons 3 days 4 hours 5 mins 6.789 secs",
+                "+1-2 +3 +4:05:06.789",
+                "P1Y2M3DT4H5M6.789S",
+            ],
+        ),
+        (
+            interval(-14, -3, -14_706_789_000),
+            [
+                "-1 years -2 mons -3 days -04:05:06.789",
+                "@ 1 year 2 mons 3 days 4 hours 5 mins 6.789 secs ago",
+                "-1-2 -3 -4:05:06.789",
+                "P-1Y-2M-3DT-4H-5M-6.789S",
+            ],
+        ),
+        (
+            interval(-10, -3, 14_706_000_000),
+            [
+                "-10 mons -3 days +04:05:06",
+                "@ 10 mons 3 days -4 hours -5 mins -6 secs ago",
+                "-0-10 -3 +4:05:06",
+                "P-10M-3DT4H5M6S",
+            ],
+        ),
+        (interval(0, 0, 0), ["00:00:00", "@ 0", "0", "PT0S"]),
+        (
+            interval(0, 1, 9_000_000_000),
+            [
+                "1 day 02:30:00",
+                "@ 1 day 2 hours 30 mins",
+                "1 2:30:00",
+                "P1DT2H30M",
+            ],
+        ),
+        (
+            interval(0, 0, -1_500_000),
+            ["-00:00:01.5", "@ 1.5 secs ago", "-0:00:01.5", "PT-1.5S"],
+        ),
+        (
+            interval(0, 0, 360_000_000_000),
+            ["100:00:00", "@ 100 hours", "100:00:00", "PT100H"],
+        ),
+        (
+            interval(-24, 0, 0),
+            ["-2 years", "@ 2 years ago", "-2-0", "P-2Y"],
+        ),
+        (interval(3, 0, 0), ["3 mons", "@ 3 mons", "0-3", "P3M"]),
+        (
+            interval(0, 1, -1),
+            [
+                "1 day -00:00:00.000001",
+                "@ 1 day -0.000001 secs",
+                "+0-0 +1 -0:00:00.000001",
+                "P1DT-0.000001S",
+            ],
+        ),
+        (
+            interval(0, -3, 0),
+            ["-3 days", "@ 3 days ago", "-3 0:00:00", "P-3D"],
+        ),
+        (
+            interval(i32::MIN, i32::MIN, i64::MIN),
+            [
+                "-178956970 years -8 mons -2147483648 days -2562047788:00:54.775808",
+                "@ 178956970 years 8 mons 2147483648 days 2562047788 hours 54.775808 secs ago",
+                "-178956970-8 -2147483648 -2562047788:00:54.775808",
+                "P-178956970Y-8M-2147483648DT-2562047788H-54.775808S",
+            ],
+        ),
+    ];
+
+    for (expected, texts) in cases {
+        for text in texts {
+            assert_eq!(parse_interval(text), expected, "{text:?}");
+        }
+    }
+
+    for text in [
+        "",
+        "P",
+        "1 fortnight",
+        "1 day 2",
+        "12:",
+        "1:2:3:4",
+        "00:00:00.1234567",
+        "PT1.5H",
+        "P1Y2",
+        "3000000000 days",
+        "@ 1 day ago ago",
+    ] {
+        assert_eq!(parse_interval(text), None, "{text:?}");
+    }
+}
@@ -6,7 +6,7 @@ use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValue
 use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue as JsonRawValue;
 use serde_json::Value as JsonValue;
-pub(crate) use sqlx_core::types::{Json, Type};
+pub(crate) use sqlx_core::types::{Json, RawJson, Type};
 
 // <https://www.postgresql.org/docs/12/datatype-json.html>
 
@@ -54,6 +54,16 @@ impl PgHasArrayType for JsonRawValue {
     }
 }
 
+impl PgHasArrayType for RawJson<'_> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::JSONB_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<RawJson<'_>>(ty)
+    }
+}
+
 impl<'q, T> Encode<'q, Postgres> for Json<T>
 where
     T: Serialize,
@@ -71,8 +81,25 @@ where
         buf.push(1);
 
         // the JSON data written to the buffer is the same regardless of parameter type
-        serde_json::to_writer(&mut **buf, &self.0)
-            .expect("failed to serialize to JSON for encoding on transmission to the database");
+        self.encode_to(buf);
+
+        IsNull::No
+    }
+}
+
+impl Encode<'_, Postgres> for RawJson<'_> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // same as `Json<T>` above, except the JSON text is copied as-is
+        buf.patch(|buf, ty: &PgTypeInfo| {
+            if *ty == PgTypeInfo::JSON || *ty == PgTypeInfo::JSON_ARRAY {
+                buf[0] = b' ';
+            }
+        });
+
+        // JSONB version (as of 2020-03-20)
+        buf.push(1);
+
+        buf.extend_from_slice(self.0.as_bytes());
 
         IsNull::No
     }
@@ -86,15 +113,31 @@ where
         let mut buf = value.as_bytes()?;
 
         if value.format() == PgValueFormat::Binary && value.type_info == PgTypeInfo::JSONB {
-            assert_eq!(
-                buf[0], 1,
-                "unsupported JSONB format version {}; please open an issue",
-                buf[0]
-            );
-
-            buf = &buf[1..];
+            match buf.split_first() {
+                // JSONB version (as of 2020-03-20)
+                Some((1, rest)) => buf = rest,
+
+                Some((version, _)) => {
+                    return Err(format!(
+                        "unsupported JSONB format version {version}; please open an issue"
+                    )
+                    .into());
+                }
+
+                None => return Err("expected JSONB format version, got empty buffer".into()),
+            }
         }
 
         serde_json::from_slice(buf).map(Json).map_err(Into::into)
     }
 }
+
+#[test]
+#[should_panic(expected = "non-string key type")]
+fn test_encode_json_with_non_string_map_keys() {
+    use std::collections::HashMap;
+
+    let value = Json(HashMap::from([((1, 2), 3)]));
+
+    let _ = Encode::<Postgres>::encode(value, &mut PgArgumentBuffer::default());
+}
@@ -12,7 +12,7 @@
 //! | `f32`                                 | REAL, FLOAT4                                         |
 //! | `f64`                                 | DOUBLE PRECISION, FLOAT8                             |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR(N), TEXT, NAME, CITEXT                 |
-//! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
+//! | `&[u8]`, `Vec<u8>`, [`PgByteaReader`], [`PgByteaStream`] | BYTEA                             |
 //! | `()`                                  | VOID                                                 |
 //! | [`PgInterval`]                        | INTERVAL                                             |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
@@ -20,6 +20,7 @@
 //! | [`PgLTree`]                           | LTREE                                                |
 //! | [`PgLQuery`]                          | LQUERY                                               |
 //! | [`PgCiText`]                          | CITEXT<sup>1</sup>                                   |
+//! | [`PgXml`]                             | XML                                                  |
 //!
 //! <sup>1</sup> SQLx generally considers `CITEXT` to be compatible with `String`, `&str`, etc.,
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
@@ -101,7 +102,7 @@
 //!
 //! | Rust type                             | Postgres type(s)                                     |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `bit_vec::BitVec`                     | BIT, VARBIT                                          |
+//! | `bit_vec::BitVec`                     | BIT, VARBIT, BOOL[] (decode only)                    |
 //!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
@@ -112,12 +113,15 @@
 //! | [`Json<T>`]                           | JSON, JSONB                                          |
 //! | `serde_json::Value`                   | JSON, JSONB                                          |
 //! | `&serde_json::value::RawValue`        | JSON, JSONB                                          |
+//! | [`RawJson<'_>`] (encode only)         | JSON, JSONB                                          |
 //!
 //! `Value` and `RawValue` from `serde_json` can be used for unstructured JSON data with
 //! Postgres.
 //!
 //! [`Json<T>`](crate::types::Json) can be used for structured JSON data with Postgres.
 //!
+//! [`RawJson<'_>`](crate::types::RawJson) binds an already-serialized JSON string as-is.
+//!
 //! # [Composite types](https://www.postgresql.org/docs/current/rowtypes.html)
 //!
 //! User-defined composite types are supported through a derive for `Type`.
@@ -195,6 +199,7 @@ mod record;
 mod str;
 mod tuple;
 mod void;
+mod xml;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 mod time_tz;
@@ -230,6 +235,7 @@ mod mac_address;
 mod bit_vec;
 
 pub use array::PgHasArrayType;
+pub use bytes::{PgByteaReader, PgByteaStream};
 pub use citext::PgCiText;
 pub use interval::PgInterval;
 pub use lquery::PgLQuery;
@@ -242,6 +248,7 @@ pub use ltree::PgLTreeParseError;
 pub use money::PgMoney;
 pub use oid::Oid;
 pub use range::PgRange;
+pub use xml::PgXml;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
@@ -63,6 +63,9 @@ pub struct PgRecordDecoder<'r> {
     typ: PgTypeInfo,
     fmt: PgValueFormat,
     ind: usize,
+    // in the text format a trailing NULL field is empty, so an empty buffer may still hold
+    // one more field; this is set once the last field (not followed by a `,`) was read
+    end: bool,
 }
 
 impl<'r> PgRecordDecoder<'r> {
@@ -88,6 +91,7 @@ impl<'r> PgRecordDecoder<'r> {
             fmt,
             typ,
             ind: 0,
+            end: false,
         })
     }
 
@@ -96,7 +100,12 @@ impl<'r> PgRecordDecoder<'r> {
     where
         T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
     {
-        if self.buf.is_empty() {
+        let exhausted = match self.fmt {
+            PgValueFormat::Binary => self.buf.is_empty(),
+            PgValueFormat::Text => self.end,
+        };
+
+        if exhausted {
             return Err(format!("no field `{0}` found on record", self.ind).into());
         }
 
@@ -147,6 +156,10 @@ impl<'r> PgRecordDecoder<'r> {
                 let mut in_escape = false;
                 let mut prev_ch = '\0';
 
+                // cleared if the field is followed by another one
+                self.end = true;
+                self.ind += 1;
+
                 while !self.buf.is_empty() {
                     let ch = self.buf.get_u8() as char;
                     match ch {
@@ -172,7 +185,10 @@ impl<'r> PgRecordDecoder<'r> {
                             in_escape = true;
                         }
 
-                        ',' if !in_quotes => break,
+                        ',' if !in_quotes => {
+                            self.end = false;
+                            break;
+                        }
 
                         _ => {
                             element.push(ch);
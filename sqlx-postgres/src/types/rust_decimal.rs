@@ -1,3 +1,5 @@
+use std::cmp;
+
 use rust_decimal::{prelude::Zero, Decimal};
 
 use crate::decode::Decode;
@@ -7,8 +9,6 @@ use crate::types::numeric::{PgNumeric, PgNumericSign};
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
-use rust_decimal::MathematicalOps;
-
 impl Type<Postgres> for Decimal {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::NUMERIC
@@ -25,7 +25,7 @@ impl TryFrom<PgNumeric> for Decimal {
     type Error = BoxDynError;
 
     fn try_from(numeric: PgNumeric) -> Result<Self, BoxDynError> {
-        let (digits, sign, mut weight, scale) = match numeric {
+        let (digits, sign, weight, scale) = match numeric {
             PgNumeric::Number {
                 digits,
                 sign,
@@ -43,29 +43,45 @@ impl TryFrom<PgNumeric> for Decimal {
             return Ok(0u64.into());
         }
 
-        let mut value = Decimal::ZERO;
+        let not_representable = || "value not representable as rust_decimal::Decimal";
+
+        // read the digits as one integer, exactly; the value is that integer times `10 ^ exponent`
+        // with the exponent derived from the weight of the last digit
+        let mut mantissa = digits
+            .iter()
+            .try_fold(0_i128, |mantissa, &digit| {
+                mantissa.checked_mul(10_000)?.checked_add(digit as i128)
+            })
+            .ok_or_else(not_representable)?;
 
-        // Sum over `digits`, multiply each by its weight and add it to `value`.
-        for digit in digits {
-            let mul = Decimal::from(10_000i16)
-                .checked_powi(weight as i64)
-                .ok_or("value not representable as rust_decimal::Decimal")?;
+        let mut exponent = (weight as i64 - digits.len() as i64 + 1) * 4;
 
-            let part = Decimal::from(digit) * mul;
+        // the last digit is padded with zeroes to a multiple of four decimal places
+        while exponent < 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            exponent += 1;
+        }
 
-            value = value
-                .checked_add(part)
-                .ok_or("value not representable as rust_decimal::Decimal")?;
+        if exponent > 0 {
+            mantissa = u32::try_from(exponent)
+                .ok()
+                .and_then(|exponent| 10_i128.checked_pow(exponent))
+                .and_then(|power| mantissa.checked_mul(power))
+                .ok_or_else(not_representable)?;
 
-            weight = weight.checked_sub(1).ok_or("weight underflowed")?;
+            exponent = 0;
         }
 
+        let mut value = Decimal::try_from_i128_with_scale(mantissa, (-exponent) as u32)
+            .map_err(|_| not_representable())?;
+
         match sign {
             PgNumericSign::Positive => value.set_sign_positive(true),
             PgNumericSign::Negative => value.set_sign_negative(true),
         }
 
-        value.rescale(scale as u32);
+        // the scale is never negative in a value sent by Postgres
+        value.rescale(cmp::max(scale, 0) as u32);
 
         Ok(value)
     }
@@ -77,9 +93,10 @@ impl TryFrom<&'_ Decimal> for PgNumeric {
     fn try_from(decimal: &Decimal) -> Result<Self, BoxDynError> {
         // `Decimal` added `is_zero()` as an inherent method in a more recent version
         if Zero::is_zero(decimal) {
+            // keep the scale so e.g. `0.00` isn't sent as `0`
             return Ok(PgNumeric::Number {
                 sign: PgNumericSign::Positive,
-                scale: 0,
+                scale: decimal.scale() as i16,
                 weight: 0,
                 digits: vec![],
             });
@@ -158,7 +175,10 @@ impl Decode<'_, Postgres> for Decimal {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         match value.format() {
             PgValueFormat::Binary => PgNumeric::decode(value.as_bytes()?)?.try_into(),
-            PgValueFormat::Text => Ok(value.as_str()?.parse::<Decimal>()?),
+            PgValueFormat::Text => match value.as_str()? {
+                "NaN" => Err("Decimal does not support NaN values".into()),
+                s => Ok(s.parse::<Decimal>()?),
+            },
         }
     }
 }
@@ -183,6 +203,21 @@ mod decimal_to_pgnumeric {
         );
     }
 
+    #[test]
+    fn zero_with_scale() {
+        let zero: Decimal = "0.00".parse().unwrap();
+
+        assert_eq!(
+            PgNumeric::try_from(&zero).unwrap(),
+            PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                scale: 2,
+                weight: 0,
+                digits: vec![]
+            }
+        );
+    }
+
     #[test]
     fn one() {
         let one: Decimal = "1".parse().unwrap();
@@ -428,3 +463,120 @@ mod decimal_to_pgnumeric {
     #[test]
     fn issue_666_trailing_zeroes_at_max_precision() {}
 }
+
+#[cfg(test)]
+mod pgnumeric_to_decimal {
+    use super::{Decimal, PgNumeric, PgNumericSign};
+    use std::convert::TryFrom;
+
+    fn decode(sign: PgNumericSign, digits: Vec<i16>, weight: i16, scale: i16) -> String {
+        Decimal::try_from(PgNumeric::Number {
+            sign,
+            digits,
+            weight,
+            scale,
+        })
+        .unwrap()
+        .to_string()
+    }
+
+    #[test]
+    fn large_integer() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![120], 2, 0),
+            "12000000000"
+        );
+    }
+
+    #[test]
+    fn negative_large_integer() {
+        assert_eq!(
+            decode(PgNumericSign::Negative, vec![1, 2345], 3, 0),
+            "-1234500000000"
+        );
+    }
+
+    #[test]
+    fn tiny_fraction() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1000], -2, 5),
+            "0.00001"
+        );
+    }
+
+    #[test]
+    fn tiny_fraction_with_integer_part() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1, 0, 1000], 0, 5),
+            "1.00001"
+        );
+    }
+
+    #[test]
+    fn power_of_ten_thousand() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![1], 1, 0), "10000");
+    }
+
+    #[test]
+    fn power_of_ten_thousand_squared() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![1], 2, 0), "100000000");
+    }
+
+    #[test]
+    fn negative_power_of_ten_thousand() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![1], -1, 4), "0.0001");
+    }
+
+    #[test]
+    fn negative_power_of_ten_thousand_squared() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1], -2, 8),
+            "0.00000001"
+        );
+    }
+
+    #[test]
+    fn leading_zero_digit() {
+        assert_eq!(decode(PgNumericSign::Positive, vec![0, 5], 0, 4), "0.0005");
+    }
+
+    #[test]
+    fn trailing_zero_digits() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1, 0, 0], 2, 0),
+            "100000000"
+        );
+    }
+
+    #[test]
+    fn scale_past_digits() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1, 5000], 0, 3),
+            "1.500"
+        );
+    }
+
+    #[test]
+    fn smallest_fraction() {
+        assert_eq!(
+            decode(PgNumericSign::Positive, vec![1], -7, 28),
+            "0.0000000000000000000000000001"
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let decode = |digits, weight, scale| {
+            Decimal::try_from(PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                digits,
+                weight,
+                scale,
+            })
+        };
+
+        assert!(decode(vec![9999], 7, 0).is_err());
+        assert!(decode(vec![1], 100, 0).is_err());
+        assert!(decode(vec![1], -8, 32).is_err());
+    }
+}
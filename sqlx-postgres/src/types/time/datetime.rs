@@ -8,7 +8,7 @@ use std::borrow::Cow;
 use std::mem;
 use time::macros::format_description;
 use time::macros::offset;
-use time::{Duration, OffsetDateTime, PrimitiveDateTime};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
 
 impl Type<Postgres> for PrimitiveDateTime {
     fn type_info() -> PgTypeInfo {
@@ -58,25 +58,42 @@ impl<'r> Decode<'r, Postgres> for PrimitiveDateTime {
             PgValueFormat::Text => {
                 let s = value.as_str()?;
 
+                // A `timestamptz` ends with the UTC offset of the session's time zone; look for
+                // its sign after the date, which contains `-` as well
+                let time_start = s.find(' ').map_or(0, |i| i + 1);
+                let (datetime, offset) = s.split_at(
+                    s[time_start..]
+                        .find(|c| c == '+' || c == '-')
+                        .map_or(s.len(), |i| time_start + i),
+                );
+
                 // If there is no decimal point we need to add one.
-                let s = if s.contains('.') {
-                    Cow::Borrowed(s)
+                let datetime = if datetime.contains('.') {
+                    Cow::Borrowed(datetime)
                 } else {
-                    Cow::Owned(format!("{s}.0"))
+                    Cow::Owned(format!("{datetime}.0"))
                 };
 
-                // Contains a time-zone specifier
-                // This is given for timestamptz for some reason
-                // Postgres already guarantees this to always be UTC
-                if s.contains('+') {
-                    PrimitiveDateTime::parse(&*s, &format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond][offset_hour]"))?
+                let datetime = PrimitiveDateTime::parse(
+                    &datetime,
+                    &format_description!(
+                        "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
+                    ),
+                )?;
+
+                if offset.is_empty() {
+                    datetime
                 } else {
-                    PrimitiveDateTime::parse(
-                        &*s,
+                    // the offset may include minutes and seconds, e.g. `+05:30`
+                    let offset = UtcOffset::parse(
+                        offset,
                         &format_description!(
-                            "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
+                            "[offset_hour sign:mandatory][optional [:[offset_minute]]][optional [:[offset_second]]]"
                         ),
-                    )?
+                    )?;
+
+                    let utc = datetime.assume_offset(offset).to_offset(offset!(UTC));
+                    PrimitiveDateTime::new(utc.date(), utc.time())
                 }
             }
         })
@@ -0,0 +1,79 @@
+use crate::types::array_compatible;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+
+/// XML document or content fragment (`xml`) support for Postgres.
+///
+/// The document is transported as a string; SQLx does not parse or validate it.
+/// Postgres checks that the value is well-formed when it is stored or cast to `xml`.
+///
+/// See [the Postgres manual, Section 8.13][PG.8.13] for details on the `xml` type.
+///
+/// [PG.8.13]: https://www.postgresql.org/docs/current/datatype-xml.html
+///
+/// ### Note: Server Support Required
+/// The `xml` type is only usable if the server was built with `libxml` (`--with-libxml`),
+/// which is the case for most distributions of Postgres.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgXml(pub String);
+
+impl Type<Postgres> for PgXml {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::XML
+    }
+}
+
+impl Deref for PgXml {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_str()
+    }
+}
+
+impl From<String> for PgXml {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PgXml> for String {
+    fn from(value: PgXml) -> Self {
+        value.0
+    }
+}
+
+impl Display for PgXml {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PgHasArrayType for PgXml {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::XML_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<PgXml>(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for PgXml {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // the binary format of `xml` is the document as text in the client encoding
+        <&str as Encode<Postgres>>::encode(&**self, buf)
+    }
+}
+
+impl Decode<'_, Postgres> for PgXml {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(PgXml(value.as_str()?.to_owned()))
+    }
+}
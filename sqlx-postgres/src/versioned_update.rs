@@ -0,0 +1,150 @@
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query_builder::QueryBuilder;
+use crate::types::Type;
+use crate::{PgConnection, PgQueryResult, Postgres};
+
+/// Builds an `UPDATE` of a single row guarded by a version column, for optimistic
+/// concurrency control.
+///
+/// The generated statement has the form:
+///
+/// ```sql
+/// UPDATE <table> SET <column> = $1, ..., <version> = <version> + 1
+/// WHERE <key> = $N AND <version> = $M
+/// ```
+///
+/// If no row matched, [`execute()`][Self::execute] checks whether a row with the key exists
+/// at all to tell the two cases apart: it returns [`PgVersionedUpdateError::Conflict`] if the
+/// row exists with a different version, and [`Error::RowNotFound`] if it does not.
+///
+/// ### Note: Identifiers are not Escaped
+/// The table and column names are inserted into the query as-is, just like
+/// [`QueryBuilder::push()`], so they must not come from untrusted input.
+/// Values are always sent as bind arguments.
+///
+/// # Example
+///
+/// ```rust
+/// use sqlx::postgres::{PgConnection, PgVersionedUpdate, PgVersionedUpdateError};
+///
+/// # pub async fn _f(conn: &mut PgConnection) -> Result<(), PgVersionedUpdateError> {
+/// // the caller read the account at version 3
+/// match PgVersionedUpdate::new("accounts", "id", "version")
+///     .set("balance", 100_i64)
+///     .execute(conn, 1_i64, 3_i32)
+///     .await
+/// {
+///     Ok(_) => {}
+///     Err(PgVersionedUpdateError::Conflict) => { /* re-read the account and try again */ }
+///     Err(e) => return Err(e),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PgVersionedUpdate<'args> {
+    table: String,
+    key_column: String,
+    version_column: String,
+    query: QueryBuilder<'args, Postgres>,
+}
+
+impl<'args> PgVersionedUpdate<'args> {
+    /// Start an update of a row in `table`, identified by `key_column`,
+    /// whose version is kept in `version_column`.
+    pub fn new(
+        table: impl Into<String>,
+        key_column: impl Into<String>,
+        version_column: impl Into<String>,
+    ) -> Self {
+        let table = table.into();
+        let query = QueryBuilder::new(format!("UPDATE {table} SET "));
+
+        Self {
+            table,
+            key_column: key_column.into(),
+            version_column: version_column.into(),
+            query,
+        }
+    }
+
+    /// Set `column` to `value`.
+    pub fn set<T>(mut self, column: &str, value: T) -> Self
+    where
+        T: 'args + Encode<'args, Postgres> + Send + Type<Postgres>,
+    {
+        self.query
+            .push(column)
+            .push(" = ")
+            .push_bind(value)
+            .push(", ");
+        self
+    }
+
+    /// Execute the update, provided the row identified by `key` is still at `expected_version`,
+    /// and increment its version.
+    ///
+    /// Returns [`PgVersionedUpdateError::Conflict`] if the row has a different version and
+    /// [`Error::RowNotFound`] if there is no row with this key.
+    pub async fn execute<K, V>(
+        mut self,
+        conn: &mut PgConnection,
+        key: K,
+        expected_version: V,
+    ) -> Result<PgQueryResult, PgVersionedUpdateError>
+    where
+        K: 'args + Encode<'args, Postgres> + Send + Type<Postgres> + Clone,
+        V: 'args + Encode<'args, Postgres> + Send + Type<Postgres>,
+    {
+        let Self {
+            table,
+            key_column,
+            version_column,
+            ref mut query,
+        } = self;
+
+        query
+            .push(format_args!(
+                "{version_column} = {version_column} + 1 WHERE {key_column} = "
+            ))
+            .push_bind(key.clone())
+            .push(format_args!(" AND {version_column} = "))
+            .push_bind(expected_version);
+
+        let result = conn.execute(query.build()).await?;
+
+        if result.rows_affected() > 0 {
+            return Ok(result);
+        }
+
+        let mut exists_query = QueryBuilder::<Postgres>::new(format!(
+            "SELECT EXISTS (SELECT 1 FROM {table} WHERE {key_column} = "
+        ));
+        exists_query.push_bind(key).push(")");
+
+        let exists: bool = exists_query
+            .build_query_scalar()
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Err(if exists {
+            PgVersionedUpdateError::Conflict
+        } else {
+            Error::RowNotFound.into()
+        })
+    }
+}
+
+/// The error returned by [`PgVersionedUpdate::execute()`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PgVersionedUpdateError {
+    /// The row was modified since the expected version was read.
+    #[error("row was modified concurrently: its version no longer matches the expected one")]
+    Conflict,
+
+    /// The update failed, or there is no row with the given key ([`Error::RowNotFound`]).
+    #[error(transparent)]
+    Execute(#[from] Error),
+}
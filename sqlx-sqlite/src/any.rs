@@ -110,11 +110,19 @@ impl AnyConnectionBackend for SqliteConnection {
                 .await?;
             futures_util::pin_mut!(stream);
 
-            if let Some(Either::Right(row)) = stream.try_next().await? {
-                return Ok(Some(AnyRow::try_from(&row)?));
+            let mut row = None;
+
+            while let Some(res) = stream.try_next().await? {
+                if let Either::Right(r) = res {
+                    if row.is_some() {
+                        return Err(sqlx_core::Error::TooManyRows);
+                    }
+
+                    row = Some(AnyRow::try_from(&r)?);
+                }
             }
 
-            Ok(None)
+            Ok(row)
         })
     }
 
@@ -27,7 +27,7 @@ pub(crate) fn iter<'a>(
     // fetch the cached statement or allocate a new one
     let statement = conn.statements.get(query, persistent)?;
 
-    let logger = QueryLogger::new(query, conn.log_settings.clone());
+    let logger = QueryLogger::new(query, "sqlite", None, conn.log_settings.clone());
 
     Ok(ExecuteIter {
         handle: &mut conn.handle,
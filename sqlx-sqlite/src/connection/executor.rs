@@ -6,7 +6,7 @@ use futures_core::stream::BoxStream;
 use futures_util::{TryFutureExt, TryStreamExt};
 use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
-use sqlx_core::executor::{Execute, Executor};
+use sqlx_core::executor::{run_until, stream_until, Execute, Executor};
 use sqlx_core::Either;
 
 impl<'c> Executor<'c> for &'c mut SqliteConnection {
@@ -24,11 +24,14 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
 
-        Box::pin(
-            self.worker
-                .execute(sql, arguments, self.row_channel_size, persistent)
-                .map_ok(flume::Receiver::into_stream)
-                .try_flatten_stream(),
+        stream_until(
+            query.deadline(),
+            Box::pin(
+                self.worker
+                    .execute(sql, arguments, self.row_channel_size, persistent)
+                    .map_ok(flume::Receiver::into_stream)
+                    .try_flatten_stream(),
+            ),
         )
     }
 
@@ -44,7 +47,7 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
 
-        Box::pin(async move {
+        Box::pin(run_until(query.deadline(), async move {
             let stream = self
                 .worker
                 .execute(sql, arguments, self.row_channel_size, persistent)
@@ -53,14 +56,20 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
 
             futures_util::pin_mut!(stream);
 
+            let mut row = None;
+
             while let Some(res) = stream.try_next().await? {
-                if let Either::Right(row) = res {
-                    return Ok(Some(row));
+                if let Either::Right(r) = res {
+                    if row.is_some() {
+                        return Err(Error::TooManyRows);
+                    }
+
+                    row = Some(r);
                 }
             }
 
-            Ok(None)
-        })
+            Ok(row)
+        }))
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -4,7 +4,7 @@ use futures_util::future;
 use libsqlite3_sys::{sqlite3, sqlite3_progress_handler};
 use sqlx_core::common::StatementCache;
 use sqlx_core::error::Error;
-use sqlx_core::transaction::Transaction;
+use sqlx_core::transaction::{Transaction, TransactionOptions};
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
 use std::os::raw::{c_int, c_void};
@@ -166,6 +166,16 @@ impl Connection for SqliteConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.worker
             .shared
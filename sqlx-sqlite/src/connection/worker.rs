@@ -53,6 +53,8 @@ enum Command {
         arguments: Option<SqliteArguments<'static>>,
         persistent: bool,
         tx: flume::Sender<Result<Either<SqliteQueryResult, SqliteRow>, Error>>,
+        // the span the query was executed in, so its span and events on the worker nest under it
+        span: tracing::Span,
     },
     Begin {
         tx: rendezvous_oneshot::Sender<Result<(), Error>>,
@@ -137,7 +139,10 @@ impl ConnectionWorker {
                             arguments,
                             persistent,
                             tx,
+                            span,
                         } => {
+                            let _guard = span.enter();
+
                             let iter = match execute::iter(&mut conn, &query, arguments, persistent)
                             {
                                 Ok(iter) => iter,
@@ -294,6 +299,7 @@ impl ConnectionWorker {
                 arguments: args.map(SqliteArguments::into_static),
                 persistent,
                 tx,
+                span: tracing::Span::current(),
             })
             .await
             .map_err(|_| Error::WorkerCrashed)?;
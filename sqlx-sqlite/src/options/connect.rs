@@ -55,6 +55,11 @@ impl ConnectOptions for SqliteConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_full_statements(mut self, enabled: bool) -> Self {
+        self.log_settings.log_full_statements(enabled);
+        self
+    }
 }
 
 impl SqliteConnectOptions {
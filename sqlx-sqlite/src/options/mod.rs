@@ -211,6 +211,22 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Get the name of the database file.
+    ///
+    /// For an in-memory database, this is the name SQLx uses to share it between connections.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_sqlite::SqliteConnectOptions;
+    /// # use std::path::Path;
+    /// let options = SqliteConnectOptions::new().filename("data.db");
+    /// assert_eq!(options.get_filename(), Path::new("data.db"));
+    /// ```
+    pub fn get_filename(&self) -> &Path {
+        &self.filename
+    }
+
     /// Set the enforcement of [foreign key constraints](https://www.sqlite.org/pragma.html#pragma_foreign_keys).
     ///
     /// SQLx chooses to enable this by default so that foreign keys function as expected,
@@ -266,6 +282,11 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Get whether the database is opened for read-only access.
+    pub fn get_read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// Sets the [access mode](https://www.sqlite.org/c3ref/open.html) to create the database file
     /// if the file does not exist.
     ///
@@ -275,6 +296,11 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Get whether the database file is created if it does not exist.
+    pub fn get_create_if_missing(&self) -> bool {
+        self.create_if_missing
+    }
+
     /// Sets the capacity of the connection's statement cache in a number of stored
     /// distinct statements. Caching is handled using LRU, meaning when the
     /// amount of queries hits the defined limit, the oldest statement will get
@@ -286,6 +312,11 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Get the capacity of the connection's statement cache.
+    pub fn get_statement_cache_capacity(&self) -> usize {
+        self.statement_cache_capacity
+    }
+
     /// Sets a timeout value to wait when the database is locked, before
     /// returning a busy timeout error.
     ///
@@ -295,6 +326,11 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Get the timeout to wait when the database is locked.
+    pub fn get_busy_timeout(&self) -> Duration {
+        self.busy_timeout
+    }
+
     /// Sets the [synchronous](https://www.sqlite.org/pragma.html#pragma_synchronous) setting for the database connection.
     ///
     /// The default synchronous settings is FULL. However, if durability is not a concern,
@@ -334,6 +370,24 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Get the value of an initial pragma, if one is set.
+    ///
+    /// This includes the pragmas set by other options, such as
+    /// [`journal_mode`][Self::journal_mode] or [`foreign_keys`][Self::foreign_keys].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_sqlite::{SqliteConnectOptions, SqliteJournalMode};
+    /// let options = SqliteConnectOptions::new().journal_mode(SqliteJournalMode::Wal);
+    /// assert_eq!(options.get_pragma("journal_mode"), Some("WAL"));
+    /// assert_eq!(options.get_pragma("foreign_keys"), Some("ON"));
+    /// assert_eq!(options.get_pragma("page_size"), None);
+    /// ```
+    pub fn get_pragma(&self, key: &str) -> Option<&str> {
+        self.pragmas.get(key)?.as_deref()
+    }
+
     /// Add a custom collation for comparing strings in SQL.
     ///
     /// If a collation with the same name already exists, it will be replaced.
@@ -169,3 +169,13 @@ fn test_parse_shared_in_memory() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_parse_read_write_create() -> Result<(), Error> {
+    let options: SqliteConnectOptions = "sqlite://a.db?mode=rwc".parse()?;
+    assert!(options.get_create_if_missing());
+    assert!(!options.get_read_only());
+    assert_eq!(options.get_filename(), std::path::Path::new("a.db"));
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::types::{Json, Type};
+use crate::types::{Json, RawJson, Type};
 use crate::{type_info::DataType, Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
 
 impl<T> Type<Sqlite> for Json<T> {
@@ -25,6 +25,12 @@ where
     }
 }
 
+impl<'q> Encode<'q, Sqlite> for RawJson<'q> {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        Encode::<Sqlite>::encode(self.0, buf)
+    }
+}
+
 impl<'r, T> Decode<'r, Sqlite> for Json<T>
 where
     T: 'r + Deserialize<'r>,
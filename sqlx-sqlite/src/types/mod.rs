@@ -73,6 +73,7 @@
 //! | [`Json<T>`]                           | TEXT                                                 |
 //! | `serde_json::JsonValue`               | TEXT                                                 |
 //! | `&serde_json::value::RawValue`        | TEXT                                                 |
+//! | [`RawJson<'_>`] (encode only)         | TEXT                                                 |
 //!
 //! # Nullable
 //!
@@ -1,5 +1,5 @@
 use sqlx::any::AnyRow;
-use sqlx::{Any, Connection, Executor, Row};
+use sqlx::{Any, Connection, Executor, Row, TransactionOptions};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -44,6 +44,40 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_enforces_row_count_for_fetch_one_and_fetch_optional() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<Any>().await?;
+
+    let res = sqlx::query_scalar::<_, i32>("SELECT 1 WHERE 1 = 0")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::RowNotFound)));
+
+    let res = sqlx::query_scalar::<_, i32>("SELECT 1 UNION ALL SELECT 2")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    let res = sqlx::query_scalar::<_, i32>("SELECT 1 UNION ALL SELECT 2")
+        .fetch_optional(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    let value: Option<i32> = sqlx::query_scalar("SELECT 1 WHERE 1 = 0")
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(value, None);
+
+    let values: Vec<i32> = sqlx::query_scalar("SELECT 1 UNION ALL SELECT 2")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(values, [1, 2]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_does_not_stop_stream_after_decoding_error() -> anyhow::Result<()> {
     use futures::stream::StreamExt;
@@ -141,3 +175,47 @@ async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_can_begin_with_options() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<Any>().await?;
+    let is_sqlite = conn.backend_name() == "SQLite";
+
+    let res = conn
+        .begin_with(TransactionOptions::new().read_only(true))
+        .await;
+
+    if is_sqlite {
+        assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+        return Ok(());
+    }
+
+    let mut tx = res?;
+
+    // the options are applied by the underlying driver
+    let res = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut *tx)
+        .await;
+
+    assert!(res.is_err());
+
+    tx.rollback().await?;
+
+    let pool = sqlx_test::pool::<Any>().await?;
+
+    let mut tx = pool
+        .begin_with(TransactionOptions::new().read_only(true))
+        .await?;
+
+    let res = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut *tx)
+        .await;
+
+    assert!(res.is_err());
+
+    tx.rollback().await?;
+
+    Ok(())
+}
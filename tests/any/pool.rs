@@ -71,6 +71,35 @@ async fn pool_should_be_returned_failed_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn pool_should_close_connections_past_max_lifetime_on_release() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(Duration::from_secs(1))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    sqlx_core::rt::sleep(Duration::from_millis(1200)).await;
+    drop(conn);
+
+    // the reaper only runs again two seconds in
+    sqlx_core::rt::timeout(Duration::from_millis(500), async {
+        while pool.size() > 0 {
+            sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the expired connection should be closed when it is released");
+
+    assert_eq!(pool.num_idle(), 0);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_pool_callbacks() -> anyhow::Result<()> {
     sqlx::any::install_default_drivers();
@@ -1,4 +1,5 @@
-use sqlx::{error::ErrorKind, mysql::MySql, Connection};
+use sqlx::mysql::{MySql, MySqlDatabaseError};
+use sqlx::{error::ErrorKind, Connection};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -18,6 +19,11 @@ async fn it_fails_with_unique_violation() -> anyhow::Result<()> {
     let err = err.into_database_error().unwrap();
 
     assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(err.code().as_deref(), Some("23000"));
+
+    let err = err.downcast::<MySqlDatabaseError>();
+
+    assert_eq!(err.number(), 1062);
 
     Ok(())
 }
@@ -53,6 +59,7 @@ async fn it_fails_with_not_null_violation() -> anyhow::Result<()> {
     let err = err.into_database_error().unwrap();
 
     assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+    assert!(err.is_not_null_violation());
 
     Ok(())
 }
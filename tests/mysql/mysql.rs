@@ -1,6 +1,13 @@
+use futures::stream::BoxStream;
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::mysql::{
+    MySql, MySqlConnectOptions, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlQueryResult,
+    MySqlRow,
+};
+use sqlx::{
+    Column, Connection, Either, Executor, IsolationLevel, QueryBuilder, Row, Statement,
+    TransactionOptions, TypeInfo,
+};
 use sqlx_test::{new, setup_if_needed};
 use std::env;
 
@@ -14,6 +21,50 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fails_to_ping_a_killed_connection() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let mut admin = new::<MySql>().await?;
+
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    admin.execute(&*format!("KILL CONNECTION {id}")).await?;
+
+    assert!(conn.ping().await.is_err());
+
+    // the connection stays unusable
+    assert!(conn.ping().await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_tracks_round_trip_latency() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    for _ in 0..5 {
+        conn.ping().await?;
+    }
+
+    let fast = conn.latency_ewma().expect("pings should be measured");
+
+    // the server only responds once the sleep is over
+    sqlx::query("SELECT SLEEP(?)")
+        .bind(0.2_f64)
+        .execute(&mut conn)
+        .await?;
+
+    let slow = conn.latency_ewma().unwrap();
+
+    // the sample is weighted by 1/8
+    assert!(slow > fast, "{slow:?} <= {fast:?}");
+    assert!(slow >= std::time::Duration::from_millis(25), "{slow:?}");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_maths() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -29,6 +80,131 @@ async fn it_maths() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_enforces_row_count_for_fetch_one_and_fetch_optional() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    const QUERY: &str =
+        "SELECT v FROM (SELECT 1 AS v UNION ALL SELECT 2 UNION ALL SELECT 2) t WHERE v = ?";
+
+    // zero rows
+    let res: Result<i64, _> = sqlx::query_scalar(QUERY)
+        .bind(0_i64)
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::RowNotFound)));
+
+    let v: Option<i64> = sqlx::query_scalar(QUERY)
+        .bind(0_i64)
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(v, None);
+
+    // one row
+    let v: i64 = sqlx::query_scalar(QUERY)
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 1);
+
+    let v: Option<i64> = sqlx::query_scalar(QUERY)
+        .bind(1_i64)
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(v, Some(1));
+
+    // two rows
+    let res: Result<i64, _> = sqlx::query_scalar(QUERY)
+        .bind(2_i64)
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    let res: Result<Option<i64>, _> = sqlx::query_scalar(QUERY)
+        .bind(2_i64)
+        .fetch_optional(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    // the rest of the result set was discarded
+    let v: i64 = sqlx::query_scalar(QUERY)
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_rows_affected_and_last_insert_id() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE affected_test (id INTEGER PRIMARY KEY AUTO_INCREMENT, v INTEGER NOT NULL)",
+    )
+    .await?;
+
+    let done = sqlx::query("INSERT INTO affected_test (v) VALUES (1)")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 1);
+    assert_eq!(done.last_insert_id(), 1);
+
+    // the id generated for the first of the inserted rows
+    let done = sqlx::query("INSERT INTO affected_test (v) VALUES (2), (3), (4), (5)")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 4);
+    assert_eq!(done.last_insert_id(), 2);
+
+    // matches three rows
+    let done = sqlx::query("UPDATE affected_test SET v = v * 10 WHERE v > ?")
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 3);
+    assert_eq!(done.last_insert_id(), 0);
+
+    let done = sqlx::query("DELETE FROM affected_test WHERE v < 10")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_execute_and_drain_results() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE drain_test (id INTEGER PRIMARY KEY AUTO_INCREMENT, v TEXT NOT NULL)",
+    )
+    .await?;
+
+    let done = conn
+        .execute_drain(
+            "INSERT INTO drain_test (v) VALUES ('a'), ('b'), ('c'); SELECT id, v FROM drain_test",
+        )
+        .await?;
+
+    assert_eq!(done.rows_affected(), 3);
+
+    // the connection should be immediately reusable
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM drain_test")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_fail_at_querying() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -238,142 +414,477 @@ async fn it_caches_statements() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_can_bind_null_and_non_null_issue_540() -> anyhow::Result<()> {
+async fn it_closes_statements_that_are_not_cached() -> anyhow::Result<()> {
+    async fn closed_statements(conn: &mut MySqlConnection) -> anyhow::Result<u64> {
+        let row = conn
+            .fetch_one("SHOW SESSION STATUS LIKE 'Com_stmt_close'")
+            .await?;
+
+        Ok(row.get::<String, _>(1).parse()?)
+    }
+
     let mut conn = new::<MySql>().await?;
 
-    let row = sqlx::query("SELECT ?, ?")
-        .bind(50_i32)
-        .bind(None::<i32>)
-        .fetch_one(&mut conn)
+    let before = closed_statements(&mut conn).await?;
+
+    for i in 0..3 {
+        let row = sqlx::query("SELECT ? AS val")
+            .bind(i)
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: u32 = row.get("val");
+
+        assert_eq!(i, val);
+    }
+
+    assert_eq!(before + 3, closed_statements(&mut conn).await?);
+    assert_eq!(0, conn.cached_statements_size());
+
+    // describing a query prepares it only for as long as it takes
+    conn.describe("SELECT ? AS val").await?;
+
+    assert_eq!(before + 4, closed_statements(&mut conn).await?);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_explicitly_prepared_statements_until_dropped() -> anyhow::Result<()> {
+    async fn status(conn: &mut MySqlConnection, name: &str) -> anyhow::Result<u64> {
+        let row = conn
+            .fetch_one(&*format!("SHOW SESSION STATUS LIKE '{name}'"))
+            .await?;
+
+        Ok(row.get::<String, _>(1).parse()?)
+    }
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn =
+        MySqlConnection::connect_with(&options.clone().statement_cache_capacity(1)).await?;
+
+    let statement = conn.prepare("SELECT ? AS val").await?;
+    let prepared = status(&mut conn, "Com_stmt_prepare").await?;
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    for i in 0..3 {
+        let val: i32 = statement
+            .query_scalar()
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(i, val);
+
+        // evicts whatever is in the cache, which the statement is not part of
+        sqlx::query(&format!("SELECT {i} + ?"))
+            .bind(0)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    // only the other queries were prepared; every execution used the statement as it was
+    assert_eq!(prepared + 3, status(&mut conn, "Com_stmt_prepare").await?);
+
+    // on another connection, it is prepared like any other query
+    let mut other = MySqlConnection::connect_with(&options).await?;
+
+    let val: i32 = statement
+        .query_scalar()
+        .bind(42)
+        .fetch_one(&mut other)
         .await?;
 
-    let v0: Option<i32> = row.get(0);
-    let v1: Option<i32> = row.get(1);
+    assert_eq!(42, val);
 
-    assert_eq!(v0, Some(50));
-    assert_eq!(v1, None);
+    let closed = status(&mut conn, "Com_stmt_close").await?;
+
+    // a copy keeps it prepared
+    let copy = statement.clone();
+    drop(statement);
+
+    assert_eq!(closed, status(&mut conn, "Com_stmt_close").await?);
+
+    // it is closed on the next use of the connection after the last copy is dropped
+    drop(copy);
+
+    assert_eq!(closed + 1, status(&mut conn, "Com_stmt_close").await?);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_bind_only_null_issue_540() -> anyhow::Result<()> {
+async fn it_can_describe_a_cached_statement() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
 
-    let row = sqlx::query("SELECT ?")
-        .bind(None::<i32>)
+    let val: i32 = sqlx::query_scalar("SELECT ? AS val")
+        .bind(1)
         .fetch_one(&mut conn)
         .await?;
 
-    let v0: Option<i32> = row.get(0);
+    assert_eq!(1, val);
+    assert_eq!(1, conn.cached_statements_size());
 
-    assert_eq!(v0, None);
+    // describing the query must not close the statement in the cache
+    let describe = conn.describe("SELECT ? AS val").await?;
+    assert_eq!(describe.columns()[0].name(), "val");
+
+    let val: i32 = sqlx::query_scalar("SELECT ? AS val")
+        .bind(2)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2, val);
+    assert_eq!(1, conn.cached_statements_size());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_bind_and_return_years() -> anyhow::Result<()> {
-    let mut conn = new::<MySql>().await?;
+async fn it_prepares_statements_on_connect() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    conn.execute(
-        r#"
-CREATE TEMPORARY TABLE too_many_years (
-    id INT PRIMARY KEY AUTO_INCREMENT,
-    the YEAR NOT NULL
-);
-    "#,
-    )
-    .await?;
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
 
-    sqlx::query(
-        r#"
-INSERT INTO too_many_years ( the ) VALUES ( ? );
-    "#,
+    let mut conn = MySqlConnection::connect_with(
+        &options
+            .clone()
+            .prepare_on_connect(["SELECT ? AS val", "SELECT 1 + 1"]),
     )
-    .bind(2142)
-    .execute(&mut conn)
     .await?;
 
-    let the: u16 = sqlx::query_scalar("SELECT the FROM too_many_years")
+    assert_eq!(2, conn.cached_statements_size());
+
+    let val: i32 = sqlx::query_scalar("SELECT ? AS val")
+        .bind(5_i32)
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(the, 2142);
+    assert_eq!(5, val);
+    assert_eq!(2, conn.cached_statements_size());
+
+    let err = MySqlConnection::connect_with(
+        &options.prepare_on_connect(["SELECT 1", "SELECT * FROM not_a_table"]),
+    )
+    .await
+    .unwrap_err();
+
+    // the error for the statement is returned as is
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("42S02")
+    );
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
-    let mut conn = new::<MySql>().await?;
-    let mut tx = conn.begin().await?;
-
-    let tweet_id: u64 = sqlx::query("INSERT INTO tweet ( text ) VALUES ( 'Hello, World' )")
-        .execute(&mut *tx)
-        .await?
-        .last_insert_id();
-
-    let statement = tx.prepare("SELECT * FROM tweet WHERE id = ?").await?;
+async fn it_rejects_writes_on_read_only_connection() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    assert_eq!(statement.column(0).name(), "id");
-    assert_eq!(statement.column(1).name(), "created_at");
-    assert_eq!(statement.column(2).name(), "text");
-    assert_eq!(statement.column(3).name(), "owner_id");
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = MySqlConnection::connect_with(&options.read_only(true)).await?;
 
-    assert_eq!(statement.column(0).type_info().name(), "BIGINT");
-    assert_eq!(statement.column(1).type_info().name(), "TIMESTAMP");
-    assert_eq!(statement.column(2).type_info().name(), "TEXT");
-    assert_eq!(statement.column(3).type_info().name(), "BIGINT");
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
 
-    let row = statement.query().bind(tweet_id).fetch_one(&mut *tx).await?;
-    let tweet_text: &str = row.try_get("text")?;
+    let err = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
 
-    assert_eq!(tweet_text, "Hello, World");
+    // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("25006")
+    );
 
     Ok(())
 }
 
-// repro is more reliable with the basic scheduler used by `#[tokio::test]`
-#[cfg(feature = "_rt-tokio")]
-#[tokio::test]
-async fn test_issue_622() -> anyhow::Result<()> {
-    use std::time::Instant;
-
+// run with `cargo test --features mysql -- --ignored it_connects_over_unix_socket`
+// on the host of the server
+#[cfg(unix)]
+#[ignore = "requires the server's Unix socket to be reachable from the test"]
+#[sqlx_macros::test]
+async fn it_connects_over_unix_socket() -> anyhow::Result<()> {
     setup_if_needed();
 
-    let pool = MySqlPoolOptions::new()
-        .max_connections(1) // also fails with higher counts, e.g. 5
-        .connect(&std::env::var("DATABASE_URL").unwrap())
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let socket: String = sqlx::query_scalar("SELECT @@socket")
+        .fetch_one(&mut conn)
         .await?;
 
-    println!("pool state: {pool:?}");
+    assert!(
+        std::path::Path::new(&socket).exists(),
+        "the server's socket {socket:?} does not exist on this host"
+    );
 
-    let mut handles = vec![];
+    // TLS is never negotiated over the socket, so requiring it must not fail
+    let mut conn = MySqlConnection::connect_with(
+        &options
+            .socket(&socket)
+            .ssl_mode(sqlx::mysql::MySqlSslMode::Required),
+    )
+    .await?;
 
-    // given repro spawned 100 tasks but I found it reliably reproduced with 3
-    for i in 0..3 {
-        let pool = pool.clone();
+    let row = conn
+        .fetch_one("SHOW SESSION STATUS LIKE 'Ssl_cipher'")
+        .await?;
+    let cipher: String = row.try_get(1)?;
 
-        handles.push(sqlx_core::rt::spawn(async move {
-            {
-                let mut conn = pool.acquire().await.unwrap();
+    assert_eq!(cipher, "");
 
-                let _ = sqlx::query("SELECT 1").fetch_one(&mut *conn).await.unwrap();
+    Ok(())
+}
 
-                // conn gets dropped here and should be returned to the pool
-            }
+#[sqlx_macros::test]
+async fn it_can_change_user() -> anyhow::Result<()> {
+    setup_if_needed();
 
-            // (do some other work here without holding on to a connection)
-            // this actually fixes the issue, depending on the timeout used
-            // sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = MySqlConnection::connect_with(&options).await?;
 
-            {
-                let start = Instant::now();
-                match pool.acquire().await {
-                    Ok(conn) => {
-                        println!("{} acquire took {:?}", i, start.elapsed());
-                        drop(conn);
+    let database: String = sqlx::query_scalar("SELECT DATABASE()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    conn.execute("DROP USER IF EXISTS 'sqlx_change_user'@'%'")
+        .await?;
+    conn.execute("CREATE USER 'sqlx_change_user'@'%' IDENTIFIED BY 'change user password'")
+        .await?;
+    conn.execute(&*format!(
+        "GRANT SELECT ON `{database}`.* TO 'sqlx_change_user'@'%'"
+    ))
+    .await?;
+
+    let original: String = sqlx::query_scalar("SELECT CURRENT_USER()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    conn.execute("SET @before_change_user = 1").await?;
+
+    conn.change_user(
+        &options
+            .clone()
+            .username("sqlx_change_user")
+            .password("change user password"),
+    )
+    .await?;
+
+    let user: String = sqlx::query_scalar("SELECT CURRENT_USER()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(user, "sqlx_change_user@%");
+
+    // the session is reset
+    let reset: i64 = sqlx::query_scalar("SELECT @before_change_user IS NULL")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(reset, 1);
+
+    // while the session settings SQLx applies on connect are restored
+    let (time_zone, concat): (String, String) =
+        sqlx::query_as("SELECT @@session.time_zone, 'a' || 'b'")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(time_zone, "+00:00");
+    assert_eq!(concat, "ab");
+
+    conn.change_user(&options).await?;
+
+    let user: String = sqlx::query_scalar("SELECT CURRENT_USER()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(user, original);
+
+    conn.execute("DROP USER 'sqlx_change_user'@'%'").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_bind_null_and_non_null_issue_540() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let row = sqlx::query("SELECT ?, ?")
+        .bind(50_i32)
+        .bind(None::<i32>)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let v0: Option<i32> = row.get(0);
+    let v1: Option<i32> = row.get(1);
+
+    assert_eq!(v0, Some(50));
+    assert_eq!(v1, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_bind_only_null_issue_540() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let row = sqlx::query("SELECT ?")
+        .bind(None::<i32>)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let v0: Option<i32> = row.get(0);
+
+    assert_eq!(v0, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_mismatched_argument_count() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let res = sqlx::query("SELECT ?, ?")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await;
+
+    let err = res.unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::Protocol(msg) if msg == "expected 2 arguments for query but 1 were bound"),
+        "{err:?}"
+    );
+
+    let res = sqlx::query("SELECT ?")
+        .bind(1_i32)
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await;
+
+    let err = res.unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::Protocol(msg) if msg == "expected 1 arguments for query but 2 were bound"),
+        "{err:?}"
+    );
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(3_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_bind_and_return_years() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE too_many_years (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    the YEAR NOT NULL
+);
+    "#,
+    )
+    .await?;
+
+    sqlx::query(
+        r#"
+INSERT INTO too_many_years ( the ) VALUES ( ? );
+    "#,
+    )
+    .bind(2142)
+    .execute(&mut conn)
+    .await?;
+
+    let the: u16 = sqlx::query_scalar("SELECT the FROM too_many_years")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(the, 2142);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let mut tx = conn.begin().await?;
+
+    let tweet_id: u64 = sqlx::query("INSERT INTO tweet ( text ) VALUES ( 'Hello, World' )")
+        .execute(&mut *tx)
+        .await?
+        .last_insert_id();
+
+    let statement = tx.prepare("SELECT * FROM tweet WHERE id = ?").await?;
+
+    assert_eq!(statement.column(0).name(), "id");
+    assert_eq!(statement.column(1).name(), "created_at");
+    assert_eq!(statement.column(2).name(), "text");
+    assert_eq!(statement.column(3).name(), "owner_id");
+
+    assert_eq!(statement.column(0).type_info().name(), "BIGINT");
+    assert_eq!(statement.column(1).type_info().name(), "TIMESTAMP");
+    assert_eq!(statement.column(2).type_info().name(), "TEXT");
+    assert_eq!(statement.column(3).type_info().name(), "BIGINT");
+
+    let row = statement.query().bind(tweet_id).fetch_one(&mut *tx).await?;
+    let tweet_text: &str = row.try_get("text")?;
+
+    assert_eq!(tweet_text, "Hello, World");
+
+    Ok(())
+}
+
+// repro is more reliable with the basic scheduler used by `#[tokio::test]`
+#[cfg(feature = "_rt-tokio")]
+#[tokio::test]
+async fn test_issue_622() -> anyhow::Result<()> {
+    use std::time::Instant;
+
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1) // also fails with higher counts, e.g. 5
+        .connect(&std::env::var("DATABASE_URL").unwrap())
+        .await?;
+
+    println!("pool state: {pool:?}");
+
+    let mut handles = vec![];
+
+    // given repro spawned 100 tasks but I found it reliably reproduced with 3
+    for i in 0..3 {
+        let pool = pool.clone();
+
+        handles.push(sqlx_core::rt::spawn(async move {
+            {
+                let mut conn = pool.acquire().await.unwrap();
+
+                let _ = sqlx::query("SELECT 1").fetch_one(&mut *conn).await.unwrap();
+
+                // conn gets dropped here and should be returned to the pool
+            }
+
+            // (do some other work here without holding on to a connection)
+            // this actually fixes the issue, depending on the timeout used
+            // sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+
+            {
+                let start = Instant::now();
+                match pool.acquire().await {
+                    Ok(conn) => {
+                        println!("{} acquire took {:?}", i, start.elapsed());
+                        drop(conn);
                     }
                     Err(e) => panic!("{i} acquire returned error: {e} pool state: {pool:?}"),
                 }
@@ -447,6 +958,147 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_rolls_back_a_raw_transaction_left_open_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    conn.execute("CREATE TEMPORARY TABLE leaked (id INTEGER) ENGINE = InnoDB")
+        .await?;
+    conn.execute("BEGIN").await?;
+    conn.execute("INSERT INTO leaked (id) VALUES (1)").await?;
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+    let (same_id, count, open): (u64, i64, i64) = sqlx::query_as(
+        "SELECT CONNECTION_ID(), (SELECT COUNT(*) FROM leaked), \
+         (SELECT COUNT(*) FROM information_schema.innodb_trx \
+          WHERE trx_mysql_thread_id = CONNECTION_ID())",
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    assert_eq!(same_id, id);
+    assert_eq!(count, 0);
+    assert_eq!(open, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_round_trips_4_byte_characters() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let (charset, collation): (String, String) =
+        sqlx::query_as("SELECT @@character_set_connection, @@collation_connection")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(charset, "utf8mb4");
+    assert_eq!(collation, "utf8mb4_unicode_ci");
+
+    conn.execute("CREATE TEMPORARY TABLE emoji (text VARCHAR(32) NOT NULL) CHARACTER SET utf8mb4")
+        .await?;
+
+    let text = "😎 𝄞 🙋‍♀️";
+
+    sqlx::query("INSERT INTO emoji (text) VALUES (?)")
+        .bind(text)
+        .execute(&mut conn)
+        .await?;
+
+    // both the text and the binary protocol
+    let row = conn
+        .fetch_one("SELECT text, CHAR_LENGTH(text) FROM emoji")
+        .await?;
+    assert_eq!(row.try_get::<String, _>(0)?, text);
+    assert_eq!(row.try_get::<i64, _>(1)?, 8);
+
+    let value: String = sqlx::query_scalar("SELECT text FROM emoji WHERE text = ?")
+        .bind(text)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, text);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_unusable_charsets() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?.parse::<MySqlConnectOptions>()?;
+
+    for options in [
+        options.clone().charset("utf16"),
+        options.clone().charset("latin1").collation("utf8mb4_bin"),
+    ] {
+        let err = MySqlConnection::connect_with(&options).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            TransactionOptions::new()
+                .isolation_level(IsolationLevel::Serializable)
+                .read_only(true),
+        )
+        .await?;
+
+    // options cannot be applied to a savepoint
+    assert!(tx
+        .begin_with(TransactionOptions::new().read_only(true))
+        .await
+        .is_err());
+
+    let err = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+
+    // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION
+    assert_eq!(
+        err.as_database_error().unwrap().code().as_deref(),
+        Some("25006")
+    );
+
+    tx.rollback().await?;
+
+    // the options only applied to that transaction
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO tweet (text) VALUES ('read write')")
+        .execute(&mut *tx)
+        .await?;
+
+    tx.rollback().await?;
+
+    // not supported by MySQL
+    assert!(conn
+        .begin_with(TransactionOptions::new().deferrable(true))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_handle_split_packets() -> anyhow::Result<()> {
     // This will only take effect on new connections
@@ -510,3 +1162,354 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_resets_user_variables_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .reset_on_release(true);
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+
+    {
+        let mut conn = pool.acquire().await?;
+
+        conn.execute("SET @sqlx_reset_test = 'hello'").await?;
+
+        let vars = conn.user_variables().await?;
+        assert!(vars.contains(&("sqlx_reset_test".to_owned(), Some("hello".to_owned()))));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    let value: Option<String> = sqlx::query_scalar("SELECT @sqlx_reset_test")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(value, None);
+    assert!(conn.user_variables().await?.is_empty());
+
+    // the session settings applied on connect survive the reset
+    let time_zone: String = sqlx::query_scalar("SELECT @@time_zone")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(time_zone, "+00:00");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_after_connect_on_every_session() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .reset_on_release(true)
+        .after_connect(|conn| {
+            Box::pin(async move {
+                conn.execute("SET @sqlx_tenant = 'acme', @@session.sql_select_limit = 5")
+                    .await?;
+
+                Ok(())
+            })
+        });
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+
+    for _ in 0..2 {
+        let mut conn = pool.acquire().await?;
+
+        let (tenant, limit): (Option<String>, u64) =
+            sqlx::query_as("SELECT @sqlx_tenant, @@session.sql_select_limit")
+                .fetch_one(&mut *conn)
+                .await?;
+
+        assert_eq!(tenant.as_deref(), Some("acme"));
+        assert_eq!(limit, 5);
+
+        // undone by the reset on release, then set again
+        conn.execute("SET @sqlx_tenant = NULL").await?;
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_an_in_list() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    async fn select_ids(conn: &mut MySqlConnection, ids: Vec<i64>) -> anyhow::Result<Vec<i64>> {
+        let mut qb = QueryBuilder::<MySql>::new(
+            "SELECT id FROM (SELECT 1 AS id UNION ALL SELECT 2 UNION ALL SELECT 3 \
+             UNION ALL SELECT 4 UNION ALL SELECT 5) AS t WHERE id > ",
+        );
+
+        qb.push_bind(1i64)
+            .push(" AND id ")
+            .push_in_list(ids)
+            .push(" AND id < ")
+            .push_bind(5i64)
+            .push(" ORDER BY id");
+
+        Ok(qb.build_query_scalar().fetch_all(conn).await?)
+    }
+
+    assert_eq!(select_ids(&mut conn, vec![]).await?, Vec::<i64>::new());
+    assert_eq!(select_ids(&mut conn, vec![3]).await?, vec![3]);
+    assert_eq!(select_ids(&mut conn, vec![1, 2, 4]).await?, vec![2, 4]);
+
+    let mut qb = QueryBuilder::<MySql>::new("SELECT 1 WHERE 1 = ");
+    qb.push_bind(1i64)
+        .push(" AND 2 ")
+        .push_in_list([1i64, 2, 3]);
+    assert_eq!(qb.sql(), "SELECT 1 WHERE 1 = ? AND 2 IN (?, ?, ?)");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_gets_columns_by_name() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // text protocol
+    let row = conn.fetch_one("SELECT 1 AS a, 2 AS b").await?;
+    assert_eq!(row.try_get::<i64, _>("a")?, 1);
+    assert_eq!(row.try_get::<i64, _>("b")?, 2);
+
+    // binary protocol
+    let row = sqlx::query("SELECT ? AS b, ? AS a")
+        .bind(2_i64)
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row.try_get::<i64, _>("a")?, 1);
+    assert_eq!(row.try_get::<i64, _>("b")?, 2);
+
+    // a duplicated name refers to the last column with that name
+    let row = conn.fetch_one("SELECT 1 AS a, 2 AS a").await?;
+    assert_eq!(row.try_get::<i64, _>("a")?, 2);
+    assert_eq!(row.try_get::<i64, _>(0)?, 1);
+
+    let row = sqlx::query("SELECT ? AS a, ? AS a")
+        .bind(1_i64)
+        .bind(2_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row.try_get::<i64, _>("a")?, 2);
+
+    assert!(matches!(
+        row.try_get::<i64, _>("c"),
+        Err(sqlx::Error::ColumnNotFound(name)) if name == "c"
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_connects_without_set_names() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .set_names(false);
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let value: String = sqlx::query_scalar("SELECT 'hello'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, "hello");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_numbered_placeholders() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .numbered_placeholders(true);
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let (sum, text, difference): (i64, String, i64) =
+        sqlx::query_as("SELECT $1 + $1, '$1 stays', $2 - $1")
+            .bind(2_i64)
+            .bind(10_i64)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(sum, 4);
+    assert_eq!(text, "$1 stays");
+    assert_eq!(difference, 8);
+
+    // `?` keeps working as usual
+    let value: i64 = sqlx::query_scalar("SELECT ? + 1")
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_string_enums_from_string_columns() -> anyhow::Result<()> {
+    #[derive(PartialEq, Eq, Debug, sqlx::Type)]
+    #[sqlx(rename_all = "lowercase")]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE colors (e ENUM('red', 'green', 'blue'), s VARCHAR(10));
+INSERT INTO colors VALUES ('red', 'green');
+    "#,
+    )
+    .await?;
+
+    let row = sqlx::query("SELECT e, s, 'blue' AS l FROM colors")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get::<Color, _>("e")?, Color::Red);
+    assert_eq!(row.try_get::<Color, _>("s")?, Color::Green);
+    assert_eq!(row.try_get::<Color, _>("l")?, Color::Blue);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_every_result_set_of_a_procedure() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+DROP PROCEDURE IF EXISTS two_result_sets;
+
+CREATE PROCEDURE two_result_sets(n INT)
+BEGIN
+    SELECT n AS a;
+    SELECT n + 1 AS b, 'x' AS c UNION ALL SELECT n + 2, 'y';
+END;
+    "#,
+    )
+    .await?;
+
+    // every result set is terminated by a query result, followed by one for the call itself
+    async fn collect_result_sets(
+        mut results: BoxStream<'_, sqlx::Result<Either<MySqlQueryResult, MySqlRow>>>,
+    ) -> sqlx::Result<Vec<Vec<MySqlRow>>> {
+        let mut sets = vec![Vec::new()];
+
+        while let Some(step) = results.try_next().await? {
+            match step {
+                Either::Left(_) => sets.push(Vec::new()),
+                Either::Right(row) => sets.last_mut().unwrap().push(row),
+            }
+        }
+
+        Ok(sets)
+    }
+
+    // the binary protocol prepares the call before it knows the columns of its result sets
+    let binary = collect_result_sets(
+        sqlx::query("CALL two_result_sets(?)")
+            .bind(10_i32)
+            .fetch_many(&mut conn),
+    )
+    .await?;
+
+    let text = collect_result_sets(conn.fetch_many("CALL two_result_sets(10)")).await?;
+
+    for sets in [binary, text] {
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets[0].len(), 1);
+        assert_eq!(sets[0][0].try_get::<i32, _>("a")?, 10);
+
+        assert_eq!(sets[1].len(), 2);
+        assert_eq!(sets[1][0].try_get::<i64, _>("b")?, 11);
+        assert_eq!(sets[1][1].try_get::<String, _>("c")?, "y");
+
+        assert!(sets[2].is_empty() && sets[3].is_empty());
+    }
+
+    conn.execute("DROP PROCEDURE two_result_sets").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_cancel_a_running_query() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let token = conn.cancel_token();
+
+    let start = std::time::Instant::now();
+
+    let cancel = sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(std::time::Duration::from_millis(500)).await;
+        token.cancel().await
+    });
+
+    // an interrupted `SLEEP()` returns 1 instead of failing
+    let interrupted: i64 = sqlx::query_scalar("SELECT SLEEP(30)")
+        .fetch_one(&mut conn)
+        .await?;
+
+    cancel.await?;
+
+    assert_eq!(interrupted, 1);
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_kills_an_abandoned_query_when_asked() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = MySqlConnection::connect_with(&options.cancel_on_drop(true)).await?;
+
+    // abandon the query while it is still running
+    {
+        let mut rows = sqlx::query("SELECT SLEEP(30)").fetch(&mut conn);
+        let res =
+            sqlx_core::rt::timeout(std::time::Duration::from_millis(500), rows.try_next()).await;
+        assert!(res.is_err());
+    }
+
+    // the next query does not wait for the abandoned one to complete
+    let start = std::time::Instant::now();
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    // and is not killed itself
+    let value: i64 = sqlx::query_scalar("SELECT SLEEP(1)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 0);
+
+    Ok(())
+}
@@ -5,7 +5,7 @@ use std::str::FromStr;
 
 use sqlx::mysql::MySql;
 use sqlx::{Executor, Row};
-use sqlx_test::{new, test_type};
+use sqlx_test::{new, test_decode_type, test_type};
 
 test_type!(bool(MySql, "false" == false, "true" == true));
 
@@ -18,7 +18,11 @@ test_type!(i16(MySql, "21415" == 21415_i16));
 test_type!(u32(MySql, "CAST(2141512 AS UNSIGNED)" == 2141512_u32));
 test_type!(i32(MySql, "2141512" == 2141512_i32));
 
-test_type!(u64(MySql, "CAST(2141512 AS UNSIGNED)" == 2141512_u64));
+test_type!(u64(
+    MySql,
+    "CAST(2141512 AS UNSIGNED)" == 2141512_u64,
+    "CAST(18446744073709551615 AS UNSIGNED)" == u64::MAX
+));
 test_type!(i64(MySql, "2141512" == 2141512_i64));
 
 test_type!(f64(MySql, "3.14159265e0" == 3.14159265_f64));
@@ -42,6 +46,13 @@ test_type!(bytes<Vec<u8>>(MySql,
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+test_type!(bytes_array<[u8; 4]>(MySql,
+    "CAST(X'DEADBEEF' AS BINARY(4))"
+        == [0xDE_u8, 0xAD, 0xBE, 0xEF],
+    "CAST(X'00000052' AS BINARY(4))"
+        == [0_u8, 0, 0, 0x52]
+));
+
 #[cfg(feature = "uuid")]
 test_type!(uuid<sqlx::types::Uuid>(MySql,
     "x'b731678f636f4135bc6f19440c13bd19'"
@@ -50,6 +61,14 @@ test_type!(uuid<sqlx::types::Uuid>(MySql,
         == sqlx::types::Uuid::parse_str("00000000-0000-0000-0000-000000000000").unwrap()
 ));
 
+#[cfg(feature = "uuid")]
+test_decode_type!(uuid_from_text<sqlx::types::Uuid>(MySql,
+    "CAST('b731678f-636f-4135-bc6f-19440c13bd19' AS CHAR(36))"
+        == sqlx::types::Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap(),
+    "CAST('b731678f636f4135bc6f19440c13bd19' AS CHAR(32))"
+        == sqlx::types::Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap()
+));
+
 #[cfg(feature = "uuid")]
 test_type!(uuid_hyphenated<sqlx::types::uuid::fmt::Hyphenated>(MySql,
     "'b731678f-636f-4135-bc6f-19440c13bd19'"
@@ -66,9 +85,53 @@ test_type!(uuid_simple<sqlx::types::uuid::fmt::Simple>(MySql,
         == sqlx::types::Uuid::parse_str("00000000000000000000000000000000").unwrap().simple()
 ));
 
+/// Connect with the given policy and read back a `DATE` and a `DATETIME` column holding zero
+/// dates, with both the text and the binary protocol.
+#[cfg(any(feature = "chrono", feature = "time"))]
+async fn zero_date_rows(
+    policy: sqlx::mysql::MySqlZeroDatePolicy,
+) -> anyhow::Result<Vec<sqlx::mysql::MySqlRow>> {
+    use sqlx::mysql::{MySqlConnectOptions, MySqlConnection};
+    use sqlx::Connection;
+
+    let options = std::env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .zero_date_policy(policy);
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    // ensure that zero dates are turned on
+    // newer MySQL has these disabled by default
+
+    conn.execute("SET @@sql_mode := REPLACE(@@sql_mode, 'NO_ZERO_IN_DATE', '');")
+        .await?;
+
+    conn.execute("SET @@sql_mode := REPLACE(@@sql_mode, 'NO_ZERO_DATE', '');")
+        .await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE zero_dates (d DATE NOT NULL, dt DATETIME NOT NULL);
+INSERT INTO zero_dates (d, dt) VALUES ('0000-00-00', '0000-00-00 00:00:00');
+    "#,
+    )
+    .await?;
+
+    // unprepared, text API
+    let text = conn.fetch_one("SELECT d, dt FROM zero_dates").await?;
+
+    // prepared, binary API
+    let binary = sqlx::query("SELECT d, dt FROM zero_dates")
+        .fetch_one(&mut conn)
+        .await?;
+
+    Ok(vec![text, binary])
+}
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use super::*;
+    use sqlx::mysql::MySqlZeroDatePolicy;
     use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
     test_type!(chrono_date<NaiveDate>(MySql,
@@ -133,11 +196,42 @@ mod chrono {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_type_chrono_zero_date_policy() -> anyhow::Result<()> {
+        for row in zero_date_rows(MySqlZeroDatePolicy::RejectZeroDates).await? {
+            assert!(row.try_get::<Option<NaiveDate>, _>(0).is_err());
+            assert!(row.try_get::<NaiveDate, _>(0).is_err());
+            assert!(row.try_get::<Option<NaiveDateTime>, _>(1).is_err());
+            assert!(row.try_get::<NaiveDateTime, _>(1).is_err());
+        }
+
+        for row in zero_date_rows(MySqlZeroDatePolicy::ConvertZeroToNull).await? {
+            assert_eq!(row.try_get::<Option<NaiveDate>, _>(0)?, None);
+            assert!(row.try_get::<NaiveDate, _>(0).is_err());
+            assert_eq!(row.try_get::<Option<NaiveDateTime>, _>(1)?, None);
+            assert!(row.try_get::<NaiveDateTime, _>(1).is_err());
+        }
+
+        let min = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+
+        for row in zero_date_rows(MySqlZeroDatePolicy::RoundToMin).await? {
+            assert_eq!(row.try_get::<Option<NaiveDate>, _>(0)?, Some(min));
+            assert_eq!(row.try_get::<NaiveDate, _>(0)?, min);
+            assert_eq!(
+                row.try_get::<NaiveDateTime, _>(1)?,
+                min.and_hms_opt(0, 0, 0).unwrap()
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
 mod time_tests {
     use super::*;
+    use sqlx::mysql::MySqlZeroDatePolicy;
     use sqlx::types::time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
     use time::macros::{date, time};
 
@@ -209,6 +303,37 @@ mod time_tests {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_type_time_zero_date_policy() -> anyhow::Result<()> {
+        for row in zero_date_rows(MySqlZeroDatePolicy::RejectZeroDates).await? {
+            assert!(row.try_get::<Option<Date>, _>(0).is_err());
+            assert!(row.try_get::<Date, _>(0).is_err());
+            assert!(row.try_get::<Option<PrimitiveDateTime>, _>(1).is_err());
+            assert!(row.try_get::<PrimitiveDateTime, _>(1).is_err());
+        }
+
+        for row in zero_date_rows(MySqlZeroDatePolicy::ConvertZeroToNull).await? {
+            assert_eq!(row.try_get::<Option<Date>, _>(0)?, None);
+            assert!(row.try_get::<Date, _>(0).is_err());
+            assert_eq!(row.try_get::<Option<PrimitiveDateTime>, _>(1)?, None);
+            assert!(row.try_get::<PrimitiveDateTime, _>(1).is_err());
+        }
+
+        for row in zero_date_rows(MySqlZeroDatePolicy::RoundToMin).await? {
+            assert_eq!(
+                row.try_get::<Option<Date>, _>(0)?,
+                Some(date!(0000 - 1 - 1))
+            );
+            assert_eq!(row.try_get::<Date, _>(0)?, date!(0000 - 1 - 1));
+            assert_eq!(
+                row.try_get::<PrimitiveDateTime, _>(1)?,
+                date!(0000 - 1 - 1).midnight()
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "bigdecimal")]
@@ -317,5 +442,58 @@ CREATE TEMPORARY TABLE with_bits (
     assert_eq!(v1, 1);
     assert_eq!(vn, 510202);
 
+    // RAW BYTES
+    let (v1, vn): (Vec<u8>, Vec<u8>) = sqlx::query_as("SELECT value_1, value_n FROM with_bits")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v1, [1]);
+    assert_eq!(vn, 510202_u64.to_be_bytes());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_varbinary_non_utf8() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE with_varbinary (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    value VARBINARY(16) NOT NULL
+);
+    "#,
+    )
+    .await?;
+
+    // not valid UTF-8
+    let value = vec![0xC3_u8, 0x28, 0xFF, 0x00, 0xFE];
+
+    sqlx::query("INSERT INTO with_varbinary (value) VALUES (?)")
+        .bind(&value)
+        .execute(&mut conn)
+        .await?;
+
+    // BINARY
+    let row = sqlx::query("SELECT value FROM with_varbinary")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get::<Vec<u8>, _>(0)?, value);
+    assert!(matches!(
+        row.try_get::<String, _>(0),
+        Err(sqlx::Error::ColumnDecode { .. })
+    ));
+
+    // TEXT
+    let row = conn.fetch_one("SELECT value FROM with_varbinary").await?;
+
+    assert_eq!(row.try_get::<Vec<u8>, _>(0)?, value);
+    assert!(matches!(
+        row.try_get::<String, _>(0),
+        Err(sqlx::Error::ColumnDecode { .. })
+    ));
+
     Ok(())
 }
@@ -1,6 +1,7 @@
 use futures::TryStreamExt;
 use sqlx::postgres::types::PgRange;
-use sqlx::{Connection, Executor, FromRow, Postgres};
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
+use sqlx::{Connection, Executor, FromRow, Postgres, Row};
 use sqlx_test::{new, test_type};
 use std::fmt::Debug;
 use std::ops::Bound;
@@ -131,6 +132,19 @@ struct InventoryItem {
     price: Option<i64>,
 }
 
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "int_text_pair")]
+struct IntTextPair {
+    number: i32,
+    label: Option<String>,
+}
+
+impl PgHasArrayType for IntTextPair {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_int_text_pair")
+    }
+}
+
 // Custom range type
 #[derive(sqlx::Type, Debug, PartialEq)]
 #[sqlx(type_name = "float_range")]
@@ -361,6 +375,23 @@ SELECT id, mood FROM people WHERE id = $1
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_enum_type_from_text() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // labels cast to (or stored as) text decode without casting back to the enum
+    let row = conn.fetch_one("SELECT 'sad'::text").await?;
+    assert_eq!(row.try_get::<Mood, _>(0)?, Mood::Sad);
+
+    let rec: (Mood,) = sqlx::query_as("SELECT 'happy'::varchar")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(rec.0, Mood::Happy);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_record_type() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -386,6 +417,63 @@ SELECT $1 = ROW('fuzzy dice', 42, 199)::inventory_item, $1
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_record_array_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = r#"
+SELECT ARRAY[
+    ROW(1, 'one'),
+    NULL,
+    ROW(3, NULL),
+    ROW(4, 'with "quotes", commas and a backslash \'),
+    ROW(5, 'NULL')
+]::int_text_pair[]
+    "#;
+
+    let expected = vec![
+        Some(IntTextPair {
+            number: 1,
+            label: Some("one".to_owned()),
+        }),
+        None,
+        Some(IntTextPair {
+            number: 3,
+            label: None,
+        }),
+        Some(IntTextPair {
+            number: 4,
+            label: Some(r#"with "quotes", commas and a backslash \"#.to_owned()),
+        }),
+        Some(IntTextPair {
+            number: 5,
+            label: Some("NULL".to_owned()),
+        }),
+    ];
+
+    // binary format (prepared)
+    let (pairs,): (Vec<Option<IntTextPair>>,) = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+    assert_eq!(pairs, expected);
+
+    // text format (simple query)
+    let row = conn.fetch_one(sql).await?;
+    let pairs: Vec<Option<IntTextPair>> = row.try_get(0)?;
+    assert_eq!(pairs, expected);
+
+    // a NULL element cannot be decoded without `Option`
+    let res: Result<(Vec<IntTextPair>,), _> = sqlx::query_as(sql).fetch_one(&mut conn).await;
+    assert!(res.is_err());
+
+    // round trip
+    let pairs: Vec<Option<IntTextPair>> = sqlx::query_scalar("SELECT $1::int_text_pair[]")
+        .bind(&expected)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(pairs, expected);
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_new_type() {
@@ -621,6 +709,37 @@ async fn test_default() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_default_with_try_from() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct HasDefault {
+        #[sqlx(default, try_from = "i32")]
+        small: u8,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let has_default: HasDefault = sqlx::query_as(r#"SELECT 1 AS not_small"#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(has_default.small, 0);
+
+    // a value that fails the conversion is an error, not a missing column
+    let err = sqlx::query_as::<_, HasDefault>(r#"SELECT 300 AS small"#)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, sqlx::Error::ColumnDecode { ref index, .. } if index == "\"small\""),
+        "{err:?}"
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_struct_default() -> anyhow::Result<()> {
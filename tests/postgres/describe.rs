@@ -93,3 +93,23 @@ async fn it_describes_composite() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_parameters_through_a_pool() -> anyhow::Result<()> {
+    let pool = sqlx_test::pool::<Postgres>().await?;
+
+    let d = pool
+        .describe("SELECT id, text FROM tweet WHERE owner_id = $1 AND text <> $2")
+        .await?;
+
+    let parameters = d.parameters().unwrap().left().unwrap();
+    assert_eq!(parameters.len(), 2);
+    assert_eq!(parameters[0].name(), "INT8");
+    assert_eq!(parameters[1].name(), "TEXT");
+
+    assert_eq!(d.columns().len(), 2);
+    assert_eq!(d.column(1).name(), "text");
+    assert_eq!(d.nullable(0), Some(false));
+
+    Ok(())
+}
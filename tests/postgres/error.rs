@@ -1,4 +1,5 @@
-use sqlx::{error::ErrorKind, postgres::Postgres, Connection};
+use sqlx::postgres::{PgDatabaseError, PgSeverity, Postgres};
+use sqlx::{error::ErrorKind, Connection};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -18,6 +19,14 @@ async fn it_fails_with_unique_violation() -> anyhow::Result<()> {
     let err = err.into_database_error().unwrap();
 
     assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(err.code().as_deref(), Some("23505"));
+    assert_eq!(err.constraint(), Some("tweet_pkey"));
+    assert_eq!(err.table(), Some("tweet"));
+
+    let err = err.downcast::<PgDatabaseError>();
+
+    assert_eq!(err.severity(), PgSeverity::Error);
+    assert!(err.detail().is_some());
 
     Ok(())
 }
@@ -53,6 +62,9 @@ async fn it_fails_with_not_null_violation() -> anyhow::Result<()> {
     let err = err.into_database_error().unwrap();
 
     assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+    assert!(err.is_not_null_violation());
+    assert_eq!(err.table(), Some("tweet"));
+    assert_eq!(err.column(), Some("text"));
 
     Ok(())
 }
@@ -1,4 +1,4 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{MigrateError, Migrator};
 use sqlx::pool::PoolConnection;
 use sqlx::postgres::{PgConnection, Postgres};
 use sqlx::Executor;
@@ -66,6 +66,48 @@ async fn reversible(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn it_releases_the_lock_when_migrating_fails(
+    mut conn: PoolConnection<Postgres>,
+) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+    migrator.run(&mut conn).await?;
+
+    // a migration that was applied but is unknown to the migrator
+    conn.execute(
+        "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+         VALUES (1, 'unknown', TRUE, '', 0)",
+    )
+    .await?;
+
+    let res = migrator.run(&mut conn).await;
+    assert!(
+        matches!(res, Err(MigrateError::VersionMissing(1))),
+        "{res:?}"
+    );
+    assert_eq!(advisory_locks(&mut conn).await?, 0);
+
+    let res = migrator.undo(&mut conn, 0).await;
+    assert!(
+        matches!(res, Err(MigrateError::VersionMissing(1))),
+        "{res:?}"
+    );
+    assert_eq!(advisory_locks(&mut conn).await?, 0);
+
+    Ok(())
+}
+
+/// Count the advisory locks held by this connection.
+async fn advisory_locks(conn: &mut PgConnection) -> anyhow::Result<i64> {
+    Ok(sqlx::query_scalar(
+        "SELECT count(*) FROM pg_locks WHERE locktype = 'advisory' AND pid = pg_backend_pid()",
+    )
+    .fetch_one(conn)
+    .await?)
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut PgConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_simple_test").await.ok();
@@ -2,11 +2,17 @@ use futures::{StreamExt, TryStreamExt};
 use sqlx::postgres::types::Oid;
 use sqlx::postgres::{
     PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres,
+    PgPoolOptions, PgRow, PgSeverity, PgSslMode, PgValueFormat, Postgres,
+};
+use sqlx::{
+    Column, ConnectOptions, Connection, Executor, IsolationLevel, QueryBuilder, Row, Statement,
+    TransactionOptions, TypeInfo,
 };
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
 use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
+use std::io::Write;
+use std::net::TcpStream;
+use std::ops::ControlFlow;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -45,6 +51,66 @@ async fn it_pings() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fails_to_ping_a_terminated_connection() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut admin = new::<Postgres>().await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&mut admin)
+        .await?;
+
+    let err = conn
+        .ping()
+        .await
+        .expect_err("ping should fail after the backend is terminated");
+
+    // the server says why it closed the connection before hanging up
+    if let sqlx::Error::Database(err) = &err {
+        assert_eq!(err.code().as_deref(), Some("57P01"));
+    } else {
+        assert!(
+            matches!(err, sqlx::Error::Io(_)),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    // the connection stays unusable
+    assert!(conn.ping().await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_tracks_round_trip_latency() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for _ in 0..5 {
+        conn.ping().await?;
+    }
+
+    let fast = conn.latency_ewma().expect("pings should be measured");
+
+    // the server only responds once the sleep is over
+    sqlx::query("SELECT pg_sleep($1)")
+        .bind(0.2_f64)
+        .execute(&mut conn)
+        .await?;
+
+    let slow = conn.latency_ewma().unwrap();
+
+    // the sample is weighted by 1/8
+    assert!(slow > fast, "{slow:?} <= {fast:?}");
+    assert!(slow >= Duration::from_millis(25), "{slow:?}");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_pings_after_suspended_query() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -52,9 +118,10 @@ async fn it_pings_after_suspended_query() -> anyhow::Result<()> {
     conn.execute("create temporary table processed_row(val int4 primary key)")
         .await?;
 
-    // This query wants to return 50 rows but we only read the first one.
+    // This query wants to return 50 rows but we only read the first two,
+    // which is enough to know that there are too many.
     // This will return a `SuspendedPortal` that the driver currently ignores.
-    let _: i32 = sqlx::query_scalar(
+    let res: Result<i32, _> = sqlx::query_scalar(
         r#"
             insert into processed_row(val)
             select * from generate_series(1, 50)
@@ -62,13 +129,15 @@ async fn it_pings_after_suspended_query() -> anyhow::Result<()> {
         "#,
     )
     .fetch_one(&mut conn)
-    .await?;
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
 
     // `Sync` closes the current autocommit transaction which presumably includes closing any
     // suspended portals.
     conn.ping().await?;
 
-    // Make sure that all the values got inserted even though we only read the first one back.
+    // Make sure that all the values got inserted even though we only read the first two back.
     let count: i64 = sqlx::query_scalar("select count(*) from processed_row")
         .fetch_one(&mut conn)
         .await?;
@@ -93,6 +162,130 @@ async fn it_maths() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_enforces_row_count_for_fetch_one_and_fetch_optional() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    const QUERY: &str =
+        "SELECT v FROM (SELECT 1 AS v UNION ALL SELECT 2 UNION ALL SELECT 2) t WHERE v = $1";
+
+    // zero rows
+    let res: Result<i32, _> = sqlx::query_scalar(QUERY)
+        .bind(0_i32)
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::RowNotFound)));
+
+    let v: Option<i32> = sqlx::query_scalar(QUERY)
+        .bind(0_i32)
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(v, None);
+
+    // one row
+    let v: i32 = sqlx::query_scalar(QUERY)
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 1);
+
+    let v: Option<i32> = sqlx::query_scalar(QUERY)
+        .bind(1_i32)
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(v, Some(1));
+
+    // two rows
+    let res: Result<i32, _> = sqlx::query_scalar(QUERY)
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    let res: Result<Option<i32>, _> = sqlx::query_scalar(QUERY)
+        .bind(2_i32)
+        .fetch_optional(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    // the rest of the result set was discarded
+    let v: i32 = sqlx::query_scalar(QUERY)
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_rows_affected_by_writes() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE affected_test (id SERIAL PRIMARY KEY, v INT NOT NULL)")
+        .await?;
+
+    let done = sqlx::query("INSERT INTO affected_test (v) SELECT i FROM generate_series(1, 5) i")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 5);
+
+    // matches three rows
+    let done = sqlx::query("UPDATE affected_test SET v = v * 10 WHERE v > $1")
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 3);
+
+    let done = sqlx::query("DELETE FROM affected_test WHERE v < 10")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 2);
+
+    // generated ids are returned with `RETURNING`
+    let id: i32 = sqlx::query_scalar("INSERT INTO affected_test (v) VALUES (1) RETURNING id")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 6);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_execute_and_drain_results() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE drain_test (id SERIAL PRIMARY KEY, v TEXT NOT NULL)")
+        .await?;
+
+    let done = conn
+        .execute_drain(
+            "INSERT INTO drain_test (v) SELECT 'row ' || i FROM generate_series(1, 1000) i RETURNING id, v",
+        )
+        .await?;
+
+    assert_eq!(done.rows_affected(), 1000);
+
+    // the connection should be immediately reusable
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM drain_test")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1000);
+
+    // errors leave the connection usable as well
+    assert!(conn.execute_drain("SELECT 1 / 0").await.is_err());
+
+    let v: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(v, 1);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_inspect_errors() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -259,170 +452,713 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
-// https://github.com/launchbadge/sqlx/issues/104
 #[sqlx_macros::test]
-async fn it_can_return_interleaved_nulls_issue_104() -> anyhow::Result<()> {
+async fn it_fetches_all_rows() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    let tuple = sqlx::query("SELECT NULL, 10::INT, NULL, 20::INT, NULL, 40::INT, NULL, 80::INT")
-        .map(|row: PgRow| {
-            (
-                row.get::<Option<i32>, _>(0),
-                row.get::<Option<i32>, _>(1),
-                row.get::<Option<i32>, _>(2),
-                row.get::<Option<i32>, _>(3),
-                row.get::<Option<i32>, _>(4),
-                row.get::<Option<i32>, _>(5),
-                row.get::<Option<i32>, _>(6),
-                row.get::<Option<i32>, _>(7),
-            )
-        })
-        .fetch_one(&mut conn)
+    let rows = sqlx::query("SELECT v, 'row ' || v FROM generate_series(1, 500) v")
+        .fetch_all(&mut conn)
         .await?;
 
-    assert_eq!(tuple.0, None);
-    assert_eq!(tuple.1, Some(10));
-    assert_eq!(tuple.2, None);
-    assert_eq!(tuple.3, Some(20));
-    assert_eq!(tuple.4, None);
-    assert_eq!(tuple.5, Some(40));
-    assert_eq!(tuple.6, None);
-    assert_eq!(tuple.7, Some(80));
+    assert_eq!(rows.len(), 500);
+
+    for (i, row) in rows.iter().enumerate() {
+        let v: i32 = row.try_get(0)?;
+        let s: String = row.try_get(1)?;
+
+        assert_eq!(v, i as i32 + 1);
+        assert_eq!(s, format!("row {v}"));
+    }
+
+    let values: Vec<(i32,)> = sqlx::query_as("SELECT v FROM generate_series(1, 500) v")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(values.len(), 500);
+    assert!(values.iter().map(|(v,)| *v).eq(1..=500));
+
+    let values: Vec<i32> = sqlx::query_scalar("SELECT v FROM generate_series(1, 500) v")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert!(values.into_iter().eq(1..=500));
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_fail_and_recover() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_times_out_reading_from_a_silent_server() -> anyhow::Result<()> {
+    // accepts connections, then never responds (nor closes them)
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
 
-    for i in 0..10 {
-        // make a query that will fail
-        let res = conn
-            .execute("INSERT INTO not_found (column) VALUES (10)")
-            .await;
+    std::thread::spawn(move || {
+        let _connections: Vec<_> = listener.incoming().collect();
+    });
 
-        assert!(res.is_err());
+    let options = PgConnectOptions::new()
+        .host("127.0.0.1")
+        .port(port)
+        .ssl_mode(PgSslMode::Disable)
+        .tcp_keepalive(Duration::from_secs(60))
+        .read_timeout(Duration::from_millis(200));
 
-        // now try and use the connection
-        let val: i32 = conn.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
+    let res = sqlx_core::rt::timeout(
+        Duration::from_secs(10),
+        PgConnection::connect_with(&options),
+    )
+    .await
+    .expect("connecting should time out instead of hanging");
 
-        assert_eq!(val, i);
+    match res {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+        other => panic!("expected a timeout, got {other:?}"),
     }
 
     Ok(())
 }
 
-#[sqlx_macros::test]
-async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
-    let pool = sqlx_test::pool::<Postgres>().await?;
+// a fake server which completes the startup handshake of every connection, then passes each
+// message it receives to `handler`, along with the index of the connection it arrived on;
+// the connection is closed once `handler` breaks
+fn fake_pg_server<F>(handler: F) -> std::io::Result<u16>
+where
+    F: Fn(usize, u8, &mut TcpStream) -> std::io::Result<ControlFlow<()>> + Send + Sync + 'static,
+{
+    use std::io::Read;
+
+    fn serve<F>(mut socket: TcpStream, connection: usize, handler: &F) -> std::io::Result<()>
+    where
+        F: Fn(usize, u8, &mut TcpStream) -> std::io::Result<ControlFlow<()>>,
+    {
+        let mut len = [0u8; 4];
+        socket.read_exact(&mut len)?;
+        let mut startup = vec![0; u32::from_be_bytes(len) as usize - 4];
+        socket.read_exact(&mut startup)?;
+
+        // AuthenticationOk, BackendKeyData, ReadyForQuery
+        socket.write_all(b"R\0\0\0\x08\0\0\0\0")?;
+        socket.write_all(b"K\0\0\0\x0c\0\0\0\x01\0\0\0\x02")?;
+        socket.write_all(b"Z\0\0\0\x05I")?;
+
+        loop {
+            let mut header = [0u8; 5];
+            socket.read_exact(&mut header)?;
+            let mut body =
+                vec![0; u32::from_be_bytes(header[1..].try_into().unwrap()) as usize - 4];
+            socket.read_exact(&mut body)?;
+
+            if handler(connection, header[0], &mut socket)?.is_break() {
+                return Ok(());
+            }
+        }
+    }
 
-    for i in 0..10 {
-        // make a query that will fail
-        let res = pool
-            .execute("INSERT INTO not_found (column) VALUES (10)")
-            .await;
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let handler = Arc::new(handler);
 
-        assert!(res.is_err());
+    std::thread::spawn(move || {
+        for (connection, socket) in listener.incoming().flatten().enumerate() {
+            let handler = Arc::clone(&handler);
+            std::thread::spawn(move || serve(socket, connection, &*handler));
+        }
+    });
 
-        // now try and use the connection
-        let val: i32 = pool.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
+    Ok(port)
+}
 
-        assert_eq!(val, i);
+// a `fake_pg_server` handler which answers `Sync` (as sent by `ping()`) with `ReadyForQuery`,
+// and closes the connection on anything else
+fn answer_sync(
+    _connection: usize,
+    message: u8,
+    socket: &mut TcpStream,
+) -> std::io::Result<ControlFlow<()>> {
+    match message {
+        b'S' => {
+            socket.write_all(b"Z\0\0\0\x05I")?;
+            Ok(ControlFlow::Continue(()))
+        }
+        _ => Ok(ControlFlow::Break(())),
     }
-
-    Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_query_scalar() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_waits_for_notifications_past_the_read_timeout() -> anyhow::Result<()> {
+    let options = PgConnectOptions::new()
+        .host("127.0.0.1")
+        .port(fake_pg_server(|connection, message, socket| {
+            if message != b'Q' {
+                return answer_sync(connection, message, socket);
+            }
 
-    let scalar: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
-    assert_eq!(scalar, 42);
+            // CommandComplete, ReadyForQuery
+            socket.write_all(b"C\0\0\0\x0bLISTEN\0")?;
+            socket.write_all(b"Z\0\0\0\x05I")?;
 
-    let scalar: Option<i32> = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
-    assert_eq!(scalar, Some(42));
+            // then a NotificationResponse, after the read timeout has passed
+            std::thread::sleep(Duration::from_millis(600));
+            socket.write_all(b"A\0\0\0\x18\0\0\0\x01test_channel\0hi\0")?;
 
-    let scalar: Option<i32> = sqlx::query_scalar("SELECT NULL")
-        .fetch_one(&mut conn)
-        .await?;
-    assert_eq!(scalar, None);
+            Ok(ControlFlow::Continue(()))
+        })?)
+        .ssl_mode(PgSslMode::Disable)
+        .read_timeout(Duration::from_millis(200));
 
-    let scalar: Option<i64> = sqlx::query_scalar("SELECT 42::bigint")
-        .fetch_optional(&mut conn)
-        .await?;
-    assert_eq!(scalar, Some(42));
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_lazy_with(options);
 
-    let scalar: Option<i16> = sqlx::query_scalar("").fetch_optional(&mut conn).await?;
-    assert_eq!(scalar, None);
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.listen("test_channel").await?;
+
+    // waiting on a quiet channel is not a stalled response
+    let notification = sqlx_core::rt::timeout(Duration::from_secs(10), listener.try_recv())
+        .await
+        .expect("the notification should be received")?
+        .expect("the connection should not be lost");
+
+    assert_eq!(notification.channel(), "test_channel");
+    assert_eq!(notification.payload(), "hi");
 
     Ok(())
 }
 
-#[sqlx_macros::test]
-/// This is separate from `it_can_query_scalar` because while implementing it I ran into a
-/// bug which that prevented `Vec<i32>` from compiling but allowed Vec<Option<i32>>.
-async fn it_can_query_all_scalar() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-
-    let scalar: Vec<i32> = sqlx::query_scalar("SELECT $1")
-        .bind(42)
-        .fetch_all(&mut conn)
-        .await?;
-    assert_eq!(scalar, vec![42]);
+// records the fields of `sqlx` spans and events as `(span name or "event", field, value)`,
+// while set as the default subscriber of the current thread
+#[derive(Clone, Default)]
+struct TracedFields {
+    fields: Arc<std::sync::Mutex<Vec<(&'static str, &'static str, String)>>>,
+    span_names: Arc<std::sync::Mutex<Vec<&'static str>>>,
+}
 
-    let scalar: Vec<Option<i32>> = sqlx::query_scalar("SELECT $1 UNION ALL SELECT NULL")
-        .bind(42)
-        .fetch_all(&mut conn)
-        .await?;
-    assert_eq!(scalar, vec![Some(42), None]);
+impl TracedFields {
+    fn get(&self, name: &str, field: &str) -> Vec<String> {
+        let fields = self.fields.lock().unwrap();
 
-    Ok(())
+        fields
+            .iter()
+            .filter(|(n, f, _)| *n == name && *f == field)
+            .map(|(_, _, value)| value.clone())
+            .collect()
+    }
 }
 
-#[sqlx_macros::test]
-async fn it_can_work_with_transactions() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+struct TracedFieldsVisitor<'a>(&'a TracedFields, &'static str);
 
-    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_1922 (id INTEGER PRIMARY KEY)")
-        .await?;
+impl tracing::field::Visit for TracedFieldsVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        let mut fields = self.0.fields.lock().unwrap();
+        fields.push((self.1, field.name(), value.trim().to_owned()));
+    }
 
-    conn.execute("TRUNCATE _sqlx_users_1922").await?;
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        let mut fields = self.0.fields.lock().unwrap();
+        fields.push((self.1, field.name(), format!("{value:?}")));
+    }
+}
 
-    // begin .. rollback
+impl tracing::Subscriber for TracedFields {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+        metadata.target().starts_with("sqlx::")
+    }
 
-    let mut tx = conn.begin().await?;
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let name = span.metadata().name();
+        span.record(&mut TracedFieldsVisitor(self, name));
 
-    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
-        .bind(10_i32)
-        .execute(&mut *tx)
-        .await?;
+        let mut span_names = self.span_names.lock().unwrap();
+        span_names.push(name);
+        tracing::span::Id::from_u64(span_names.len() as u64)
+    }
 
-    tx.rollback().await?;
+    fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        let name = self.span_names.lock().unwrap()[span.into_u64() as usize - 1];
+        values.record(&mut TracedFieldsVisitor(self, name));
+    }
 
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
-        .fetch_one(&mut conn)
-        .await?;
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
 
-    assert_eq!(count, 0);
+    fn event(&self, event: &tracing::Event<'_>) {
+        event.record(&mut TracedFieldsVisitor(self, "event"));
+    }
 
-    // begin .. commit
+    fn enter(&self, _span: &tracing::span::Id) {}
 
-    let mut tx = conn.begin().await?;
+    fn exit(&self, _span: &tracing::span::Id) {}
+}
 
-    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
-        .bind(10_i32)
-        .execute(&mut *tx)
-        .await?;
+#[sqlx_macros::test]
+async fn it_logs_only_the_summary_of_statements_when_asked() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    tx.commit().await?;
+    let traced = TracedFields::default();
+    let _guard = tracing::subscriber::set_default(traced.clone());
 
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
-        .fetch_one(&mut conn)
-        .await?;
+    let sql = "SELECT 1 AS one, 2 AS two";
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
 
-    assert_eq!(count, 1);
+    let mut conn = PgConnection::connect_with(&options).await?;
+    conn.execute(sql).await?;
+    conn.close().await?;
+
+    let mut conn = PgConnection::connect_with(&options.log_full_statements(false)).await?;
+    conn.execute(sql).await?;
+    conn.close().await?;
+
+    // the second connection logs the summary without a `db.statement` field
+    let summaries = traced.get("event", "summary");
+    let statements = traced.get("event", "db.statement");
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(statements.len(), 1);
+    assert!(statements[0].contains("2 AS two"));
+
+    Ok(())
+}
+
+#[cfg(feature = "tracing-spans")]
+#[sqlx_macros::test]
+async fn it_opens_spans_for_queries_and_acquires() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let traced = TracedFields::default();
+    let _guard = tracing::subscriber::set_default(traced.clone());
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let process_id: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    sqlx::query("SELECT * FROM generate_series(1, 3)")
+        .execute(&pool)
+        .await?;
+
+    pool.close().await;
+
+    assert_eq!(
+        traced.get("acquire", "db.system"),
+        ["postgresql", "postgresql"]
+    );
+
+    assert_eq!(
+        traced.get("query", "db.system"),
+        ["postgresql", "postgresql"]
+    );
+    assert_eq!(
+        traced.get("query", "db.statement"),
+        [
+            "SELECT pg_backend_pid()",
+            "SELECT * FROM generate_series(1, 3)"
+        ]
+    );
+    assert_eq!(
+        traced.get("query", "db.connection_id"),
+        [process_id.to_string(), process_id.to_string()]
+    );
+    assert_eq!(traced.get("query", "db.rows_returned"), ["1", "3"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_replaces_a_connection_whose_ping_times_out() -> anyhow::Result<()> {
+    let black_hole = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let options = PgConnectOptions::new()
+        .host("127.0.0.1")
+        .port(fake_pg_server({
+            let black_hole = black_hole.clone();
+
+            // the first connection stops answering, without closing, once `black_hole` is set,
+            // like one that was silently dropped by a firewall
+            move |connection, message, socket| {
+                if connection == 0 && black_hole.load(std::sync::atomic::Ordering::SeqCst) {
+                    return Ok(ControlFlow::Continue(()));
+                }
+
+                answer_sync(connection, message, socket)
+            }
+        })?)
+        .ssl_mode(PgSslMode::Disable);
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(30))
+        .ping_timeout(Duration::from_millis(500))
+        .connect_with(options)
+        .await?;
+
+    // the connection is released to the pool, and then stops answering
+    while pool.num_idle() == 0 {
+        sqlx_core::rt::yield_now().await;
+    }
+
+    black_hole.store(true, std::sync::atomic::Ordering::SeqCst);
+
+    let start = std::time::Instant::now();
+    let mut conn = pool.acquire().await?;
+
+    assert!(start.elapsed() < Duration::from_secs(10));
+    conn.ping().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_counts_pool_metrics() -> anyhow::Result<()> {
+    let options = PgConnectOptions::new()
+        .host("127.0.0.1")
+        .port(fake_pg_server(answer_sync)?)
+        .ssl_mode(PgSslMode::Disable);
+
+    let failed_once = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(200))
+        .after_connect(move |_conn, _meta| {
+            let failed_once = failed_once.clone();
+
+            Box::pin(async move {
+                if failed_once.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                    Ok(())
+                } else {
+                    Err(sqlx::Error::Protocol("first connection refused".into()))
+                }
+            })
+        })
+        .connect_lazy_with(options);
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.size, 0);
+    assert_eq!(metrics.acquires, 0);
+    assert_eq!(metrics.acquire_wait_mean(), None);
+
+    let conn = pool.acquire().await?;
+
+    // the pool is exhausted
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.size, 1);
+    assert_eq!(metrics.num_idle, 0);
+    assert_eq!(metrics.acquires, 1);
+    assert_eq!(metrics.acquire_timeouts, 1);
+    assert_eq!(metrics.connections_opened, 1);
+    assert_eq!(metrics.connect_errors, 1);
+    assert_eq!(metrics.acquire_wait_mean(), Some(metrics.acquire_wait_max));
+
+    drop(conn);
+    drop(pool.acquire().await?);
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.acquires, 2);
+    assert_eq!(metrics.connections_opened, 1);
+    assert!(metrics.acquire_wait_total >= metrics.acquire_wait_max);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_discards_a_connection_after_a_protocol_desync() -> anyhow::Result<()> {
+    let options = PgConnectOptions::new()
+        .host("127.0.0.1")
+        .port(fake_pg_server(|connection, message, socket| {
+            if message != b'Q' {
+                return answer_sync(connection, message, socket);
+            }
+
+            // a `CopyData` message no client could expect at this point, then `ReadyForQuery`
+            socket.write_all(b"d\0\0\0\x07abc")?;
+            socket.write_all(b"Z\0\0\0\x05I")?;
+
+            Ok(ControlFlow::Continue(()))
+        })?)
+        .ssl_mode(PgSslMode::Disable);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    match conn.execute("SELECT 1").await {
+        Err(sqlx::Error::Protocol(msg)) => assert!(msg.contains("out of sync"), "{msg}"),
+        other => panic!("expected a protocol error, got {other:?}"),
+    }
+
+    // the rest of the response was drained, but the connection refuses to be used again
+    assert!(matches!(conn.ping().await, Err(sqlx::Error::Protocol(_))));
+    assert!(matches!(
+        conn.execute("SELECT 1").await,
+        Err(sqlx::Error::Protocol(_))
+    ));
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    assert!(conn.execute("SELECT 1").await.is_err());
+    drop(conn);
+
+    // the connection is closed instead of being returned to the pool
+    sqlx_core::rt::timeout(Duration::from_secs(10), async {
+        while pool.size() > 0 {
+            sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the desynced connection should be discarded");
+
+    assert_eq!(pool.num_idle(), 0);
+
+    Ok(())
+}
+
+// https://github.com/launchbadge/sqlx/issues/104
+#[sqlx_macros::test]
+async fn it_can_return_interleaved_nulls_issue_104() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let tuple = sqlx::query("SELECT NULL, 10::INT, NULL, 20::INT, NULL, 40::INT, NULL, 80::INT")
+        .map(|row: PgRow| {
+            (
+                row.get::<Option<i32>, _>(0),
+                row.get::<Option<i32>, _>(1),
+                row.get::<Option<i32>, _>(2),
+                row.get::<Option<i32>, _>(3),
+                row.get::<Option<i32>, _>(4),
+                row.get::<Option<i32>, _>(5),
+                row.get::<Option<i32>, _>(6),
+                row.get::<Option<i32>, _>(7),
+            )
+        })
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(tuple.0, None);
+    assert_eq!(tuple.1, Some(10));
+    assert_eq!(tuple.2, None);
+    assert_eq!(tuple.3, Some(20));
+    assert_eq!(tuple.4, None);
+    assert_eq!(tuple.5, Some(40));
+    assert_eq!(tuple.6, None);
+    assert_eq!(tuple.7, Some(80));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_replaces_terminated_connection_on_acquire() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    // kill the pooled connection's backend from outside the pool
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&mut conn)
+        .await?;
+
+    while sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM pg_stat_activity WHERE pid = $1)",
+    )
+    .bind(pid)
+    .fetch_one(&mut conn)
+    .await?
+    {
+        sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+    }
+
+    // the dead connection fails the ping in `acquire()` and is replaced
+    let new_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_ne!(pid, new_pid);
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_does_not_reuse_a_connection_after_an_io_error() -> anyhow::Result<()> {
+    // the fake server closes the connection when it receives a query
+    let options = PgConnectOptions::new()
+        .host("127.0.0.1")
+        .port(fake_pg_server(answer_sync)?)
+        .ssl_mode(PgSslMode::Disable);
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .connect_with(options)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    assert!(!conn.is_broken());
+
+    assert!(matches!(
+        conn.execute("SELECT 1").await,
+        Err(sqlx::Error::Io(_))
+    ));
+    assert!(conn.is_broken());
+    drop(conn);
+
+    // the broken connection is closed instead of being handed out again
+    let mut conn = pool.acquire().await?;
+    assert!(!conn.is_broken());
+    conn.ping().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_fail_and_recover() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for i in 0..10 {
+        // make a query that will fail
+        let res = conn
+            .execute("INSERT INTO not_found (column) VALUES (10)")
+            .await;
+
+        assert!(res.is_err());
+
+        // now try and use the connection
+        let val: i32 = conn.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
+
+        assert_eq!(val, i);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
+    let pool = sqlx_test::pool::<Postgres>().await?;
+
+    for i in 0..10 {
+        // make a query that will fail
+        let res = pool
+            .execute("INSERT INTO not_found (column) VALUES (10)")
+            .await;
+
+        assert!(res.is_err());
+
+        // now try and use the connection
+        let val: i32 = pool.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
+
+        assert_eq!(val, i);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_query_scalar() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let scalar: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(scalar, 42);
+
+    let scalar: Option<i32> = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(scalar, Some(42));
+
+    let scalar: Option<i32> = sqlx::query_scalar("SELECT NULL")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(scalar, None);
+
+    let scalar: Option<i64> = sqlx::query_scalar("SELECT 42::bigint")
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(scalar, Some(42));
+
+    let scalar: Option<i16> = sqlx::query_scalar("").fetch_optional(&mut conn).await?;
+    assert_eq!(scalar, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+/// This is separate from `it_can_query_scalar` because while implementing it I ran into a
+/// bug which that prevented `Vec<i32>` from compiling but allowed Vec<Option<i32>>.
+async fn it_can_query_all_scalar() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let scalar: Vec<i32> = sqlx::query_scalar("SELECT $1")
+        .bind(42)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(scalar, vec![42]);
+
+    let scalar: Vec<Option<i32>> = sqlx::query_scalar("SELECT $1 UNION ALL SELECT NULL")
+        .bind(42)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(scalar, vec![Some(42), None]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_work_with_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_1922 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    conn.execute("TRUNCATE _sqlx_users_1922").await?;
+
+    // begin .. rollback
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
+        .bind(10_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.rollback().await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // begin .. commit
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
+        .bind(10_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
 
     // begin .. (drop)
 
@@ -441,90 +1177,322 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(count, 1);
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_2523 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    conn.execute("TRUNCATE _sqlx_users_2523").await?;
+
+    // begin
+    let mut tx = conn.begin().await?; // transaction
+
+    // insert a user
+    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
+        .bind(50_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    // begin once more
+    let mut tx2 = tx.begin().await?; // savepoint
+
+    // insert another user
+    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
+        .bind(10_i32)
+        .execute(&mut *tx2)
+        .await?;
+
+    // never mind, rollback
+    tx2.rollback().await?; // roll that one back
+
+    // did we really?
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    // actually, commit
+    tx.commit().await?;
+
+    // did we really?
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_releases_a_savepoint_after_rolling_back_to_it() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    for _ in 0..3 {
+        let inner = tx.begin().await?;
+        inner.rollback().await?;
+    }
+
+    {
+        // dropping a savepoint rolls it back as well
+        let _inner = tx.begin().await?;
+    }
+
+    // the savepoint no longer exists once it was rolled back
+    let err = tx
+        .execute("RELEASE SAVEPOINT _sqlx_savepoint_1")
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.into_database_error().unwrap().code().as_deref(),
+        Some("3B001")
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_3952 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    conn.execute("TRUNCATE _sqlx_users_3952").await?;
+
+    // begin .. (drop)
+
+    // run 2 times to see what happens if we drop transactions repeatedly
+    for _ in 0..2 {
+        {
+            let mut tx = conn.begin().await?;
+
+            // do actually something before dropping
+            let _user = sqlx::query("INSERT INTO _sqlx_users_3952 (id) VALUES ($1) RETURNING id")
+                .bind(20_i32)
+                .fetch_one(&mut *tx)
+                .await?;
+        }
+
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3952")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(count, 0);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_a_raw_transaction_left_open_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    conn.execute("CREATE TEMPORARY TABLE leaked (id INTEGER)")
+        .await?;
+    conn.execute("BEGIN").await?;
+    conn.execute("INSERT INTO leaked (id) VALUES (1)").await?;
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+    let (same_pid, count, txid): (i32, i64, Option<i64>) = sqlx::query_as(
+        "SELECT pg_backend_pid(), (SELECT count(*) FROM leaked), txid_current_if_assigned()",
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    assert_eq!(same_pid, pid);
+    assert_eq!(count, 0);
+    assert_eq!(txid, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_a_limited_number_of_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the third row would fail with a division by zero, so execution must stop after two
+    let rows = conn
+        .fetch_limited("SELECT 1 / (3 - x) FROM generate_series(1, 10) x", 2)
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].try_get::<i32, _>(0)?, 0);
+    assert_eq!(rows[1].try_get::<i32, _>(0)?, 1);
+
+    // with arguments
+    let rows = conn
+        .fetch_limited(
+            sqlx::query("SELECT x FROM generate_series(1, $1) x").bind(100_i32),
+            5,
+        )
+        .await?;
+
+    let values: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+    assert_eq!(values, [1, 2, 3, 4, 5]);
+
+    // fewer rows than the limit
+    let rows = conn
+        .fetch_limited("SELECT x FROM generate_series(1, 3) x", 10)
+        .await?;
+
+    assert_eq!(rows.len(), 3);
+
+    assert!(conn.fetch_limited("SELECT 1", 0).await?.is_empty());
+
+    // the suspended portal was closed and the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_a_pipeline() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE pipelined (id INT PRIMARY KEY, label TEXT)")
+        .await?;
+
+    let results = conn
+        .pipeline()
+        .push(
+            sqlx::query("INSERT INTO pipelined VALUES ($1, $2)")
+                .bind(1_i32)
+                .bind("a"),
+        )
+        .push(
+            sqlx::query("INSERT INTO pipelined VALUES ($1, $2)")
+                .bind(2_i32)
+                .bind("b"),
+        )
+        .push(sqlx::query("UPDATE pipelined SET label = label || $1").bind("!"))
+        .push("SELECT * FROM pipelined")
+        .execute()
+        .await?;
+
+    let rows_affected: Vec<u64> = results.iter().map(|r| r.rows_affected()).collect();
+    assert_eq!(rows_affected, [1, 1, 2, 2]);
+
+    let labels: Vec<String> = sqlx::query_scalar("SELECT label FROM pipelined ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(labels, ["a!", "b!"]);
+
+    // the queries run in one implicit transaction, so a failure rolls back the others
+    let res = conn
+        .pipeline()
+        .push(
+            sqlx::query("INSERT INTO pipelined VALUES ($1, $2)")
+                .bind(3_i32)
+                .bind("c"),
+        )
+        .push(
+            sqlx::query("INSERT INTO pipelined VALUES ($1, $2)")
+                .bind(1_i32)
+                .bind("d"),
+        )
+        .execute()
+        .await;
+
+    let err = res.unwrap_err().into_database_error().unwrap();
+    assert_eq!(err.code().as_deref(), Some("23505"));
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pipelined")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    assert!(conn.pipeline().execute().await?.is_empty());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
+async fn it_can_begin_with_options() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_2523 (id INTEGER PRIMARY KEY)")
+    let mut tx = conn
+        .begin_with(
+            TransactionOptions::new()
+                .isolation_level(IsolationLevel::Serializable)
+                .read_only(true),
+        )
         .await?;
 
-    conn.execute("TRUNCATE _sqlx_users_2523").await?;
-
-    // begin
-    let mut tx = conn.begin().await?; // transaction
-
-    // insert a user
-    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
-        .bind(50_i32)
-        .execute(&mut *tx)
+    let isolation: String = sqlx::query_scalar("SHOW transaction_isolation")
+        .fetch_one(&mut *tx)
         .await?;
 
-    // begin once more
-    let mut tx2 = tx.begin().await?; // savepoint
+    assert_eq!(isolation, "serializable");
 
-    // insert another user
-    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
-        .bind(10_i32)
-        .execute(&mut *tx2)
-        .await?;
+    // options cannot be applied to a savepoint
+    assert!(tx
+        .begin_with(TransactionOptions::new().read_only(true))
+        .await
+        .is_err());
 
-    // never mind, rollback
-    tx2.rollback().await?; // roll that one back
+    let err = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
 
-    // did we really?
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
-        .fetch_one(&mut *tx)
-        .await?;
+    // read_only_sql_transaction
+    assert_eq!(
+        err.as_database_error().unwrap().code().as_deref(),
+        Some("25006")
+    );
 
-    assert_eq!(count, 1);
+    tx.rollback().await?;
 
-    // actually, commit
-    tx.commit().await?;
+    // the options only applied to that transaction
+    let mut tx = conn.begin().await?;
 
-    // did we really?
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
-        .fetch_one(&mut conn)
+    let read_only: String = sqlx::query_scalar("SHOW transaction_read_only")
+        .fetch_one(&mut *tx)
         .await?;
 
-    assert_eq!(count, 1);
+    assert_eq!(read_only, "off");
 
-    Ok(())
-}
+    tx.rollback().await?;
 
-#[sqlx_macros::test]
-async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+    // from a pool
+    let pool = pool::<Postgres>().await?;
 
-    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_3952 (id INTEGER PRIMARY KEY)")
+    let mut tx = pool
+        .begin_with(TransactionOptions::new().isolation_level(IsolationLevel::RepeatableRead))
         .await?;
 
-    conn.execute("TRUNCATE _sqlx_users_3952").await?;
-
-    // begin .. (drop)
-
-    // run 2 times to see what happens if we drop transactions repeatedly
-    for _ in 0..2 {
-        {
-            let mut tx = conn.begin().await?;
-
-            // do actually something before dropping
-            let _user = sqlx::query("INSERT INTO _sqlx_users_3952 (id) VALUES ($1) RETURNING id")
-                .bind(20_i32)
-                .fetch_one(&mut *tx)
-                .await?;
-        }
+    let isolation: String = sqlx::query_scalar("SHOW transaction_isolation")
+        .fetch_one(&mut *tx)
+        .await?;
 
-        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3952")
-            .fetch_one(&mut conn)
-            .await?;
+    assert_eq!(isolation, "repeatable read");
 
-        assert_eq!(count, 0);
-    }
+    tx.rollback().await?;
 
     Ok(())
 }
@@ -604,118 +1572,421 @@ async fn pool_smoke_test() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn test_invalid_query() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn test_invalid_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("definitely not a correct query")
+        .await
+        .unwrap_err();
+
+    let mut s = conn.fetch("select 1");
+    let row = s.try_next().await?.unwrap();
+
+    assert_eq!(row.get::<i32, _>(0), 1i32);
+
+    Ok(())
+}
+
+/// Tests the edge case of executing a completely empty query string.
+///
+/// This gets flagged as an `EmptyQueryResponse` in Postgres. We
+/// catch this and just return no rows.
+#[sqlx_macros::test]
+async fn test_empty_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let done = conn.execute("").await?;
+
+    assert_eq!(done.rows_affected(), 0);
+
+    Ok(())
+}
+
+/// Test a simple select expression. This should return the row.
+#[sqlx_macros::test]
+async fn test_select_expression() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut s = conn.fetch("SELECT 5");
+    let row = s.try_next().await?.unwrap();
+
+    assert!(5i32 == row.try_get::<i32, _>(0)?);
+
+    Ok(())
+}
+
+/// Test that we can interleave reads and writes to the database
+/// in one simple query. Using the `Cursor` API we should be
+/// able to fetch from both queries in sequence.
+#[sqlx_macros::test]
+async fn test_multi_read_write() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut s = conn.fetch(
+        "
+CREATE TABLE IF NOT EXISTS _sqlx_test_postgres_5112 (
+    id BIGSERIAL PRIMARY KEY,
+    text TEXT NOT NULL
+);
+
+SELECT 'Hello World' as _1;
+
+INSERT INTO _sqlx_test_postgres_5112 (text) VALUES ('this is a test');
+
+SELECT id, text FROM _sqlx_test_postgres_5112;
+    ",
+    );
+
+    let row = s.try_next().await?.unwrap();
+
+    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
+
+    let row = s.try_next().await?.unwrap();
+
+    let id: i64 = row.try_get("id")?;
+    let text: &str = row.try_get("text")?;
+
+    assert_eq!(1_i64, id);
+    assert_eq!("this is a test", text);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_caches_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for i in 0..2 {
+        let row = sqlx::query("SELECT $1 AS val")
+            .bind(Oid(i))
+            .persistent(true)
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: Oid = row.get("val");
+
+        assert_eq!(Oid(i), val);
+    }
+
+    assert_eq!(1, conn.cached_statements_size());
+    conn.clear_cached_statements().await?;
+    assert_eq!(0, conn.cached_statements_size());
+
+    for i in 0..2 {
+        let row = sqlx::query("SELECT $1 AS val")
+            .bind(Oid(i))
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: Oid = row.get("val");
+
+        assert_eq!(Oid(i), val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_closes_statements_that_are_not_cached() -> anyhow::Result<()> {
+    async fn prepared_statements(conn: &mut PgConnection) -> anyhow::Result<i64> {
+        Ok(
+            sqlx::query_scalar("SELECT count(*) FROM pg_prepared_statements")
+                .persistent(false)
+                .fetch_one(&mut *conn)
+                .await?,
+        )
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let before = prepared_statements(&mut conn).await?;
+
+    for i in 0..5 {
+        let val: i32 = sqlx::query_scalar("SELECT $1::int4")
+            .bind(i)
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(i, val);
+
+        // a failing query must not keep its statement around either
+        sqlx::query("SELECT 1 / $1::int4")
+            .bind(0)
+            .persistent(false)
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+    }
+
+    assert_eq!(before, prepared_statements(&mut conn).await?);
+
+    // the same goes for a connection without a statement cache
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.statement_cache_capacity(0)).await?;
+
+    let before = prepared_statements(&mut conn).await?;
+
+    for i in 0..5 {
+        let val: i32 = sqlx::query_scalar("SELECT $1::int4")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(i, val);
+    }
+
+    assert_eq!(before, prepared_statements(&mut conn).await?);
+
+    // nor does describing a query
+    conn.describe("SELECT $1::int4 + 1").await?;
+
+    assert_eq!(before, prepared_statements(&mut conn).await?);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_explicitly_prepared_statements_until_dropped() -> anyhow::Result<()> {
+    async fn prepared_statements(conn: &mut PgConnection, sql: &str) -> anyhow::Result<i64> {
+        Ok(
+            sqlx::query_scalar("SELECT count(*) FROM pg_prepared_statements WHERE statement = $1")
+                .bind(sql)
+                .persistent(false)
+                .fetch_one(&mut *conn)
+                .await?,
+        )
+    }
+
+    const SQL: &str = "SELECT $1::int4 + 1";
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.clone().statement_cache_capacity(1)).await?;
+
+    let statement = conn.prepare(SQL).await?;
+
+    assert_eq!(1, prepared_statements(&mut conn, SQL).await?);
+    assert_eq!(0, conn.cached_statements_size());
+
+    for i in 0..3 {
+        let val: i32 = statement
+            .query_scalar()
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(i + 1, val);
+
+        // evicts whatever is in the cache, which the statement is not part of
+        sqlx::query(&format!("SELECT {i} + $1::int4"))
+            .bind(0)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    // every execution used the statement as it was prepared
+    assert_eq!(1, prepared_statements(&mut conn, SQL).await?);
+
+    // on another connection, it is prepared like any other query
+    let mut other = PgConnection::connect_with(&options).await?;
+
+    let val: i32 = statement
+        .query_scalar()
+        .bind(41)
+        .fetch_one(&mut other)
+        .await?;
+
+    assert_eq!(42, val);
+
+    // a copy keeps it prepared
+    let copy = statement.clone();
+    drop(statement);
+
+    assert_eq!(1, prepared_statements(&mut conn, SQL).await?);
+
+    // it is closed on the next use of the connection after the last copy is dropped
+    drop(copy);
+
+    assert_eq!(0, prepared_statements(&mut conn, SQL).await?);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_statements_on_connect() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    conn.execute("definitely not a correct query")
-        .await
-        .unwrap_err();
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
 
-    let mut s = conn.fetch("select 1");
-    let row = s.try_next().await?.unwrap();
+    let mut conn = PgConnection::connect_with(
+        &options
+            .clone()
+            .prepare_on_connect(["SELECT $1::int4 AS val", "SELECT 1 + 1"]),
+    )
+    .await?;
 
-    assert_eq!(row.get::<i32, _>(0), 1i32);
+    assert_eq!(2, conn.cached_statements_size());
 
-    Ok(())
-}
+    let val: i32 = sqlx::query_scalar("SELECT $1::int4 AS val")
+        .bind(5_i32)
+        .fetch_one(&mut conn)
+        .await?;
 
-/// Tests the edge case of executing a completely empty query string.
-///
-/// This gets flagged as an `EmptyQueryResponse` in Postgres. We
-/// catch this and just return no rows.
-#[sqlx_macros::test]
-async fn test_empty_query() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    let done = conn.execute("").await?;
+    assert_eq!(5, val);
+    assert_eq!(2, conn.cached_statements_size());
 
-    assert_eq!(done.rows_affected(), 0);
+    let err = PgConnection::connect_with(
+        &options.prepare_on_connect(["SELECT 1", "SELECT * FROM not_a_table"]),
+    )
+    .await
+    .unwrap_err();
+
+    // the error for the statement is returned as is
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("42P01")
+    );
 
     Ok(())
 }
 
-/// Test a simple select expression. This should return the row.
 #[sqlx_macros::test]
-async fn test_select_expression() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_captures_query_plans() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-    let mut s = conn.fetch("SELECT 5");
-    let row = s.try_next().await?.unwrap();
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.capture_query_plans(true)).await?;
 
-    assert!(5i32 == row.try_get::<i32, _>(0)?);
+    let mut s = sqlx::query("SELECT v FROM generate_series(1, $1) v")
+        .bind(10_i32)
+        .fetch_many(&mut conn);
 
-    Ok(())
-}
+    let mut rows = 0;
+    let mut plan = None;
 
-/// Test that we can interleave reads and writes to the database
-/// in one simple query. Using the `Cursor` API we should be
-/// able to fetch from both queries in sequence.
-#[sqlx_macros::test]
-async fn test_multi_read_write() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+    while let Some(step) = s.try_next().await? {
+        match step {
+            sqlx::Either::Left(done) => plan = done.query_plan().map(ToOwned::to_owned),
+            sqlx::Either::Right(_) => rows += 1,
+        }
+    }
 
-    let mut s = conn.fetch(
-        "
-CREATE TABLE IF NOT EXISTS _sqlx_test_postgres_5112 (
-    id BIGSERIAL PRIMARY KEY,
-    text TEXT NOT NULL
-);
+    drop(s);
 
-SELECT 'Hello World' as _1;
+    assert_eq!(rows, 10);
 
-INSERT INTO _sqlx_test_postgres_5112 (text) VALUES ('this is a test');
+    let plan: serde_json::Value = serde_json::from_str(&plan.expect("expected a query plan"))?;
+    assert_eq!(plan[0]["Plan"]["Node Type"], "Function Scan");
+    assert_eq!(plan[0]["Plan"]["Actual Rows"], 10);
 
-SELECT id, text FROM _sqlx_test_postgres_5112;
-    ",
-    );
+    // writes made while explaining are rolled back, even inside a transaction
+    conn.execute("CREATE TEMPORARY TABLE explain_test (id INT)")
+        .await?;
 
-    let row = s.try_next().await?.unwrap();
+    let mut tx = conn.begin().await?;
 
-    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
+    let done = tx.execute("INSERT INTO explain_test VALUES (1)").await?;
+    assert!(done.query_plan().is_some());
 
-    let row = s.try_next().await?.unwrap();
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM explain_test")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(count, 1);
 
-    let id: i64 = row.try_get("id")?;
-    let text: &str = row.try_get("text")?;
+    tx.commit().await?;
 
-    assert_eq!(1_i64, id);
-    assert_eq!("this is a test", text);
+    // statements which cannot be explained still execute
+    let done = conn.execute("DROP TABLE explain_test").await?;
+    assert!(done.query_plan().is_none());
+
+    // only plain DML statements are explained, even if others could be
+    let done = conn
+        .execute("CREATE TEMPORARY TABLE explain_ctas AS SELECT 1 AS x")
+        .await?;
+    assert!(done.query_plan().is_none());
+
+    let done = conn.execute("  select x from explain_ctas").await?;
+    assert!(done.query_plan().is_some());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_caches_statements() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_sends_startup_parameters() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-    for i in 0..2 {
-        let row = sqlx::query("SELECT $1 AS val")
-            .bind(Oid(i))
-            .persistent(true)
-            .fetch_one(&mut conn)
-            .await?;
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.clone().startup_parameters([
+        ("application_name", "sqlx-test"),
+        ("search_path", "pg_catalog"),
+        ("statement_timeout", "5min"),
+    ]))
+    .await?;
 
-        let val: Oid = row.get("val");
+    let (application_name, search_path, statement_timeout): (String, String, String) =
+        sqlx::query_as(
+            "SELECT current_setting('application_name'), current_setting('search_path'), \
+             current_setting('statement_timeout')",
+        )
+        .fetch_one(&mut conn)
+        .await?;
 
-        assert_eq!(Oid(i), val);
+    assert_eq!(application_name, "sqlx-test");
+    assert_eq!(search_path, "pg_catalog");
+    assert_eq!(statement_timeout, "5min");
+
+    for parameters in [[("application_name", "sqlx\0test")], [("user", "postgres")]] {
+        let err = PgConnection::connect_with(&options.clone().startup_parameters(parameters))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
     }
 
-    assert_eq!(1, conn.cached_statements_size());
-    conn.clear_cached_statements().await?;
-    assert_eq!(0, conn.cached_statements_size());
+    Ok(())
+}
 
-    for i in 0..2 {
-        let row = sqlx::query("SELECT $1 AS val")
-            .bind(Oid(i))
-            .persistent(false)
-            .fetch_one(&mut conn)
-            .await?;
+#[cfg(unix)]
+#[sqlx_macros::test]
+async fn it_connects_over_unix_socket() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-        let val: Oid = row.get("val");
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-        assert_eq!(Oid(i), val);
-    }
+    let directories: String = sqlx::query_scalar("SHOW unix_socket_directories")
+        .fetch_one(&mut conn)
+        .await?;
 
-    assert_eq!(0, conn.cached_statements_size());
+    let port: String = sqlx::query_scalar("SHOW port").fetch_one(&mut conn).await?;
+    let port: u16 = port.parse()?;
+
+    // the server may be running on another host or in a container
+    let Some(directory) = directories.split(',').map(str::trim).find(|directory| {
+        std::path::Path::new(directory)
+            .join(format!(".s.PGSQL.{port}"))
+            .exists()
+    }) else {
+        return Ok(());
+    };
+
+    // TLS is never negotiated over the socket, so requiring it must not fail
+    let mut conn = PgConnection::connect_with(
+        &options
+            .socket(directory)
+            .port(port)
+            .ssl_mode(sqlx::postgres::PgSslMode::Require),
+    )
+    .await?;
+
+    // `inet_server_addr()` is NULL for connections over a Unix-domain socket
+    let is_unix: bool = sqlx::query_scalar("SELECT inet_server_addr() IS NULL")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(is_unix);
 
     Ok(())
 }
@@ -767,6 +2038,30 @@ async fn it_sets_application_name() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_rejects_writes_on_read_only_connection() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.read_only(true)).await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    let err = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    // read_only_sql_transaction
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("25006")
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
     new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
@@ -803,6 +2098,74 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_rejects_mismatched_argument_count() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // too few arguments
+    let res = sqlx::query("SELECT $1::int4, $2::int4")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await;
+
+    let err = res.unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::Protocol(msg) if msg == "expected 2 arguments for query but 1 were bound"),
+        "{err:?}"
+    );
+
+    // too many arguments for a statement that was already prepared
+    let statement = conn.prepare("SELECT $1::int4").await?;
+
+    let res = statement
+        .query()
+        .bind(1_i32)
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await;
+
+    let err = res.unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::Protocol(msg) if msg == "expected 1 arguments for query but 2 were bound"),
+        "{err:?}"
+    );
+
+    // the connection is still usable
+    let value: i32 = statement
+        .query_scalar()
+        .bind(3_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_each_column_in_its_result_format() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `aclitem` has no binary output function so it must be requested as text,
+    // while the other column can still be sent in binary
+    for _ in 0..2 {
+        let row = sqlx::query("SELECT $1::int8, 'postgres=r/postgres'::aclitem")
+            .bind(42_i64)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(row.try_get_raw(0)?.format(), PgValueFormat::Binary);
+        assert_eq!(row.try_get::<i64, _>(0)?, 42);
+
+        assert_eq!(row.try_get_raw(1)?.format(), PgValueFormat::Text);
+        assert_eq!(
+            row.try_get_unchecked::<String, _>(1)?,
+            "postgres=r/postgres"
+        );
+    }
+
+    Ok(())
+}
+
 // repro is more reliable with the basic scheduler used by `#[tokio::test]`
 #[cfg(feature = "_rt-tokio")]
 #[tokio::test]
@@ -995,6 +2358,46 @@ async fn test_listener_cleanup() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_pg_listener_resubscribes_after_reconnect() -> anyhow::Result<()> {
+    let mut notify_conn = new::<Postgres>().await?;
+    let mut listener = PgListener::connect(&env::var("DATABASE_URL")?).await?;
+
+    // listening twice and unlistening once must not leave the channel to be re-subscribed
+    listener.listen("reconnect_a").await?;
+    listener.listen("reconnect_a").await?;
+    listener.unlisten("reconnect_a").await?;
+    listener.listen_all(["reconnect_b", "reconnect_b"]).await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut listener)
+        .await?;
+
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&mut notify_conn)
+        .await?;
+
+    // the lost connection is reported once, then replaced on the next use
+    assert!(listener.try_recv().await?.is_none());
+
+    let new_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut listener)
+        .await?;
+
+    assert_ne!(pid, new_pid);
+
+    notify_conn.execute("NOTIFY reconnect_a, 'a'").await?;
+    notify_conn.execute("NOTIFY reconnect_b, 'b'").await?;
+
+    let notification = listener.recv().await?;
+
+    assert_eq!(notification.channel(), "reconnect_b");
+    assert_eq!(notification.payload(), "b");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
     let pool = pool::<Postgres>().await?;
@@ -1012,6 +2415,42 @@ async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_closes_pool_after_close_timeout() -> anyhow::Result<()> {
+    let options = PgConnectOptions::new()
+        .host("127.0.0.1")
+        .port(fake_pg_server(answer_sync)?)
+        .ssl_mode(PgSslMode::Disable);
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .close_timeout(Duration::from_millis(500))
+        .connect_with(options)
+        .await?;
+
+    // held for the whole test, which would normally prevent the pool from closing
+    let mut conn = pool.acquire().await?;
+
+    sqlx_core::rt::timeout(Duration::from_secs(10), pool.close())
+        .await
+        .expect("pool.close() should give up waiting after `close_timeout`");
+
+    assert!(pool.is_closed());
+
+    // the connection held across the close is poisoned
+    let res = sqlx::Acquire::begin(&mut conn).await;
+    assert!(matches!(res, Err(sqlx::Error::PoolClosed)), "{res:?}");
+    drop(res);
+
+    // and so is its socket, even when used directly
+    match conn.ping().await {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::ConnectionAborted),
+        other => panic!("expected the connection to be aborted, got {other:?}"),
+    }
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
     // Only supported in Postgres 11+
@@ -1505,15 +2944,103 @@ async fn it_can_abort_copy_in() -> anyhow::Result<()> {
         )
         .await?;
 
-    copy.abort("this is only a test").await?;
+    copy.abort("this is only a test").await?;
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_streams_a_bytea_parameter_from_a_file() -> anyhow::Result<()> {
+    #[cfg(feature = "_rt-tokio")]
+    use tokio::fs::File;
+
+    #[cfg(not(feature = "_rt-tokio"))]
+    use async_std::fs::File;
+
+    use sha2::{Digest, Sha256};
+    use sqlx::postgres::types::PgByteaStream;
+
+    // large enough to take many reads and flushes
+    let data: Vec<u8> = (0..20 * 1024 * 1024 + 7).map(|i| (i % 251) as u8).collect();
+
+    let dir = tempdir::TempDir::new("sqlx-bytea-stream")?;
+    let path = dir.path().join("data.bin");
+    std::fs::write(&path, &data)?;
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE uploads (id INT, data BYTEA)")
+        .await?;
+
+    let file = File::open(&path).await?;
+    let len = file.metadata().await?.len();
+
+    let done = sqlx::query("INSERT INTO uploads (id, data) VALUES ($1, $2)")
+        .bind(1_i32)
+        .bind(PgByteaStream::new(file, len))
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 1);
+
+    let (stored_len, digest): (i32, Vec<u8>) =
+        sqlx::query_as("SELECT length(data), sha256(data) FROM uploads WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(stored_len as usize, data.len());
+    assert_eq!(digest, Sha256::digest(&data).to_vec());
+
+    // the connection is still in a usable state
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM uploads")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fails_to_stream_a_bytea_parameter_of_the_wrong_length() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgByteaStream;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let short = sqlx::query("SELECT length($1)")
+        .bind(PgByteaStream::new(&b"hello"[..], 10))
+        .execute(&mut conn)
+        .await;
+
+    match short {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof),
+        other => panic!("expected an I/O error, got {other:?}"),
+    }
+
+    // the server is left waiting for the rest of the message
+    assert!(conn.ping().await.is_err());
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
-        .fetch_one(&mut conn)
-        .await?;
+    let mut conn = new::<Postgres>().await?;
 
-    assert_eq!(2i32, value);
+    let long = sqlx::query("SELECT length($1)")
+        .bind(PgByteaStream::new(&b"hello, world"[..], 5))
+        .execute(&mut conn)
+        .await;
+
+    match long {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput),
+        other => panic!("expected an I/O error, got {other:?}"),
+    }
+
+    assert!(conn.ping().await.is_err());
 
     Ok(())
 }
@@ -1550,6 +3077,94 @@ async fn it_can_copy_out() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_discards_the_rest_of_an_unfinished_copy_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let mut copy = conn
+            .copy_out_raw("COPY (SELECT generate_series(1, 100000)) TO STDOUT")
+            .await?;
+
+        assert_eq!(copy.next().await.unwrap()?, "1\n");
+    }
+
+    // conn is safe for reuse
+    let value: i32 = sqlx::query_scalar("select 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_aborts_copy_in_when_dropped() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER NOT NULL)")
+        .await?;
+
+    {
+        let mut copy = conn.copy_in_raw("COPY users (id) FROM STDIN").await?;
+        copy.send("1\n2\n".as_bytes()).await?;
+    }
+
+    // conn is safe for reuse and nothing was copied
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_reuse_the_connection_after_a_failed_copy() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER NOT NULL)")
+        .await?;
+
+    async fn select_one(conn: &mut PgConnection) -> anyhow::Result<()> {
+        let value: i32 = sqlx::query_scalar("select 1").fetch_one(conn).await?;
+        assert_eq!(value, 1);
+
+        Ok(())
+    }
+
+    // the statement fails
+    assert!(conn
+        .copy_out_raw("COPY not_a_table TO STDOUT")
+        .await
+        .is_err());
+    select_one(&mut conn).await?;
+
+    assert!(conn
+        .copy_in_raw("COPY not_a_table FROM STDIN")
+        .await
+        .is_err());
+    select_one(&mut conn).await?;
+
+    // the statement copies the other way
+    assert!(conn
+        .copy_in_raw("COPY (SELECT generate_series(1, 1000)) TO STDOUT")
+        .await
+        .is_err());
+    select_one(&mut conn).await?;
+
+    assert!(conn.copy_out_raw("COPY users FROM STDIN").await.is_err());
+    select_one(&mut conn).await?;
+
+    // the data is rejected
+    let mut copy = conn.copy_in_raw("COPY users (id) FROM STDIN").await?;
+    copy.send("not a number\n".as_bytes()).await?;
+    assert!(copy.finish().await.is_err());
+    select_one(&mut conn).await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
     use sqlx::encode::IsNull;
@@ -1813,3 +3428,397 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_can_cancel_a_streaming_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let token = conn.cancel_token();
+
+    let sql = "SELECT generate_series(1, 1000000000::int8)";
+
+    // cancel, then read the stream until the server reports the cancellation
+    {
+        let mut rows = sqlx::query_scalar::<_, i64>(sql).fetch(&mut conn);
+
+        for i in 1..=1000 {
+            assert_eq!(rows.try_next().await?, Some(i));
+        }
+
+        token.cancel().await?;
+
+        let error = loop {
+            match rows.try_next().await {
+                Ok(Some(_)) => continue,
+                Ok(None) => panic!("the query should have been cancelled"),
+                Err(error) => break error,
+            }
+        };
+
+        assert_eq!(
+            error.into_database_error().unwrap().code().as_deref(),
+            Some("57014")
+        );
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    // cancel, then drop the stream and leave the rest of the result to the connection
+    {
+        let mut rows = sqlx::query_scalar::<_, i64>(sql).fetch(&mut conn);
+
+        for i in 1..=1000 {
+            assert_eq!(rows.try_next().await?, Some(i));
+        }
+
+        token.cancel().await?;
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT 2").fetch_one(&mut conn).await?;
+    assert_eq!(value, 2);
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_an_abandoned_query_when_asked() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.cancel_on_drop(true)).await?;
+
+    // abandon the query while it is still running
+    {
+        let mut rows = sqlx::query("SELECT pg_sleep(30)").fetch(&mut conn);
+        let res = sqlx_core::rt::timeout(Duration::from_millis(500), rows.try_next()).await;
+        assert!(res.is_err());
+    }
+
+    // the next query does not wait for the abandoned one to complete
+    let start = std::time::Instant::now();
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    // and is not cancelled itself
+    let value: i32 = sqlx::query_scalar("SELECT 2 FROM pg_sleep(1)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_an_in_list() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    async fn select_ids(conn: &mut PgConnection, ids: Vec<i32>) -> anyhow::Result<Vec<i32>> {
+        let mut qb = QueryBuilder::<Postgres>::new(
+            "SELECT id FROM (VALUES (1), (2), (3), (4), (5)) AS t(id) WHERE id > ",
+        );
+
+        qb.push_bind(1i32)
+            .push(" AND id ")
+            .push_in_list(ids)
+            .push(" AND id < ")
+            .push_bind(5i32)
+            .push(" ORDER BY id");
+
+        Ok(qb.build_query_scalar().fetch_all(conn).await?)
+    }
+
+    assert_eq!(select_ids(&mut conn, vec![]).await?, Vec::<i32>::new());
+    assert_eq!(select_ids(&mut conn, vec![3]).await?, vec![3]);
+    assert_eq!(select_ids(&mut conn, vec![1, 2, 4]).await?, vec![2, 4]);
+
+    let mut qb = QueryBuilder::<Postgres>::new("SELECT 1 WHERE 1 = ");
+    qb.push_bind(1i32)
+        .push(" AND 2 ")
+        .push_in_list([1i32, 2, 3]);
+    assert_eq!(qb.sql(), "SELECT 1 WHERE 1 = $1 AND 2 IN ($2, $3, $4)");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_enforces_a_statement_timeout() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let previous: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // the server cancels the statement
+    let res = conn
+        .with_statement_timeout(Duration::from_millis(100), |conn| {
+            Box::pin(async move { sqlx::query("SELECT pg_sleep(5)").execute(conn).await })
+        })
+        .await;
+
+    let err = res.unwrap_err();
+    assert_eq!(
+        err.as_database_error().unwrap().code().as_deref(),
+        Some("57014")
+    );
+
+    // the timeout does not leak into the session
+    let value: i32 = conn
+        .with_statement_timeout(Duration::from_secs(5), |conn| {
+            Box::pin(async move { sqlx::query_scalar("SELECT 1").fetch_one(conn).await })
+        })
+        .await?;
+    assert_eq!(value, 1);
+
+    let current: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(current, previous);
+
+    // within a transaction, the previous value is restored
+    let mut tx = conn.begin().await?;
+    tx.execute("SET LOCAL statement_timeout = '1min'").await?;
+
+    tx.with_statement_timeout(Duration::from_secs(5), |conn| {
+        Box::pin(async move { sqlx::query("SELECT 1").execute(conn).await })
+    })
+    .await?;
+
+    let current: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(current, "1min");
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_detects_concurrent_updates_with_a_version_column() -> anyhow::Result<()> {
+    use sqlx::postgres::{PgVersionedUpdate, PgVersionedUpdateError};
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE accounts (
+    id INT8 PRIMARY KEY,
+    balance INT8 NOT NULL,
+    version INT4 NOT NULL
+);
+
+INSERT INTO accounts (id, balance, version) VALUES (1, 10, 1);
+        "#,
+    )
+    .await?;
+
+    let update =
+        |balance: i64| PgVersionedUpdate::new("accounts", "id", "version").set("balance", balance);
+
+    // up to date
+    let result = update(20).execute(&mut conn, 1_i64, 1_i32).await?;
+    assert_eq!(result.rows_affected(), 1);
+
+    let (balance, version): (i64, i32) =
+        sqlx::query_as("SELECT balance, version FROM accounts WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!((balance, version), (20, 2));
+
+    // stale version
+    let res = update(30).execute(&mut conn, 1_i64, 1_i32).await;
+    assert!(matches!(res, Err(PgVersionedUpdateError::Conflict)));
+
+    let balance: i64 = sqlx::query_scalar("SELECT balance FROM accounts WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(balance, 20);
+
+    // no such row
+    let res = update(30).execute(&mut conn, 2_i64, 2_i32).await;
+    assert!(matches!(
+        res,
+        Err(PgVersionedUpdateError::Execute(sqlx::Error::RowNotFound))
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_infers_untyped_parameters_from_bound_values() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // without a type hint the server could not determine the type of `$1`
+    let (value, ty): (i64, String) = sqlx::query_as("SELECT $1, pg_typeof($1)::text")
+        .bind(5_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!((value, &*ty), (5, "bigint"));
+
+    // the same statement bound with another type must not reuse the `int8` one from the cache
+    let (value, ty): (String, String) = sqlx::query_as("SELECT $1, pg_typeof($1)::text")
+        .bind("five")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!((&*value, &*ty), ("five", "text"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_honors_a_query_deadline_across_acquire_and_execute() -> anyhow::Result<()> {
+    // nothing listens on this port, so every connection attempt is refused and retried
+    let port = std::net::TcpListener::bind("127.0.0.1:0")?
+        .local_addr()?
+        .port();
+
+    let unreachable = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(30))
+        .connect_lazy_with(
+            PgConnectOptions::new()
+                .host("127.0.0.1")
+                .port(port)
+                .ssl_mode(PgSslMode::Disable),
+        );
+
+    let start = std::time::Instant::now();
+
+    let res = sqlx::query("SELECT 1")
+        .with_deadline(start + Duration::from_millis(300))
+        .execute(&unreachable)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)), "{res:?}");
+    assert!(
+        start.elapsed() < Duration::from_secs(1),
+        "{:?}",
+        start.elapsed()
+    );
+
+    // the same deadline covers acquiring a connection and running the query
+    let pool = pool::<Postgres>().await?;
+
+    let start = std::time::Instant::now();
+
+    let res = sqlx::query_scalar::<_, i32>("SELECT 1 FROM pg_sleep(1)")
+        .with_deadline(start + Duration::from_millis(200))
+        .fetch_one(&pool)
+        .await;
+
+    match res {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+
+    assert!(
+        start.elapsed() < Duration::from_millis(800),
+        "{:?}",
+        start.elapsed()
+    );
+
+    // streams are cut off as well
+    let mut conn = new::<Postgres>().await?;
+
+    let start = std::time::Instant::now();
+
+    let res: Result<Vec<i32>, _> =
+        sqlx::query_scalar::<_, i32>("SELECT x FROM generate_series(1, 2) x, pg_sleep(1)")
+            .with_deadline(start + Duration::from_millis(200))
+            .fetch(&mut conn)
+            .try_collect()
+            .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Io(_))), "{res:?}");
+    assert!(
+        start.elapsed() < Duration::from_millis(800),
+        "{:?}",
+        start.elapsed()
+    );
+
+    // a deadline that is not reached changes nothing
+    let value: i32 = sqlx::query_scalar("SELECT 42")
+        .with_deadline(std::time::Instant::now() + Duration::from_secs(30))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 42);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_unqualified_names_in_the_default_schema() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // mixed case and a space to check that the name is quoted
+    conn.execute(
+        r#"
+DROP SCHEMA IF EXISTS "Default Schema" CASCADE;
+CREATE SCHEMA "Default Schema";
+CREATE TABLE "Default Schema".default_schema_items (name TEXT NOT NULL);
+INSERT INTO "Default Schema".default_schema_items (name) VALUES ('in the default schema');
+        "#,
+    )
+    .await?;
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .default_schema("Default Schema");
+
+    assert_eq!(options.get_default_schema(), Some("Default Schema"));
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .after_release(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute("RESET search_path").await?;
+                Ok(true)
+            })
+        })
+        .connect_with(options)
+        .await?;
+
+    let mut pooled = pool.acquire().await?;
+
+    let name: String = sqlx::query_scalar("SELECT name FROM default_schema_items")
+        .fetch_one(&mut *pooled)
+        .await?;
+    assert_eq!(name, "in the default schema");
+
+    // a session-level change made by one user of the connection ...
+    pooled.execute("SET search_path = public").await?;
+
+    let res = sqlx::query("SELECT name FROM default_schema_items")
+        .execute(&mut *pooled)
+        .await;
+    assert!(res.is_err());
+
+    drop(pooled);
+
+    // ... is reset to the default schema, not the server default, before the next one
+    let mut pooled = pool.acquire().await?;
+
+    let schema: String = sqlx::query_scalar("SELECT current_schema()")
+        .fetch_one(&mut *pooled)
+        .await?;
+    assert_eq!(schema, "Default Schema");
+
+    let name: String = sqlx::query_scalar("SELECT name FROM default_schema_items")
+        .fetch_one(&mut *pooled)
+        .await?;
+    assert_eq!(name, "in the default schema");
+
+    drop(pooled);
+    pool.close().await;
+
+    conn.execute(r#"DROP SCHEMA "Default Schema" CASCADE"#)
+        .await?;
+
+    Ok(())
+}
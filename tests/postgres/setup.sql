@@ -15,6 +15,12 @@ CREATE TYPE inventory_item AS
     price       BIGINT
 );
 
+CREATE TYPE int_text_pair AS
+(
+    number INT,
+    label  TEXT
+);
+
 -- https://github.com/prisma/database-schema-examples/tree/master/postgres/basic-twitter#basic-twitter
 CREATE TABLE tweet
 (
@@ -2,7 +2,7 @@ extern crate time_ as time;
 
 use std::ops::Bound;
 
-use sqlx::postgres::types::{Oid, PgCiText, PgInterval, PgMoney, PgRange};
+use sqlx::postgres::types::{Oid, PgByteaReader, PgCiText, PgInterval, PgMoney, PgRange, PgXml};
 use sqlx::postgres::Postgres;
 use sqlx_test::{test_decode_type, test_prepared_type, test_type};
 
@@ -100,6 +100,11 @@ test_type!(i16(
     "821::smallint" == 821_i16,
 ));
 
+test_type!(i16_vec<Vec<i16>>(Postgres,
+    "'{5,-10,32767,-32768}'::int2[]" == vec![5_i16, -10, 32767, -32768],
+    "'{}'::int2[]" == Vec::<i16>::new(),
+));
+
 test_type!(i32(
     Postgres,
     "94101::int" == 94101_i32,
@@ -229,6 +234,35 @@ test_type!(bitvec<sqlx::types::BitVec>(
     },
 ));
 
+#[cfg(feature = "bit-vec")]
+test_decode_type!(bitvec_from_bool_array<sqlx::types::BitVec>(
+    Postgres,
+    "array(SELECT x % 3 = 0 FROM generate_series(0, 63) x)"
+        == (0..64).map(|x| x % 3 == 0).collect::<sqlx::types::BitVec>(),
+    "'{}'::bool[]" == sqlx::types::BitVec::new(),
+));
+
+#[cfg(feature = "bit-vec")]
+#[sqlx_macros::test]
+async fn test_bitvec_from_bool_array_with_null() -> anyhow::Result<()> {
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    let res = sqlx::query_scalar::<_, sqlx::types::BitVec>("SELECT '{true,NULL}'::bool[]")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    // still decodes as `Vec<Option<bool>>`
+    let bools: Vec<Option<bool>> = sqlx::query_scalar("SELECT '{true,NULL}'::bool[]")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(bools, [Some(true), None]);
+
+    Ok(())
+}
+
 #[cfg(feature = "ipnetwork")]
 test_type!(ipnetwork_vec<Vec<sqlx::types::ipnetwork::IpNetwork>>(Postgres,
     "'{127.0.0.1,8.8.8.8/24}'::inet[]"
@@ -297,6 +331,53 @@ mod chrono {
             ]
     ));
 
+    test_type!(chrono_date_time_tz_vec_with_null<Vec<Option<DateTime::<Utc>>>>(Postgres,
+        "array['2019-01-02 05:10:20.115100+00', NULL, '2024-02-29 23:59:59+02']::timestamptz[]"
+            == vec![
+                Some(Utc.ymd(2019, 1, 2).and_hms_micro(5, 10, 20, 115100)),
+                None,
+                Some(Utc.ymd(2024, 2, 29).and_hms(21, 59, 59)),
+            ]
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_chrono_infinity_is_an_error() -> anyhow::Result<()> {
+        let mut conn = sqlx_test::new::<Postgres>().await?;
+
+        for sql in [
+            "SELECT 'infinity'::timestamp",
+            "SELECT '-infinity'::timestamp",
+        ] {
+            let res = sqlx::query_scalar::<_, NaiveDateTime>(sql)
+                .fetch_one(&mut conn)
+                .await;
+
+            assert!(
+                matches!(res, Err(sqlx::Error::ColumnDecode { .. })),
+                "{sql}"
+            );
+        }
+
+        let res = sqlx::query_scalar::<_, DateTime<Utc>>("SELECT 'infinity'::timestamptz")
+            .fetch_one(&mut conn)
+            .await;
+
+        assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+        for sql in ["SELECT 'infinity'::date", "SELECT '-infinity'::date"] {
+            let res = sqlx::query_scalar::<_, NaiveDate>(sql)
+                .fetch_one(&mut conn)
+                .await;
+
+            assert!(
+                matches!(res, Err(sqlx::Error::ColumnDecode { .. })),
+                "{sql}"
+            );
+        }
+
+        Ok(())
+    }
+
     test_type!(chrono_time_tz<PgTimeTz>(Postgres,
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: NaiveTime::from_hms_micro(5, 10, 20, 115100), offset: FixedOffset::east(0) },
         "TIMETZ '05:10:20.115100+06:30'" == PgTimeTz { time: NaiveTime::from_hms_micro(5, 10, 20, 115100), offset: FixedOffset::east(60 * 60 * 6 + 1800) },
@@ -339,6 +420,45 @@ mod time_tests {
                 .assume_utc()
     ));
 
+    test_type!(time_timestamp_vec_with_null<Vec<Option<OffsetDateTime>>>(
+        Postgres,
+        "array['2019-01-02 05:10:20.115100+00', NULL, '2024-02-29 23:59:59+02']::timestamptz[]"
+            == vec![
+                Some(date!(2019 - 1 - 2).with_time(time!(5:10:20.115100)).assume_utc()),
+                None,
+                Some(date!(2024 - 2 - 29).with_time(time!(21:59:59)).assume_utc()),
+            ]
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_time_timestamptz_text_with_negative_offset() -> anyhow::Result<()> {
+        use sqlx::Executor;
+
+        let mut conn = sqlx_test::new::<Postgres>().await?;
+
+        // timestamptz values are sent with the UTC offset of the session's time zone
+        conn.execute("SET TIME ZONE 'America/New_York'").await?;
+
+        // array elements are decoded from the text format
+        let values: Vec<OffsetDateTime> = sqlx::query_scalar(
+            "SELECT array['2019-01-02 05:10:20+00', '2019-01-02 05:10:20.1151+00']::timestamptz[]",
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+        assert_eq!(
+            values,
+            [
+                date!(2019 - 1 - 2).with_time(time!(5:10:20)).assume_utc(),
+                date!(2019 - 1 - 2)
+                    .with_time(time!(5:10:20.115100))
+                    .assume_utc(),
+            ]
+        );
+
+        Ok(())
+    }
+
     test_prepared_type!(time_time_tz<PgTimeTz>(Postgres,
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(0).unwrap() },
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(0).unwrap() },
@@ -354,7 +474,7 @@ mod json {
     use serde_json::value::RawValue as JsonRawValue;
     use serde_json::{json, Value as JsonValue};
     use sqlx::postgres::PgRow;
-    use sqlx::types::Json;
+    use sqlx::types::{Json, RawJson};
     use sqlx::{Executor, Row};
     use sqlx_test::new;
 
@@ -413,6 +533,60 @@ mod json {
             ]
     ));
 
+    test_type!(json_nested<JsonValue>(
+        Postgres,
+        "SELECT ({0}::jsonb is not distinct from $1::jsonb)::int4, {0} as _2, $2 as _3",
+        "'{\"name\":\"Joe\",\"friends\":[{\"name\":\"Bob\",\"tags\":{\"a\":1,\"b\":null}}]}'::json"
+            == json!({ "name": "Joe", "friends": [{ "name": "Bob", "tags": { "a": 1, "b": null } }] })
+    ));
+
+    test_type!(jsonb_nested<JsonValue>(
+        Postgres,
+        "'{\"name\":\"Joe\",\"friends\":[{\"name\":\"Bob\",\"tags\":{\"a\":1,\"b\":null}}]}'::jsonb"
+            == json!({ "name": "Joe", "friends": [{ "name": "Bob", "tags": { "a": 1, "b": null } }] })
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_json_arbitrary_precision() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let value: JsonValue = serde_json::from_str(
+            r#"{"id":9223372036854775807,"amount":3.14159265358979323846264338327950288}"#,
+        )?;
+
+        // unprepared, text API
+        let row: PgRow = conn
+            .fetch_one(
+                r#"SELECT '{"id":9223372036854775807,"amount":3.14159265358979323846264338327950288}'::jsonb"#,
+            )
+            .await?;
+
+        let decoded: JsonValue = row.try_get(0)?;
+
+        assert_eq!(decoded, value);
+
+        // prepared, binary API
+        let decoded: JsonValue = sqlx::query_scalar("SELECT $1::jsonb")
+            .bind(&value)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(decoded, value);
+        assert_eq!(decoded["id"].as_i64(), Some(i64::MAX));
+
+        // without `json-arbitrary-precision`, the number is rounded to the nearest `f64`
+        if cfg!(feature = "json-arbitrary-precision") {
+            assert_eq!(
+                decoded["amount"].to_string(),
+                "3.14159265358979323846264338327950288"
+            );
+        } else {
+            assert_eq!(decoded["amount"].as_f64(), Some(std::f64::consts::PI));
+        }
+
+        Ok(())
+    }
+
     #[sqlx_macros::test]
     async fn test_json_raw_value() -> anyhow::Result<()> {
         let mut conn = new::<Postgres>().await?;
@@ -437,6 +611,48 @@ mod json {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_bind_raw_json() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        conn.execute("CREATE TEMPORARY TABLE raw_json (data jsonb NOT NULL)")
+            .await?;
+
+        let cached = r#"{"name":"Joe","friends":[{"name":"Bob","age":22}]}"#;
+
+        sqlx::query("INSERT INTO raw_json (data) VALUES ($1)")
+            .bind(RawJson(cached))
+            .execute(&mut conn)
+            .await?;
+
+        let data: JsonValue = sqlx::query_scalar("SELECT data FROM raw_json")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(
+            data,
+            json!({ "name": "Joe", "friends": [{ "name": "Bob", "age": 22 }] })
+        );
+
+        // also encodes as `json[]`/`jsonb[]` elements
+        let data: Vec<JsonValue> = sqlx::query_scalar("SELECT unnest($1::json[])::jsonb")
+            .bind(vec![RawJson("[1, 2]"), RawJson("null")])
+            .fetch_all(&mut conn)
+            .await?;
+
+        assert_eq!(data, vec![json!([1, 2]), json!(null)]);
+
+        // the string is not checked client-side, so invalid JSON is left to the server to reject
+        let res = sqlx::query("SELECT $1::jsonb")
+            .bind(RawJson("{not json"))
+            .execute(&mut conn)
+            .await;
+
+        assert!(res.is_err());
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "bigdecimal")]
@@ -465,6 +681,22 @@ test_type!(bigdecimal<sqlx::types::BigDecimal>(Postgres,
     "0.00000002::numeric" == "0.00000002".parse::<sqlx::types::BigDecimal>().unwrap(),
     "12.34::numeric" == "12.34".parse::<sqlx::types::BigDecimal>().unwrap(),
     "12345.6789::numeric" == "12345.6789".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "12000000000::numeric" == "12000000000".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "-12000000000::numeric" == "-12000000000".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "100000000::numeric" == "100000000".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "1000000000000000000000000000000000000000::numeric" == "1e39".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "0.00001::numeric" == "0.00001".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "0.00000001::numeric" == "0.00000001".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "1.00001::numeric" == "1.00001".parse::<sqlx::types::BigDecimal>().unwrap(),
+));
+
+#[cfg(feature = "bigdecimal")]
+test_type!(bigdecimal_vec<Vec<Option<sqlx::types::BigDecimal>>>(Postgres,
+    "array[12.34, NULL, -0.005]::numeric[]" == vec![
+        Some("12.34".parse::<sqlx::types::BigDecimal>().unwrap()),
+        None,
+        Some("-0.005".parse::<sqlx::types::BigDecimal>().unwrap()),
+    ],
 ));
 
 #[cfg(feature = "bigdecimal")]
@@ -477,17 +709,99 @@ test_type!(numrange_bigdecimal<PgRange<sqlx::types::BigDecimal>>(Postgres,
 #[cfg(feature = "rust_decimal")]
 test_type!(decimal<sqlx::types::Decimal>(Postgres,
     "0::numeric" == sqlx::types::Decimal::from_str("0").unwrap(),
+    "0.00::numeric" == sqlx::types::Decimal::from_str("0.00").unwrap(),
     "1::numeric" == sqlx::types::Decimal::from_str("1").unwrap(),
     "10000::numeric" == sqlx::types::Decimal::from_str("10000").unwrap(),
     "0.1::numeric" == sqlx::types::Decimal::from_str("0.1").unwrap(),
     "0.01234::numeric" == sqlx::types::Decimal::from_str("0.01234").unwrap(),
     "12.34::numeric" == sqlx::types::Decimal::from_str("12.34").unwrap(),
     "12345.6789::numeric" == sqlx::types::Decimal::from_str("12345.6789").unwrap(),
+    "12000000000::numeric" == sqlx::types::Decimal::from_str("12000000000").unwrap(),
+    "-12000000000::numeric" == sqlx::types::Decimal::from_str("-12000000000").unwrap(),
+    "100000000::numeric" == sqlx::types::Decimal::from_str("100000000").unwrap(),
+    "0.00001::numeric" == sqlx::types::Decimal::from_str("0.00001").unwrap(),
+    "0.00000001::numeric" == sqlx::types::Decimal::from_str("0.00000001").unwrap(),
+    "1.00001::numeric" == sqlx::types::Decimal::from_str("1.00001").unwrap(),
+    "0.0000000000000000000000000001::numeric" == sqlx::types::Decimal::from_str("0.0000000000000000000000000001").unwrap(),
     // https://github.com/launchbadge/sqlx/issues/666#issuecomment-683872154
     "17.905625985174584660842500258::numeric" == sqlx::types::Decimal::from_str("17.905625985174584660842500258").unwrap(),
     "-17.905625985174584660842500258::numeric" == sqlx::types::Decimal::from_str("-17.905625985174584660842500258").unwrap(),
 ));
 
+#[cfg(feature = "rust_decimal")]
+test_type!(decimal_vec<Vec<Option<sqlx::types::Decimal>>>(Postgres,
+    "array[12.34, NULL, -0.005]::numeric[]" == vec![
+        Some(sqlx::types::Decimal::from_str("12.34").unwrap()),
+        None,
+        Some(sqlx::types::Decimal::from_str("-0.005").unwrap()),
+    ],
+));
+
+#[sqlx_macros::test]
+async fn test_unsupported_arrays_are_an_error() -> anyhow::Result<()> {
+    use sqlx::{Executor, Row};
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    for (sql, expected) in [
+        ("SELECT '{{1,2},{3,4}}'::int4[]", "one-dimensional"),
+        ("SELECT '[0:1]={1,2}'::int4[]", "starting at one"),
+    ] {
+        // binary format
+        let err = sqlx::query_scalar::<_, Vec<i32>>(sql)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(expected), "{sql}: {err}");
+
+        // text format
+        let row = conn.fetch_one(sql).await?;
+        let err = row.try_get::<Vec<i32>, _>(0).unwrap_err();
+        assert!(err.to_string().contains(expected), "{sql}: {err}");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "rust_decimal")]
+#[sqlx_macros::test]
+async fn test_decimal_vec_with_nan() -> anyhow::Result<()> {
+    use sqlx::{Executor, Row};
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    // `Decimal` has no NaN, so both the binary and the text format refuse it
+    let err =
+        sqlx::query_scalar::<_, Vec<sqlx::types::Decimal>>("SELECT array[1, 'NaN']::numeric[]")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+    assert!(err.to_string().contains("does not support NaN"), "{err}");
+
+    let row = conn.fetch_one("SELECT array[1, 'NaN']::numeric[]").await?;
+    let err = row.try_get::<Vec<sqlx::types::Decimal>, _>(0).unwrap_err();
+    assert!(err.to_string().contains("does not support NaN"), "{err}");
+
+    Ok(())
+}
+
+#[cfg(feature = "rust_decimal")]
+#[sqlx_macros::test]
+async fn test_decimal_keeps_scale() -> anyhow::Result<()> {
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    for value in ["0.00", "1.50", "-0.0001"] {
+        let text: String = sqlx::query_scalar("SELECT $1::text")
+            .bind(sqlx::types::Decimal::from_str(value)?)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(text, value);
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "rust_decimal")]
 test_type!(numrange_decimal<PgRange<sqlx::types::Decimal>>(Postgres,
     "'(1.3,2.4)'::numrange" == PgRange::from(
@@ -519,7 +833,7 @@ test_type!(int4range<PgRange<i32>>(Postgres,
     "'[1,2]'::int4range" == PgRange::from((INC1, EXC3)),
 ));
 
-test_prepared_type!(interval<PgInterval>(
+test_type!(interval<PgInterval>(
     Postgres,
     "INTERVAL '1h'"
         == PgInterval {
@@ -547,12 +861,49 @@ test_prepared_type!(interval<PgInterval>(
         },
 ));
 
+#[sqlx_macros::test]
+async fn test_interval_text_in_every_style() -> anyhow::Result<()> {
+    use sqlx::{Executor, Row};
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    let expected = PgInterval {
+        months: 0,
+        days: 1,
+        microseconds: 9_000_000_000,
+    };
+
+    for (style, text) in [
+        ("postgres", "1 day 02:30:00"),
+        ("postgres_verbose", "@ 1 day 2 hours 30 mins"),
+        ("sql_standard", "1 2:30:00"),
+        ("iso_8601", "P1DT2H30M"),
+    ] {
+        conn.execute(&*format!("SET IntervalStyle = {style}"))
+            .await?;
+
+        // unprepared queries return intervals in the text format
+        let row = conn
+            .fetch_one("SELECT INTERVAL '1 day 2 hours 30 minutes', INTERVAL '1 day 2 hours 30 minutes'::text")
+            .await?;
+
+        assert_eq!(row.try_get::<String, _>(1)?, text);
+        assert_eq!(row.try_get::<PgInterval, _>(0)?, expected, "{style}");
+    }
+
+    Ok(())
+}
+
 test_prepared_type!(money<PgMoney>(Postgres, "123.45::money" == PgMoney(12345)));
 
 test_prepared_type!(money_vec<Vec<PgMoney>>(Postgres,
     "array[123.45,420.00,666.66]::money[]" == vec![PgMoney(12345), PgMoney(42000), PgMoney(66666)],
 ));
 
+test_prepared_type!(money_vec_with_null<Vec<Option<PgMoney>>>(Postgres,
+    "array[123.45,NULL,-0.01]::money[]" == vec![Some(PgMoney(12345)), None, Some(PgMoney(-1))],
+));
+
 test_prepared_type!(citext_array<Vec<PgCiText>>(Postgres,
     "array['one','two','three']::citext[]" == vec![
         PgCiText("one".to_string()),
@@ -579,3 +930,236 @@ test_type!(ltree_vec<Vec<sqlx::postgres::types::PgLTree>>(Postgres,
             sqlx::postgres::types::PgLTree::from_iter(["Alpha", "Beta", "Delta", "Gamma"]).unwrap()
         ]
 ));
+
+// `xml` has no equality operator, so compare the text representations
+test_type!(xml<PgXml>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0}, $2",
+    "'<a>b</a>'::xml" == PgXml("<a>b</a>".to_string()),
+    "'<book><title>Manual</title></book>'::xml"
+        == PgXml("<book><title>Manual</title></book>".to_string()),
+));
+
+test_type!(xml_vec<Vec<PgXml>>(Postgres,
+    "SELECT ({0}::text[] is not distinct from $1::text[])::int4, {0}, $2",
+    "array['<a>b</a>', '<c/>']::xml[]" == vec![
+        PgXml("<a>b</a>".to_string()),
+        PgXml("<c/>".to_string()),
+    ],
+));
+
+#[sqlx_macros::test]
+async fn test_null_int2_array_element_requires_option() -> anyhow::Result<()> {
+    use sqlx::{Executor, Row};
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    // prepared queries return binary arrays
+    let v: Vec<Option<i16>> = sqlx::query_scalar("SELECT $1::int2[]")
+        .bind(vec![Some(1_i16), None, Some(-3)])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v, vec![Some(1_i16), None, Some(-3)]);
+
+    let res: Result<Vec<i16>, _> = sqlx::query_scalar("SELECT '{1,NULL,3}'::int2[]")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+    // unprepared queries return text arrays
+    let row = conn.fetch_one("SELECT '{1,NULL,3}'::int2[]").await?;
+
+    let v: Vec<Option<i16>> = row.try_get(0)?;
+    assert_eq!(v, vec![Some(1_i16), None, Some(3)]);
+
+    let res: Result<Vec<i16>, _> = row.try_get(0);
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_quoted_null_text_array_element_is_a_string() -> anyhow::Result<()> {
+    use sqlx::{Executor, Row};
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    // only an unquoted `NULL` is a NULL element of a text array
+    let row = conn.fetch_one(r#"SELECT '{"NULL",NULL}'::text[]"#).await?;
+
+    let v: Vec<Option<String>> = row.try_get(0)?;
+    assert_eq!(v, vec![Some("NULL".to_owned()), None]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_xml_insert_and_select() -> anyhow::Result<()> {
+    use sqlx::Executor;
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE xml_docs (id INT4 PRIMARY KEY, doc XML NOT NULL)")
+        .await?;
+
+    let doc = PgXml("<book><title>Manual</title><chapter>1</chapter></book>".to_string());
+
+    sqlx::query("INSERT INTO xml_docs (id, doc) VALUES (1, $1)")
+        .bind(&doc)
+        .execute(&mut conn)
+        .await?;
+
+    let stored: PgXml = sqlx::query_scalar("SELECT doc FROM xml_docs WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(stored, doc);
+
+    // the server rejects documents that are not well-formed
+    let res = sqlx::query("INSERT INTO xml_docs (id, doc) VALUES (2, $1)")
+        .bind(PgXml("<book><title>Manual</book>".to_string()))
+        .execute(&mut conn)
+        .await;
+
+    let err = res.unwrap_err();
+    assert_eq!(
+        err.as_database_error().unwrap().code().as_deref(),
+        Some("2200N")
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_bytea_reader_hashes_without_buffering() -> anyhow::Result<()> {
+    use sha2::{Digest, Sha256};
+    use sqlx::{Executor, Row};
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    // 4 MiB of bytes that aren't all the same
+    let data: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let expected = Sha256::digest(&data);
+
+    // binary format, as used by prepared queries
+    let row = sqlx::query("SELECT $1::bytea")
+        .bind(&data)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let mut reader: PgByteaReader<'_> = row.try_get(0)?;
+    assert_eq!(reader.remaining_len(), data.len());
+
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    assert_eq!(hasher.finalize(), expected);
+
+    // hex text format, as used by simple queries
+    let row = conn
+        .fetch_one("SELECT decode(repeat('0123456789abcdef', 262144), 'hex')")
+        .await?;
+
+    let mut reader: PgByteaReader<'_> = row.try_get(0)?;
+
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    assert_eq!(
+        hasher.finalize(),
+        Sha256::digest(b"\x01\x23\x45\x67\x89\xab\xcd\xef".repeat(262144))
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_bytea_round_trip_all_byte_values() -> anyhow::Result<()> {
+    use sqlx::{Executor, Row};
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE blobs (id INT4 PRIMARY KEY, data BYTEA NOT NULL)")
+        .await?;
+
+    // every byte value, including 0x00 and the high bytes
+    let data: Vec<u8> = (0..=255).collect();
+
+    sqlx::query("INSERT INTO blobs (id, data) VALUES (1, $1), (2, $2)")
+        .bind(&data)
+        .bind(Vec::<u8>::new())
+        .execute(&mut conn)
+        .await?;
+
+    // binary format
+    let row = sqlx::query("SELECT data FROM blobs WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row.try_get::<Vec<u8>, _>(0)?, data);
+    assert_eq!(row.try_get::<&[u8], _>(0)?, &data[..]);
+
+    let empty: Vec<u8> = sqlx::query_scalar("SELECT data FROM blobs WHERE id = 2")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(empty.is_empty());
+
+    // hex text format
+    let row = conn
+        .fetch_one("SELECT data, length(data) FROM blobs WHERE id = 1")
+        .await?;
+    assert_eq!(row.try_get::<Vec<u8>, _>(0)?, data);
+    assert_eq!(row.try_get::<i32, _>(1)?, 256);
+
+    let row = conn
+        .fetch_one("SELECT data FROM blobs WHERE id = 2")
+        .await?;
+    assert!(row.try_get::<Vec<u8>, _>(0)?.is_empty());
+
+    Ok(())
+}
+
+test_type!(byte_vec_array<Vec<Vec<u8>>>(Postgres,
+    "array[E'\\\\xDEADBEEF', E'\\\\x', E'\\\\x00ff']::bytea[]"
+        == vec![vec![0xDE_u8, 0xAD, 0xBE, 0xEF], vec![], vec![0x00, 0xFF]],
+    "array[]::bytea[]" == Vec::<Vec<u8>>::new(),
+));
+
+#[sqlx_macros::test]
+async fn test_bytea_array_round_trip_with_null() -> anyhow::Result<()> {
+    use sqlx::Executor;
+
+    let mut conn = sqlx_test::new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE blob_lists (id INT4 PRIMARY KEY, blobs BYTEA[] NOT NULL)")
+        .await?;
+
+    let blobs: Vec<Option<Vec<u8>>> =
+        vec![Some(vec![0x00, 0x01, 0xFF]), None, Some(b"sqlx".to_vec())];
+
+    sqlx::query("INSERT INTO blob_lists (id, blobs) VALUES (1, $1)")
+        .bind(&blobs)
+        .execute(&mut conn)
+        .await?;
+
+    let stored: Vec<Option<Vec<u8>>> =
+        sqlx::query_scalar("SELECT blobs FROM blob_lists WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(stored, blobs);
+
+    let (len, null_count): (i32, i64) = sqlx::query_as(
+        "SELECT array_length(blobs, 1), (SELECT count(*) FROM unnest(blobs) b WHERE b IS NULL) \
+         FROM blob_lists WHERE id = 1",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!((len, null_count), (3, 1));
+
+    // without `Option`, the NULL element can't be decoded
+    let res: Result<Vec<Vec<u8>>, _> =
+        sqlx::query_scalar("SELECT blobs FROM blob_lists WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await;
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+    Ok(())
+}
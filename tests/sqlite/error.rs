@@ -49,6 +49,7 @@ async fn it_fails_with_not_null_violation() -> anyhow::Result<()> {
     let err = err.into_database_error().unwrap();
 
     assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+    assert!(err.is_not_null_violation());
 
     Ok(())
 }
@@ -99,9 +99,12 @@ struct RawAccount {
 async fn test_query_as_raw() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
 
-    let account = sqlx::query_as!(RawAccount, "SELECT id, name, is_active from accounts")
-        .fetch_one(&mut conn)
-        .await?;
+    let account = sqlx::query_as!(
+        RawAccount,
+        "SELECT id, name, is_active from accounts WHERE id = 1"
+    )
+    .fetch_one(&mut conn)
+    .await?;
 
     assert_eq!(account.id, 1);
     assert_eq!(account.name, "Herp Derpinson");
@@ -179,7 +182,7 @@ async fn test_query_scalar() -> anyhow::Result<()> {
 async fn macro_select_from_view() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
 
-    let account = sqlx::query!("SELECT id, name, is_active from accounts_view")
+    let account = sqlx::query!("SELECT id, name, is_active from accounts_view WHERE id = 1")
         .fetch_one(&mut conn)
         .await?;
 
@@ -49,13 +49,13 @@ async fn it_fetches_and_inflates_row() -> anyhow::Result<()> {
     // these rows should be immediately inflated
 
     let row1 = conn
-        .fetch_one("SELECT 15 UNION SELECT 51 UNION SELECT 39")
+        .fetch_one("SELECT 15 UNION SELECT 51 UNION SELECT 39 LIMIT 1")
         .await?;
 
     assert_eq!(row1.get::<i32, _>(0), 15);
 
     let row2 = conn
-        .fetch_one("SELECT 15 UNION SELECT 51 UNION SELECT 39")
+        .fetch_one("SELECT 15 UNION SELECT 51 UNION SELECT 39 LIMIT 1")
         .await?;
 
     assert_eq!(row1.get::<i32, _>(0), 15);
@@ -65,13 +65,13 @@ async fn it_fetches_and_inflates_row() -> anyhow::Result<()> {
     // and fetch the first row a few times
 
     let row1 = conn
-        .fetch_one(query("SELECT 15 UNION SELECT 51 UNION SELECT 39"))
+        .fetch_one(query("SELECT 15 UNION SELECT 51 UNION SELECT 39 LIMIT 1"))
         .await?;
 
     assert_eq!(row1.get::<i32, _>(0), 15);
 
     let row2 = conn
-        .fetch_one(query("SELECT 15 UNION SELECT 51 UNION SELECT 39"))
+        .fetch_one(query("SELECT 15 UNION SELECT 51 UNION SELECT 39 LIMIT 1"))
         .await?;
 
     assert_eq!(row1.get::<i32, _>(0), 15);
@@ -95,6 +95,77 @@ async fn it_maths() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_enforces_row_count_for_fetch_one_and_fetch_optional() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    const QUERY: &str =
+        "SELECT v FROM (SELECT 1 AS v UNION ALL SELECT 2 UNION ALL SELECT 2) t WHERE v = ?1";
+
+    // zero rows
+    let res: Result<i32, _> = sqlx::query_scalar(QUERY)
+        .bind(0_i32)
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::RowNotFound)));
+
+    let v: Option<i32> = sqlx::query_scalar(QUERY)
+        .bind(0_i32)
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(v, None);
+
+    // one row
+    let v: i32 = sqlx::query_scalar(QUERY)
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 1);
+
+    let v: Option<i32> = sqlx::query_scalar(QUERY)
+        .bind(1_i32)
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(v, Some(1));
+
+    // two rows
+    let res: Result<i32, _> = sqlx::query_scalar(QUERY)
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    let res: Result<Option<i32>, _> = sqlx::query_scalar(QUERY)
+        .bind(2_i32)
+        .fetch_optional(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::TooManyRows)));
+
+    // the rest of the result set was discarded
+    let v: i32 = sqlx::query_scalar(QUERY)
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(v, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_names_the_column_in_decode_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn.fetch_one("SELECT 'text' AS name").await?;
+
+    let err = row.try_get::<i32, _>(0).unwrap_err();
+    assert!(matches!(&err, sqlx::Error::ColumnDecode { index, .. } if index == "0 (\"name\")"));
+
+    let err = row.try_get::<i32, _>("name").unwrap_err();
+    assert!(matches!(&err, sqlx::Error::ColumnDecode { index, .. } if index == "\"name\""));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_bind_multiple_statements_multiple_values() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -128,6 +199,27 @@ async fn test_bind_multiple_statements_same_value() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_streams_rows_and_can_stop_early() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // far more rows than could be buffered up front in reasonable time
+    let sql = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) SELECT i FROM n";
+
+    {
+        let mut rows = sqlx::query_scalar::<_, i64>(sql).fetch(&mut conn);
+
+        for i in 1..=1000 {
+            assert_eq!(rows.try_next().await?, Some(i));
+        }
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_describe_with_pragma() -> anyhow::Result<()> {
     use sqlx::{Decode, TypeInfo, ValueRef};
@@ -430,7 +522,7 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL COLLATE
         .await?;
 
     let row: SqliteRow = conn
-        .fetch_one("SELECT name FROM users ORDER BY name ASC")
+        .fetch_one("SELECT name FROM users ORDER BY name ASC LIMIT 1")
         .await?;
     let name: &str = row.try_get(0)?;
 
@@ -0,0 +1,3 @@
+fn main() {
+    let _query = sqlx::query!("select 1 as id, 2 as id");
+}